@@ -1,96 +1,471 @@
 use super::*;
 
 use oauth2::basic::BasicClient as Client;
+use oauth2::basic::BasicTokenResponse;
 use oauth2::reqwest::async_http_client;
-use oauth2::{AuthUrl, TokenUrl};
+use oauth2::{AuthUrl, RedirectUrl, TokenUrl};
+use oauth2::{AuthorizationCode, CsrfToken, Scope};
 use oauth2::{ClientId, ClientSecret};
+use oauth2::{PkceCodeChallenge, PkceCodeVerifier};
 use oauth2::{RefreshToken, TokenResponse};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+use std::sync::RwLock as SyncRwLock;
+use std::sync::Weak;
+
+use tokio::sync::Notify;
+use tokio::time::{sleep, timeout};
+
+/// How long to wait before retrying a background refresh that failed.
+const REFRESH_RETRY_DELAY: StdDuration = StdDuration::from_secs(30);
+
+#[derive(Debug, Clone, Builder)]
 pub struct AuthenticatorConfig {
+    /// The name under which the provider's tokens are stored (i.e.
+    /// `spotify`).
+    #[builder(setter(into))]
+    pub provider: String,
+
     pub client_id: String,
     pub client_secret: String,
     pub token_endpoint: Url,
-    pub refresh_token: String,
+
+    /// The endpoint that users are sent to in order to authorize access,
+    /// which is required to authorize with an authorization code.
+    #[builder(default, setter(into))]
+    pub authorize_endpoint: Option<Url>,
+
+    /// The URL that users are redirected to after authorizing access.
+    #[builder(default, setter(into))]
+    pub redirect_url: Option<Url>,
+
+    #[builder(default, setter(into))]
+    pub refresh_token: Option<String>,
+
+    /// How long before an access token expires to refresh it in the
+    /// background, so that requests rarely have to wait for a refresh.
+    #[builder(default = Duration::minutes(5))]
+    pub refresh_leeway: Duration,
 }
 
+/// Manages the OAuth tokens for a provider.
+///
+/// Access tokens are refreshed in the background before they expire, or
+/// on demand if they're rejected, with concurrent refreshes coalesced into
+/// one. If the provider issues a new refresh token, it replaces the old one.
+/// With a `TokenStore`, tokens are persisted whenever they change.
 #[derive(Debug)]
 pub struct Authenticator {
+    provider: String,
     client: Client,
-    refresh_token: String,
+    refresh_token: AsyncRwLock<Option<String>>,
     current_access_token: AsyncMutex<Option<AccessToken>>,
+    store: SyncRwLock<Option<Arc<dyn TokenStore>>>,
     clock_skew_leeway: Duration,
+    refresh_leeway: Duration,
+
+    /// Wakes the background refresher when the access token changes.
+    token_changed: Arc<Notify>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AccessToken {
     pub token: String,
     pub expires_at: Option<DateTime>,
 }
 
+impl AccessToken {
+    fn from_response(response: &BasicTokenResponse) -> Result<Self> {
+        let token = response.access_token().secret().to_owned();
+        let expires_at = match response.expires_in() {
+            Some(expires_in) => {
+                let expires_in = Duration::from_std(expires_in)
+                    .context("invalid token lifetime")?;
+                let expires_at = now()
+                    .checked_add_signed(expires_in)
+                    .context("token expiry out of range")?;
+                Some(expires_at)
+            }
+            None => None,
+        };
+        let token = AccessToken { token, expires_at };
+        Ok(token)
+    }
+
+    /// Whether the token expires within `leeway` from now.
+    fn expires_within(&self, leeway: Duration) -> bool {
+        match self.expires_at {
+            Some(expires_at) => now() >= expires_at - leeway,
+            None => false,
+        }
+    }
+}
+
+/// The tokens for a provider, as persisted by a `TokenStore`.
+#[derive(Debug, Clone)]
+pub struct StoredTokens {
+    pub refresh_token: String,
+    pub access_token: Option<AccessToken>,
+}
+
+/// Persists the tokens for each provider, so that they survive restarts.
+#[async_trait]
+pub trait TokenStore: Debug + Send + Sync {
+    async fn load(&self, provider: &str) -> Result<Option<StoredTokens>>;
+    async fn save(&self, provider: &str, tokens: StoredTokens) -> Result<()>;
+}
+
+/// A request for a user to authorize access, using an authorization code
+/// with PKCE.
+#[derive(Debug, Clone)]
+pub struct AuthorizationRequest {
+    /// The URL to send the user to.
+    pub url: Url,
+
+    /// A random value that must be returned unchanged to the redirect URL.
+    pub state: String,
+
+    /// The PKCE verifier that must accompany the authorization code, which
+    /// should be kept secret until then.
+    pub verifier: String,
+}
+
 impl Authenticator {
-    pub fn new(config: AuthenticatorConfig) -> Self {
+    /// Build an authenticator, which refreshes its access token in the
+    /// background for as long as it's alive.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn new(config: AuthenticatorConfig) -> Result<Arc<Self>> {
         let AuthenticatorConfig {
+            provider,
             client_id,
             client_secret,
             token_endpoint,
+            authorize_endpoint,
+            redirect_url,
             refresh_token,
+            refresh_leeway,
         } = config;
         let client_id = ClientId::new(client_id);
         let client_secret = ClientSecret::new(client_secret);
-        let auth_url = AuthUrl::new("https://example.com".to_owned()).unwrap();
-        let token_url = TokenUrl::new(token_endpoint.to_string()).unwrap();
-        let client = Client::new(
-            client_id,
-            Some(client_secret),
-            auth_url,
-            Some(token_url),
-        );
-        Authenticator {
+        let auth_url = {
+            // The client always needs an authorization URL, even if it's
+            // only used to refresh tokens.
+            let url = authorize_endpoint
+                .map(|endpoint| endpoint.to_string())
+                .unwrap_or_else(|| token_endpoint.to_string());
+            AuthUrl::new(url).context("invalid authorize endpoint")?
+        };
+        let token_url = TokenUrl::new(token_endpoint.to_string())
+            .context("invalid token endpoint")?;
+        let client = {
+            let client = Client::new(
+                client_id,
+                Some(client_secret),
+                auth_url,
+                Some(token_url),
+            );
+            match redirect_url {
+                Some(url) => {
+                    let url = RedirectUrl::new(url.to_string())
+                        .context("invalid redirect URL")?;
+                    client.set_redirect_uri(url)
+                }
+                None => client,
+            }
+        };
+        let token_changed = Arc::new(Notify::new());
+        let authenticator = Arc::new(Authenticator {
+            provider,
             client,
-            refresh_token,
+            refresh_token: AsyncRwLock::new(refresh_token),
             current_access_token: default(),
+            store: default(),
             clock_skew_leeway: Duration::seconds(60),
+            refresh_leeway,
+            token_changed: token_changed.clone(),
+        });
+        spawn(Self::refresh_in_background(
+            Arc::downgrade(&authenticator),
+            token_changed,
+        ));
+        Ok(authenticator)
+    }
+
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    /// Persist tokens to `store` from now on, starting with any tokens it
+    /// already has for this provider (which replace the current ones).
+    pub async fn set_store(&self, store: Arc<dyn TokenStore>) -> Result<()> {
+        let tokens = store
+            .load(&self.provider)
+            .await
+            .context("failed to load tokens")?;
+        let mut current_access_token = self.current_access_token.lock().await;
+        *self.store.write().unwrap() = Some(store);
+        if let Some(tokens) = tokens {
+            let StoredTokens {
+                refresh_token,
+                access_token,
+            } = tokens;
+            *self.refresh_token.write().await = Some(refresh_token);
+            *current_access_token = access_token
+                .filter(|token| !token.expires_within(self.clock_skew_leeway));
         }
+        Ok(())
+    }
+
+    /// Whether tokens are being persisted to a `TokenStore`.
+    pub fn has_store(&self) -> bool {
+        self.store.read().unwrap().is_some()
     }
 
     pub async fn access_token(&self) -> Result<AccessToken> {
-        let mut current_access_token = self.current_access_token.lock().await;
+        let token = {
+            let mut current_access_token =
+                self.current_access_token.lock().await;
+            if let Some(token) = current_access_token.as_ref() {
+                if !token.expires_within(self.clock_skew_leeway) {
+                    return Ok(token.clone());
+                }
+            }
+            self.refresh(&mut current_access_token).await?
+        };
+        self.save_refreshed(&token).await;
+        Ok(token)
+    }
 
-        if let Some(token) = current_access_token.as_ref() {
-            let AccessToken { expires_at, .. } = token;
-            let is_valid = match expires_at {
-                Some(expires_at) => {
-                    let expires_at = (*expires_at) - self.clock_skew_leeway;
-                    now() < expires_at
+    /// Get a new access token to replace one that was rejected.
+    ///
+    /// If the rejected token was already replaced (i.e. by a concurrent
+    /// request that was also rejected), its replacement is returned rather
+    /// than refreshing again.
+    pub async fn refresh_access_token(
+        &self,
+        rejected: &AccessToken,
+    ) -> Result<AccessToken> {
+        let token = {
+            let mut current_access_token =
+                self.current_access_token.lock().await;
+            if let Some(token) = current_access_token.as_ref() {
+                if token != rejected {
+                    return Ok(token.clone());
+                }
+            }
+            self.refresh(&mut current_access_token).await?
+        };
+        self.save_refreshed(&token).await;
+        Ok(token)
+    }
+
+    /// Exchange the refresh token for a new access token, which replaces
+    /// `current_access_token`.
+    ///
+    /// Callers must hold the lock on `current_access_token`, so that only
+    /// one refresh happens at a time, and should save the new token once
+    /// they've released it.
+    async fn refresh(
+        &self,
+        current_access_token: &mut Option<AccessToken>,
+    ) -> Result<AccessToken> {
+        let refresh_token = self.refresh_token.read().await.clone();
+        let refresh_token = refresh_token.context("not authorized")?;
+        let response = self
+            .client
+            .exchange_refresh_token(&RefreshToken::new(refresh_token.clone()))
+            .request_async(async_http_client)
+            .await?;
+        let token = AccessToken::from_response(&response)?;
+        debug!(provider = %self.provider, "refreshed access token");
+
+        // Some providers rotate refresh tokens, in which case the old one
+        // may stop working
+        if let Some(refresh_token) = response.refresh_token() {
+            let refresh_token = refresh_token.secret().to_owned();
+            *self.refresh_token.write().await = Some(refresh_token);
+        }
+
+        *current_access_token = Some(token.clone());
+        self.token_changed.notify_one();
+        Ok(token)
+    }
+
+    /// Refresh the access token in the background shortly before it
+    /// expires, until the authenticator is dropped.
+    async fn refresh_in_background(
+        authenticator: Weak<Self>,
+        token_changed: Arc<Notify>,
+    ) {
+        loop {
+            let delay = match authenticator.upgrade() {
+                Some(authenticator) => authenticator.refresh_delay().await,
+                None => return,
+            };
+
+            // Wait until the token is due to be refreshed, starting over if
+            // it's replaced in the meantime
+            let changed = token_changed.notified();
+            match delay {
+                Some(delay) => {
+                    if timeout(delay, changed).await.is_ok() {
+                        continue;
+                    }
                 }
-                None => true,
+                None => {
+                    changed.await;
+                    continue;
+                }
+            }
+
+            let authenticator = match authenticator.upgrade() {
+                Some(authenticator) => authenticator,
+                None => return,
             };
-            if is_valid {
-                return Ok(token.clone());
+            if let Err(error) = authenticator.refresh_if_expiring().await {
+                error!(
+                    provider = %authenticator.provider,
+                    error = %format!("{:#}", error),
+                    "failed to refresh access token",
+                );
+                drop(authenticator);
+                sleep(REFRESH_RETRY_DELAY).await;
             }
         }
+    }
+
+    /// How long until the access token is due to be refreshed, if it
+    /// expires at all.
+    async fn refresh_delay(&self) -> Option<StdDuration> {
+        let current_access_token = self.current_access_token.lock().await;
+        let expires_at = current_access_token.as_ref()?.expires_at?;
+        let delay = (expires_at - self.refresh_leeway) - now();
+        Some(delay.to_std().unwrap_or_default())
+    }
 
+    async fn refresh_if_expiring(&self) -> Result<()> {
         let token = {
-            let response = {
-                let refresh_token =
-                    RefreshToken::new(self.refresh_token.clone());
-                self.client
-                    .exchange_refresh_token(&refresh_token)
-                    .request_async(async_http_client)
-                    .await?
+            let mut current_access_token =
+                self.current_access_token.lock().await;
+            let is_expiring = match current_access_token.as_ref() {
+                Some(token) => token.expires_within(self.refresh_leeway),
+                None => false,
             };
-            let token = {
-                let token = response.access_token().secret().to_owned();
-                let expires_at = response.expires_in().map(|expires_in| {
-                    let expires_in = Duration::from_std(expires_in).unwrap();
-                    now() + expires_in
-                });
-                AccessToken { token, expires_at }
-            };
-            current_access_token.insert(token).clone()
+            if !is_expiring {
+                return Ok(());
+            }
+            self.refresh(&mut current_access_token).await?
         };
+        self.save_refreshed(&token).await;
+        Ok(())
+    }
 
-        Ok(token)
+    /// Persist a refreshed access token, along with the current refresh
+    /// token.
+    ///
+    /// Failures are logged rather than returned, since the token is already
+    /// in use.
+    async fn save_refreshed(&self, access_token: &AccessToken) {
+        let refresh_token = match self.refresh_token.read().await.clone() {
+            Some(refresh_token) => refresh_token,
+            None => return,
+        };
+        let tokens = StoredTokens {
+            refresh_token,
+            access_token: Some(access_token.clone()),
+        };
+        if let Err(error) = self.save(tokens).await {
+            error!(
+                provider = %self.provider,
+                error = %format!("{:#}", error),
+                "failed to save tokens",
+            );
+        }
+    }
+
+    /// Persist tokens to the store, if there is one.
+    async fn save(&self, tokens: StoredTokens) -> Result<()> {
+        let store = match self.store.read().unwrap().clone() {
+            Some(store) => store,
+            None => return Ok(()),
+        };
+        store
+            .save(&self.provider, tokens)
+            .await
+            .context("failed to save tokens")
+    }
+
+    /// Start authorizing access with the given scopes.
+    pub fn authorize_url(&self, scopes: &[&str]) -> AuthorizationRequest {
+        let (challenge, verifier) = PkceCodeChallenge::new_random_sha256();
+        let (url, state) = self
+            .client
+            .authorize_url(CsrfToken::new_random)
+            .add_scopes(
+                scopes.iter().map(|scope| Scope::new(scope.to_string())),
+            )
+            .set_pkce_challenge(challenge)
+            .url();
+        AuthorizationRequest {
+            url,
+            state: state.secret().to_owned(),
+            verifier: verifier.secret().to_owned(),
+        }
+    }
+
+    /// Exchange an authorization code (and its PKCE verifier) for tokens,
+    /// and use (and persist) them from now on.
+    ///
+    /// Fails if the tokens can't be persisted, since they'd otherwise be lost
+    /// on restart.
+    pub async fn authorize(&self, code: &str, verifier: &str) -> Result<()> {
+        let tokens = {
+            let mut current_access_token =
+                self.current_access_token.lock().await;
+            let response = self
+                .client
+                .exchange_code(AuthorizationCode::new(code.to_owned()))
+                .set_pkce_verifier(PkceCodeVerifier::new(verifier.to_owned()))
+                .request_async(async_http_client)
+                .await?;
+            let refresh_token = response
+                .refresh_token()
+                .context("missing refresh token")?
+                .secret()
+                .to_owned();
+            let token = AccessToken::from_response(&response)?;
+            *self.refresh_token.write().await = Some(refresh_token.clone());
+            *current_access_token = Some(token.clone());
+            self.token_changed.notify_one();
+            StoredTokens {
+                refresh_token,
+                access_token: Some(token),
+            }
+        };
+        self.save(tokens).await
+    }
+
+    /// Replace the refresh token, i.e. after the user re-authorizes access.
+    ///
+    /// Fails if the refresh token can't be persisted.
+    pub async fn set_refresh_token(&self, refresh_token: String) -> Result<()> {
+        {
+            let mut current_access_token =
+                self.current_access_token.lock().await;
+            *self.refresh_token.write().await = Some(refresh_token.clone());
+            *current_access_token = None;
+        }
+        let tokens = StoredTokens {
+            refresh_token,
+            access_token: None,
+        };
+        self.save(tokens).await
+    }
+}
+
+impl Drop for Authenticator {
+    fn drop(&mut self) {
+        // Wake the background refresher, so that it stops
+        self.token_changed.notify_one();
     }
 }
@@ -0,0 +1,61 @@
+use super::*;
+
+use chacha20poly1305::aead::{Aead, NewAead};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+
+/// The size of a nonce, in bytes.
+const NONCE_SIZE: usize = 24;
+
+/// Encrypts secrets (i.e. refresh tokens) before they're stored.
+#[derive(Derivative, Clone)]
+#[derivative(Debug)]
+pub struct Cipher {
+    #[derivative(Debug = "ignore")]
+    cipher: XChaCha20Poly1305,
+}
+
+impl Cipher {
+    pub fn new(key: &[u8]) -> Result<Self> {
+        ensure!(key.len() == 32, "key must be 32 bytes long");
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(key));
+        Ok(Cipher { cipher })
+    }
+
+    /// Encrypt a secret, and encode it (along with its nonce) as base64.
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let nonce: [u8; NONCE_SIZE] = rand::random();
+        let ciphertext = self
+            .cipher
+            .encrypt(XNonce::from_slice(&nonce), plaintext.as_bytes())
+            .ok()
+            .context("failed to encrypt")?;
+        let mut data = nonce.to_vec();
+        data.extend(ciphertext);
+        Ok(base64::encode(data))
+    }
+
+    /// Decrypt a secret that was encrypted by `encrypt`.
+    pub fn decrypt(&self, encrypted: &str) -> Result<String> {
+        let data = base64::decode(encrypted).context("invalid base64")?;
+        ensure!(data.len() > NONCE_SIZE, "missing nonce");
+        let (nonce, ciphertext) = data.split_at(NONCE_SIZE);
+        let plaintext = self
+            .cipher
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .ok()
+            .context("failed to decrypt")?;
+        let plaintext =
+            String::from_utf8(plaintext).context("plaintext is not UTF-8")?;
+        Ok(plaintext)
+    }
+}
+
+impl FromStr for Cipher {
+    type Err = Error;
+
+    /// Parse a cipher from its base64-encoded key.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = base64::decode(s).context("invalid base64")?;
+        Self::new(&key)
+    }
+}
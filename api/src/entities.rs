@@ -1,3 +1,4 @@
+mod auth_token;
 mod build;
 mod email;
 mod form;
@@ -5,8 +6,11 @@ mod form_response;
 mod handle;
 mod heart_rate;
 mod knowledge_entry;
+mod music_play;
 mod phone;
+mod track_lyrics;
 
+pub use auth_token::*;
 pub use build::*;
 pub use email::*;
 pub use form::*;
@@ -14,7 +18,9 @@ pub use form_response::*;
 pub use handle::*;
 pub use heart_rate::*;
 pub use knowledge_entry::*;
+pub use music_play::*;
 pub use phone::*;
+pub use track_lyrics::*;
 
 use super::*;
 
@@ -32,6 +38,6 @@ use ::bson::{bson, doc, from_document, to_document};
 use ::bson::{Bson, Document};
 
 use services::Services;
-use services::{LyriclyService, ObsidianService, SpotifyService};
+use services::{LyricsService, ObsidianService, SpotifyService};
 
 pub type Context<T = Services> = EntityContext<T>;
@@ -0,0 +1,261 @@
+use super::*;
+
+use auth::{AccessToken, StoredTokens, TokenStore};
+use crypto::Cipher;
+
+pub type AuthTokenId = EntityId<AuthToken>;
+
+/// Credentials for an external provider (i.e. Spotify) that were obtained by
+/// authorizing with it.
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+pub struct AuthToken {
+    #[builder(default, setter(skip))]
+    pub id: AuthTokenId,
+
+    #[builder(default = now(), setter(skip))]
+    pub created_at: DateTime,
+
+    #[builder(default, setter(skip))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime>,
+
+    pub provider: String,
+
+    /// The refresh token, encrypted with a `Cipher`.
+    pub refresh_token: String,
+
+    /// The latest access token, encrypted with a `Cipher`.
+    #[builder(default, setter(into))]
+    pub access_token: Option<String>,
+
+    #[builder(default, setter(into))]
+    pub expires_at: Option<DateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthTokenDocument {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+
+    pub created_at: BsonDateTime,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<BsonDateTime>,
+
+    pub provider: String,
+    pub refresh_token: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_token: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<BsonDateTime>,
+}
+
+impl From<AuthToken> for AuthTokenDocument {
+    fn from(token: AuthToken) -> Self {
+        let AuthToken {
+            id,
+            created_at,
+            updated_at,
+            provider,
+            refresh_token,
+            access_token,
+            expires_at,
+        } = token;
+
+        AuthTokenDocument {
+            id: id.into(),
+            created_at: BsonDateTime::from_chrono(created_at),
+            updated_at: updated_at.map(BsonDateTime::from_chrono),
+            provider,
+            refresh_token,
+            access_token,
+            expires_at: expires_at.map(BsonDateTime::from_chrono),
+        }
+    }
+}
+
+impl From<AuthTokenDocument> for AuthToken {
+    fn from(doc: AuthTokenDocument) -> Self {
+        let AuthTokenDocument {
+            id,
+            created_at,
+            updated_at,
+            provider,
+            refresh_token,
+            access_token,
+            expires_at,
+        } = doc;
+
+        AuthToken {
+            id: id.into(),
+            created_at: created_at.to_chrono(),
+            updated_at: updated_at.map(BsonDateTime::to_chrono),
+            provider,
+            refresh_token,
+            access_token,
+            expires_at: expires_at.map(BsonDateTime::to_chrono),
+        }
+    }
+}
+
+impl AuthToken {
+    /// Find the token for a provider, if one has been stored.
+    pub fn for_provider(provider: &str) -> MaybeFindOneQuery<Self> {
+        Self::find_one({
+            AuthTokenConditions::builder()
+                .provider(provider.to_owned())
+                .build()
+        })
+        .optional()
+    }
+}
+
+impl Object for AuthToken {
+    fn to_document(&self) -> Result<Document> {
+        let doc = AuthTokenDocument::from(self.clone());
+        let doc = to_document(&doc)?;
+        Ok(doc)
+    }
+
+    fn from_document(doc: Document) -> Result<Self> {
+        let doc = from_document::<AuthTokenDocument>(doc)?;
+        let token = Self::from(doc);
+        Ok(token)
+    }
+}
+
+impl Entity for AuthToken {
+    const NAME: &'static str = "AuthToken";
+
+    type Services = Services;
+    type Conditions = AuthTokenConditions;
+    type Sorting = EmptySorting;
+
+    fn id(&self) -> EntityId<Self> {
+        self.id
+    }
+}
+
+impl Updateable for AuthToken {
+    fn as_updateable(&self) -> UpdateableView {
+        let AuthToken { updated_at, .. } = self;
+        UpdateableView { updated_at }
+    }
+
+    fn as_updateable_mut(&mut self) -> UpdateableViewMut {
+        let AuthToken { updated_at, .. } = self;
+        UpdateableViewMut { updated_at }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+pub struct AuthTokenConditions {
+    #[builder(default, setter(into))]
+    pub provider: Option<String>,
+}
+
+impl EntityConditions for AuthTokenConditions {
+    fn to_document(&self) -> Document {
+        let AuthTokenConditions { provider } = self;
+
+        let mut doc = Document::new();
+        if let Some(provider) = provider {
+            doc.insert("provider", provider);
+        }
+
+        doc
+    }
+}
+
+/// Stores tokens as `AuthToken`s, with their secrets encrypted.
+#[derive(Debug, Clone)]
+pub struct AuthTokenStore {
+    services: Services,
+    cipher: Cipher,
+}
+
+impl AuthTokenStore {
+    pub fn new(services: Services, cipher: Cipher) -> Self {
+        Self { services, cipher }
+    }
+}
+
+#[async_trait]
+impl TokenStore for AuthTokenStore {
+    async fn load(&self, provider: &str) -> Result<Option<StoredTokens>> {
+        let AuthTokenStore { services, cipher } = self;
+        let ctx = Context::new(services.clone());
+        let token = AuthToken::for_provider(provider)
+            .load(&ctx)
+            .await
+            .context("failed to load token")?;
+        let token = match token {
+            Some(token) => token,
+            None => return Ok(None),
+        };
+        let AuthToken {
+            refresh_token,
+            access_token,
+            expires_at,
+            ..
+        } = token;
+        let refresh_token = cipher
+            .decrypt(&refresh_token)
+            .context("failed to decrypt refresh token")?;
+        let access_token = access_token
+            .map(|token| cipher.decrypt(&token))
+            .transpose()
+            .context("failed to decrypt access token")?
+            .map(|token| AccessToken { token, expires_at });
+        let tokens = StoredTokens {
+            refresh_token,
+            access_token,
+        };
+        Ok(Some(tokens))
+    }
+
+    async fn save(&self, provider: &str, tokens: StoredTokens) -> Result<()> {
+        let AuthTokenStore { services, cipher } = self;
+        let StoredTokens {
+            refresh_token,
+            access_token,
+        } = tokens;
+        let refresh_token = cipher
+            .encrypt(&refresh_token)
+            .context("failed to encrypt refresh token")?;
+        let (access_token, expires_at) = match access_token {
+            Some(AccessToken { token, expires_at }) => {
+                let token = cipher
+                    .encrypt(&token)
+                    .context("failed to encrypt access token")?;
+                (Some(token), expires_at)
+            }
+            None => (None, None),
+        };
+
+        let ctx = Context::new(services.clone());
+        let token = AuthToken::for_provider(provider)
+            .load(&ctx)
+            .await
+            .context("failed to load token")?;
+        let mut token = match token {
+            Some(mut token) => {
+                token.refresh_token = refresh_token;
+                token.access_token = access_token;
+                token.expires_at = expires_at;
+                token
+            }
+            None => AuthToken::builder()
+                .provider(provider.to_owned())
+                .refresh_token(refresh_token)
+                .access_token(access_token)
+                .expires_at(expires_at)
+                .build(),
+        };
+        token.save(&ctx).await.context("failed to save token")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,365 @@
+use super::*;
+
+use pollers::PlayStore;
+use services::spotify::Album as SpotifyAlbum;
+use services::spotify::Artist as SpotifyArtist;
+use services::spotify::Track as SpotifyTrack;
+
+use serde::de::DeserializeOwned;
+
+/// The collection that `MusicPlay`s are stored in, for aggregations.
+const MUSIC_PLAY_COLLECTION: &str = "musicPlay";
+
+pub type MusicPlayId = EntityId<MusicPlay>;
+
+/// A play of a track on Spotify.
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+pub struct MusicPlay {
+    #[builder(default, setter(skip))]
+    pub id: MusicPlayId,
+
+    pub track: MusicPlayTrack,
+    pub started_at: DateTime,
+
+    /// When the track was last seen playing.
+    pub ended_at: DateTime,
+
+    /// The furthest position reached in the track, in milliseconds.
+    pub progress: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MusicPlayDocument {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+
+    pub track: MusicPlayTrack,
+    pub started_at: BsonDateTime,
+    pub ended_at: BsonDateTime,
+    pub progress: u32,
+}
+
+impl From<MusicPlay> for MusicPlayDocument {
+    fn from(play: MusicPlay) -> Self {
+        let MusicPlay {
+            id,
+            track,
+            started_at,
+            ended_at,
+            progress,
+        } = play;
+
+        MusicPlayDocument {
+            id: id.into(),
+            track,
+            started_at: BsonDateTime::from_chrono(started_at),
+            ended_at: BsonDateTime::from_chrono(ended_at),
+            progress,
+        }
+    }
+}
+
+impl From<MusicPlayDocument> for MusicPlay {
+    fn from(doc: MusicPlayDocument) -> Self {
+        let MusicPlayDocument {
+            id,
+            track,
+            started_at,
+            ended_at,
+            progress,
+        } = doc;
+
+        Self {
+            id: id.into(),
+            track,
+            started_at: started_at.to_chrono(),
+            ended_at: ended_at.to_chrono(),
+            progress,
+        }
+    }
+}
+
+/// A played track, as recorded at the time of the play.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicPlayTrack {
+    /// The track's ID on Spotify.
+    pub id: String,
+
+    pub name: String,
+
+    /// The length of the track, in milliseconds.
+    pub duration: u32,
+
+    pub album: MusicPlayAlbum,
+    pub artists: Vec<MusicPlayArtist>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicPlayAlbum {
+    /// The album's ID on Spotify.
+    pub id: String,
+
+    pub name: String,
+    pub image_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicPlayArtist {
+    /// The artist's ID on Spotify.
+    pub id: String,
+
+    pub name: String,
+}
+
+impl From<SpotifyTrack> for MusicPlayTrack {
+    fn from(track: SpotifyTrack) -> Self {
+        let SpotifyTrack {
+            id,
+            name,
+            duration,
+            album,
+            artists,
+            ..
+        } = track;
+        let SpotifyAlbum {
+            id: album_id,
+            name: album_name,
+            images: album_images,
+            ..
+        } = album;
+        let album = MusicPlayAlbum {
+            id: album_id,
+            name: album_name,
+            image_url: album_images.into_iter().next().map(|image| image.url),
+        };
+        let artists = artists
+            .into_iter()
+            .map(|artist| {
+                let SpotifyArtist { id, name, .. } = artist;
+                MusicPlayArtist { id, name }
+            })
+            .collect();
+        Self {
+            id,
+            name,
+            duration,
+            album,
+            artists,
+        }
+    }
+}
+
+impl Object for MusicPlay {
+    fn to_document(&self) -> Result<Document> {
+        let doc = MusicPlayDocument::from(self.clone());
+        let doc = to_document(&doc)?;
+        Ok(doc)
+    }
+
+    fn from_document(doc: Document) -> Result<Self> {
+        let doc = from_document::<MusicPlayDocument>(doc)?;
+        let play = Self::from(doc);
+        Ok(play)
+    }
+}
+
+impl Entity for MusicPlay {
+    const NAME: &'static str = "MusicPlay";
+
+    type Services = Services;
+    type Conditions = MusicPlayConditions;
+    type Sorting = MusicPlaySorting;
+
+    fn id(&self) -> EntityId<Self> {
+        self.id
+    }
+}
+
+impl MusicPlay {
+    /// Count the plays that match `conditions`, and the total time spent
+    /// listening to them.
+    pub async fn totals(
+        ctx: &Context,
+        conditions: &MusicPlayConditions,
+    ) -> Result<MusicPlayTotals> {
+        let pipeline = vec![
+            doc! { "$match": conditions.to_document() },
+            doc! {
+                "$group": {
+                    "_id": null,
+                    "playCount": { "$sum": 1 },
+                    "listeningTime": { "$sum": "$progress" },
+                },
+            },
+        ];
+        let totals = aggregate_plays(ctx, pipeline)
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+        Ok(totals)
+    }
+
+    /// The most played tracks among the plays that match `conditions`, most
+    /// played first.
+    pub async fn top_tracks(
+        ctx: &Context,
+        conditions: &MusicPlayConditions,
+        limit: u32,
+    ) -> Result<Vec<MusicPlayTrackCount>> {
+        let pipeline = vec![
+            doc! { "$match": conditions.to_document() },
+            doc! {
+                "$group": {
+                    "_id": "$track.id",
+                    "track": { "$last": "$track" },
+                    "playCount": { "$sum": 1 },
+                },
+            },
+            doc! { "$sort": { "playCount": -1, "_id": 1 } },
+            doc! { "$limit": limit },
+        ];
+        aggregate_plays(ctx, pipeline).await
+    }
+
+    /// The most played artists among the plays that match `conditions`,
+    /// most played first.
+    pub async fn top_artists(
+        ctx: &Context,
+        conditions: &MusicPlayConditions,
+        limit: u32,
+    ) -> Result<Vec<MusicPlayArtistCount>> {
+        let pipeline = vec![
+            doc! { "$match": conditions.to_document() },
+            doc! { "$unwind": "$track.artists" },
+            doc! {
+                "$group": {
+                    "_id": "$track.artists.id",
+                    "artist": { "$last": "$track.artists" },
+                    "playCount": { "$sum": 1 },
+                },
+            },
+            doc! { "$sort": { "playCount": -1, "_id": 1 } },
+            doc! { "$limit": limit },
+        ];
+        aggregate_plays(ctx, pipeline).await
+    }
+}
+
+async fn aggregate_plays<T: DeserializeOwned>(
+    ctx: &Context,
+    pipeline: Vec<Document>,
+) -> Result<Vec<T>> {
+    let collection = ctx
+        .services()
+        .database()
+        .collection::<Document>(MUSIC_PLAY_COLLECTION);
+    let docs = collection
+        .aggregate(pipeline, None)
+        .await
+        .context("failed to aggregate plays")?;
+    let docs = docs
+        .try_collect::<Vec<_>>()
+        .await
+        .context("failed to load aggregated plays")?;
+    docs.into_iter()
+        .map(|doc| {
+            from_document(doc).context("failed to decode aggregated plays")
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicPlayTotals {
+    pub play_count: u32,
+
+    /// The total time spent listening, in milliseconds.
+    pub listening_time: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicPlayTrackCount {
+    pub track: MusicPlayTrack,
+    pub play_count: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MusicPlayArtistCount {
+    pub artist: MusicPlayArtist,
+    pub play_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, Builder)]
+pub struct MusicPlayConditions {
+    /// Only include plays that started at or after this time.
+    #[builder(default, setter(into))]
+    pub started_since: Option<DateTime>,
+
+    /// Only include plays that started before this time.
+    #[builder(default, setter(into))]
+    pub started_until: Option<DateTime>,
+}
+
+impl EntityConditions for MusicPlayConditions {
+    fn to_document(&self) -> Document {
+        let MusicPlayConditions {
+            started_since,
+            started_until,
+        } = self;
+        let mut doc = Document::new();
+        let mut started_at = Document::new();
+        if let Some(since) = started_since {
+            started_at.insert("$gte", BsonDateTime::from_chrono(*since));
+        }
+        if let Some(until) = started_until {
+            started_at.insert("$lt", BsonDateTime::from_chrono(*until));
+        }
+        if !started_at.is_empty() {
+            doc.insert("startedAt", started_at);
+        }
+        doc
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MusicPlaySorting {
+    StartedAt(SortingDirection),
+}
+
+impl EntitySorting for MusicPlaySorting {
+    fn to_document(&self) -> Document {
+        use MusicPlaySorting::*;
+        match self {
+            StartedAt(direction) => doc! { "startedAt": direction },
+        }
+    }
+}
+
+/// Stores recorded plays as `MusicPlay` entities.
+#[derive(Debug, Clone)]
+pub struct MusicPlayStore {
+    services: Services,
+}
+
+impl MusicPlayStore {
+    pub fn new(services: Services) -> Self {
+        Self { services }
+    }
+}
+
+#[async_trait]
+impl PlayStore for MusicPlayStore {
+    async fn save(&self, play: &MusicPlay) -> Result<()> {
+        let MusicPlayStore { services } = self;
+        let ctx = Context::new(services.clone());
+        let mut play = play.clone();
+        play.save(&ctx).await.context("failed to save play")
+    }
+}
@@ -0,0 +1,312 @@
+use super::*;
+
+use services::lyrics::Lyrics;
+use services::lyrics::{LyricsKey, LyricsStore, StoredLyrics};
+
+pub type TrackLyricsId = EntityId<TrackLyrics>;
+
+/// The lyrics for a track, as found by a lyrics provider or set by hand.
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+pub struct TrackLyrics {
+    #[builder(default, setter(skip))]
+    pub id: TrackLyricsId,
+
+    #[builder(default = now(), setter(skip))]
+    pub created_at: DateTime,
+
+    #[builder(default, setter(skip))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime>,
+
+    /// The track's ID on Spotify, if the lyrics were stored under one;
+    /// otherwise, they're stored under the track's name.
+    #[builder(default, setter(into))]
+    pub spotify_track_id: Option<String>,
+
+    pub track_name: String,
+    pub artist_name: String,
+
+    /// The lyrics, or `None` if they weren't found.
+    #[builder(default, setter(into))]
+    pub lyrics: Option<Lyrics>,
+
+    /// When to look the lyrics up again, or `None` to keep them for good.
+    #[builder(default, setter(into))]
+    pub expires_at: Option<DateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TrackLyricsDocument {
+    #[serde(rename = "_id")]
+    pub id: ObjectId,
+
+    pub created_at: BsonDateTime,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<BsonDateTime>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spotify_track_id: Option<String>,
+
+    pub track_name: String,
+    pub artist_name: String,
+    pub lyrics: Option<Lyrics>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<BsonDateTime>,
+}
+
+impl From<TrackLyrics> for TrackLyricsDocument {
+    fn from(lyrics: TrackLyrics) -> Self {
+        let TrackLyrics {
+            id,
+            created_at,
+            updated_at,
+            spotify_track_id,
+            track_name,
+            artist_name,
+            lyrics,
+            expires_at,
+        } = lyrics;
+
+        TrackLyricsDocument {
+            id: id.into(),
+            created_at: BsonDateTime::from_chrono(created_at),
+            updated_at: updated_at.map(BsonDateTime::from_chrono),
+            spotify_track_id,
+            track_name,
+            artist_name,
+            lyrics,
+            expires_at: expires_at.map(BsonDateTime::from_chrono),
+        }
+    }
+}
+
+impl From<TrackLyricsDocument> for TrackLyrics {
+    fn from(doc: TrackLyricsDocument) -> Self {
+        let TrackLyricsDocument {
+            id,
+            created_at,
+            updated_at,
+            spotify_track_id,
+            track_name,
+            artist_name,
+            lyrics,
+            expires_at,
+        } = doc;
+
+        TrackLyrics {
+            id: id.into(),
+            created_at: created_at.to_chrono(),
+            updated_at: updated_at.map(BsonDateTime::to_chrono),
+            spotify_track_id,
+            track_name,
+            artist_name,
+            lyrics,
+            expires_at: expires_at.map(BsonDateTime::to_chrono),
+        }
+    }
+}
+
+impl TrackLyrics {
+    /// Find the lyrics stored under a Spotify track ID.
+    pub fn for_spotify_track(
+        spotify_track_id: &str,
+    ) -> MaybeFindOneQuery<Self> {
+        Self::find_one({
+            TrackLyricsConditions::builder()
+                .spotify_track_id(spotify_track_id.to_owned())
+                .build()
+        })
+        .optional()
+    }
+
+    /// Find the lyrics stored under a track's name (rather than its
+    /// Spotify track ID).
+    pub fn for_name(
+        track_name: &str,
+        artist_name: &str,
+    ) -> MaybeFindOneQuery<Self> {
+        Self::find_one({
+            TrackLyricsConditions::builder()
+                .track_name(track_name.to_owned())
+                .artist_name(artist_name.to_owned())
+                .has_spotify_track_id(false)
+                .build()
+        })
+        .optional()
+    }
+}
+
+impl Object for TrackLyrics {
+    fn to_document(&self) -> Result<Document> {
+        let doc = TrackLyricsDocument::from(self.clone());
+        let doc = to_document(&doc)?;
+        Ok(doc)
+    }
+
+    fn from_document(doc: Document) -> Result<Self> {
+        let doc = from_document::<TrackLyricsDocument>(doc)?;
+        let lyrics = Self::from(doc);
+        Ok(lyrics)
+    }
+}
+
+impl Entity for TrackLyrics {
+    const NAME: &'static str = "TrackLyrics";
+
+    type Services = Services;
+    type Conditions = TrackLyricsConditions;
+    type Sorting = EmptySorting;
+
+    fn id(&self) -> EntityId<Self> {
+        self.id
+    }
+}
+
+impl Updateable for TrackLyrics {
+    fn as_updateable(&self) -> UpdateableView {
+        let TrackLyrics { updated_at, .. } = self;
+        UpdateableView { updated_at }
+    }
+
+    fn as_updateable_mut(&mut self) -> UpdateableViewMut {
+        let TrackLyrics { updated_at, .. } = self;
+        UpdateableViewMut { updated_at }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+pub struct TrackLyricsConditions {
+    #[builder(default, setter(into))]
+    pub spotify_track_id: Option<String>,
+
+    #[builder(default, setter(into))]
+    pub track_name: Option<String>,
+
+    #[builder(default, setter(into))]
+    pub artist_name: Option<String>,
+
+    /// Only include lyrics that were (or weren't) stored under a Spotify
+    /// track ID.
+    #[builder(default, setter(into))]
+    pub has_spotify_track_id: Option<bool>,
+}
+
+impl EntityConditions for TrackLyricsConditions {
+    fn to_document(&self) -> Document {
+        let TrackLyricsConditions {
+            spotify_track_id,
+            track_name,
+            artist_name,
+            has_spotify_track_id,
+        } = self;
+
+        let mut doc = Document::new();
+        if let Some(id) = spotify_track_id {
+            doc.insert("spotifyTrackId", id);
+        } else if let Some(exists) = has_spotify_track_id {
+            doc.insert("spotifyTrackId", doc! { "$exists": exists });
+        }
+        if let Some(name) = track_name {
+            doc.insert("trackName", name);
+        }
+        if let Some(name) = artist_name {
+            doc.insert("artistName", name);
+        }
+
+        doc
+    }
+}
+
+/// Stores lyrics as `TrackLyrics`.
+#[derive(Debug, Clone)]
+pub struct TrackLyricsStore {
+    services: Services,
+}
+
+impl TrackLyricsStore {
+    pub fn new(services: Services) -> Self {
+        Self { services }
+    }
+
+    /// Find the lyrics stored under the key's Spotify track ID, if it has
+    /// one, or else under its name.
+    async fn find(&self, key: &LyricsKey) -> Result<Option<TrackLyrics>> {
+        let LyricsKey {
+            spotify_track_id,
+            track_name,
+            artist_name,
+        } = key;
+        let ctx = Context::new(self.services.clone());
+        let query = match spotify_track_id {
+            Some(id) => TrackLyrics::for_spotify_track(id),
+            None => TrackLyrics::for_name(track_name, artist_name),
+        };
+        let lyrics = query.load(&ctx).await?;
+        Ok(lyrics)
+    }
+}
+
+#[async_trait]
+impl LyricsStore for TrackLyricsStore {
+    async fn load(&self, key: &LyricsKey) -> Result<Option<StoredLyrics>> {
+        let LyricsKey {
+            spotify_track_id,
+            track_name,
+            artist_name,
+        } = key;
+        let mut lyrics =
+            self.find(key).await.context("failed to load lyrics")?;
+
+        // Fall back to lyrics stored under the track's name.
+        if lyrics.is_none() && spotify_track_id.is_some() {
+            let ctx = Context::new(self.services.clone());
+            lyrics = TrackLyrics::for_name(track_name, artist_name)
+                .load(&ctx)
+                .await
+                .context("failed to load lyrics by name")?;
+        }
+
+        let stored = lyrics.map(|lyrics| {
+            let TrackLyrics {
+                lyrics, expires_at, ..
+            } = lyrics;
+            StoredLyrics { lyrics, expires_at }
+        });
+        Ok(stored)
+    }
+
+    async fn save(&self, key: &LyricsKey, stored: StoredLyrics) -> Result<()> {
+        let StoredLyrics { lyrics, expires_at } = stored;
+        let existing = self.find(key).await.context("failed to load lyrics")?;
+        let mut track_lyrics = match existing {
+            Some(mut track_lyrics) => {
+                track_lyrics.lyrics = lyrics;
+                track_lyrics.expires_at = expires_at;
+                track_lyrics
+            }
+            None => {
+                let LyricsKey {
+                    spotify_track_id,
+                    track_name,
+                    artist_name,
+                } = key.clone();
+                TrackLyrics::builder()
+                    .spotify_track_id(spotify_track_id)
+                    .track_name(track_name)
+                    .artist_name(artist_name)
+                    .lyrics(lyrics)
+                    .expires_at(expires_at)
+                    .build()
+            }
+        };
+        let ctx = Context::new(self.services.clone());
+        track_lyrics
+            .save(&ctx)
+            .await
+            .context("failed to save lyrics")?;
+        Ok(())
+    }
+}
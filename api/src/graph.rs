@@ -7,36 +7,66 @@ pub use query::*;
 pub use subscription::*;
 
 mod build;
+mod date;
 mod date_time;
 mod form;
 mod form_response;
 mod heart_rate;
 mod id;
+mod journal_entry;
 mod knowledge_entry;
+mod knowledge_entry_link;
 mod knowledge_entry_links;
+mod knowledge_entry_revision;
+mod knowledge_entry_search_result;
+mod knowledge_graph;
+mod knowledge_tag;
+mod knowledge_task;
 mod lyric_line;
 mod lyrics;
 mod music_album;
 mod music_artist;
+mod music_context;
+mod music_device;
 mod music_info;
+mod music_library;
+mod music_play;
+mod music_playlist;
 mod music_track;
+mod podcast_episode;
+mod podcast_show;
 mod test;
 mod user;
 
 use build::*;
+use date::*;
 use date_time::*;
 use form::*;
 use form_response::*;
 use heart_rate::*;
 use id::*;
+use journal_entry::*;
 use knowledge_entry::*;
+use knowledge_entry_link::*;
 use knowledge_entry_links::*;
+use knowledge_entry_revision::*;
+use knowledge_entry_search_result::*;
+use knowledge_graph::*;
+use knowledge_tag::*;
+use knowledge_task::*;
 use lyric_line::*;
 use lyrics::*;
 use music_album::*;
 use music_artist::*;
+use music_context::*;
+use music_device::*;
 use music_info::*;
+use music_library::*;
+use music_play::*;
+use music_playlist::*;
 use music_track::*;
+use podcast_episode::*;
+use podcast_show::*;
 use test::*;
 use user::*;
 
@@ -45,13 +75,15 @@ use super::*;
 use services::auth0::UserInfo;
 use services::segment::Identity;
 use services::segment::TrackEvent as SegmentTrackEvent;
-use services::Services;
+use services::{Services, Settings};
 
 use entrust::{Comparison, SortingDirection};
 use entrust::{Entity, EntityId};
 
+use graphql::connection::{Connection, CursorType, Edge};
 use graphql::scalar;
 use graphql::Context;
+use graphql::Json as JsonScalar;
 use graphql::Value;
 use graphql::{Enum, EnumType};
 use graphql::{FieldError, FieldResult};
@@ -88,3 +120,17 @@ impl<'a> ContextExt for Context<'a> {
 fn format_error(error: Error) -> FieldError {
     FieldError::new(format!("{:#}", error))
 }
+
+/// Decode an `after` cursor into the offset of the first item to return.
+fn decode_offset_cursor(after: Option<String>) -> Result<usize> {
+    let offset = match after {
+        Some(cursor) => {
+            let index = usize::decode_cursor(&cursor)
+                .map_err(|error| Error::msg(error.to_string()))
+                .context("invalid cursor")?;
+            index + 1
+        }
+        None => 0,
+    };
+    Ok(offset)
+}
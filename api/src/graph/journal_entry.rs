@@ -0,0 +1,93 @@
+use super::*;
+
+use services::obsidian::Note as ObsidianNote;
+
+#[derive(Debug, Clone)]
+pub(super) struct JournalEntryObject {
+    pub date: Date,
+    pub note: ObsidianNote,
+}
+
+#[Object(name = "JournalEntry")]
+impl JournalEntryObject {
+    async fn date(&self) -> DateScalar {
+        self.date.into()
+    }
+
+    async fn entry(&self) -> KnowledgeEntryObject {
+        self.note.clone().into()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct JournalEntryQuery;
+
+#[Object]
+impl JournalEntryQuery {
+    async fn journal_entry(
+        &self,
+        ctx: &Context<'_>,
+        date: DateScalar,
+    ) -> FieldResult<Option<JournalEntryObject>> {
+        self.resolve_journal_entry(ctx, date)
+            .await
+            .map_err(format_error)
+    }
+
+    async fn journal_entries(
+        &self,
+        ctx: &Context<'_>,
+        from: DateScalar,
+        to: DateScalar,
+    ) -> FieldResult<Vec<JournalEntryObject>> {
+        self.resolve_journal_entries(ctx, from, to)
+            .await
+            .map_err(format_error)
+    }
+}
+
+impl JournalEntryQuery {
+    async fn resolve_journal_entry(
+        &self,
+        ctx: &Context<'_>,
+        date: DateScalar,
+    ) -> Result<Option<JournalEntryObject>> {
+        let identity = ctx.userinfo();
+        let date = Date::from(date);
+        let note = ctx
+            .services()
+            .obsidian()
+            .get_journal_note(date)
+            .await
+            .context("failed to get journal note")?;
+        let entry = note
+            .filter(|note| is_knowledge_entry_visible(note, identity))
+            .map(|note| JournalEntryObject { date, note });
+        Ok(entry)
+    }
+
+    async fn resolve_journal_entries(
+        &self,
+        ctx: &Context<'_>,
+        from: DateScalar,
+        to: DateScalar,
+    ) -> Result<Vec<JournalEntryObject>> {
+        let identity = ctx.userinfo();
+        let from = Date::from(from);
+        let to = Date::from(to);
+        ensure!(from <= to, "range must start before it ends");
+
+        let notes = ctx
+            .services()
+            .obsidian()
+            .list_journal_notes(from, to)
+            .await
+            .context("failed to list journal notes")?;
+        let entries = notes
+            .into_iter()
+            .filter(|(_, note)| is_knowledge_entry_visible(note, identity))
+            .map(|(date, note)| JournalEntryObject { date, note })
+            .collect();
+        Ok(entries)
+    }
+}
@@ -1,6 +1,10 @@
 use super::*;
 
 use services::obsidian::Note as ObsidianNote;
+use services::obsidian::NoteConditions as ObsidianNoteConditions;
+use services::obsidian::NoteSorting as ObsidianNoteSorting;
+use services::obsidian::NoteTaskConditions as ObsidianNoteTaskConditions;
+use services::obsidian::NoteUpdate as ObsidianNoteUpdate;
 
 #[derive(Debug, Clone, From)]
 pub(super) struct KnowledgeEntryObject {
@@ -17,6 +21,14 @@ impl KnowledgeEntryObject {
         &self.note.names
     }
 
+    async fn aliases(&self) -> &Set<String> {
+        &self.note.aliases
+    }
+
+    async fn title(&self) -> Option<&str> {
+        self.note.title.as_deref()
+    }
+
     async fn links(&self) -> KnowledgeEntryLinksObject {
         self.note.clone().into()
     }
@@ -24,6 +36,196 @@ impl KnowledgeEntryObject {
     async fn tags(&self) -> &Set<String> {
         &self.note.tags
     }
+
+    async fn tasks(&self) -> Vec<KnowledgeTaskObject> {
+        let tasks = self.note.tasks.iter().cloned();
+        tasks
+            .map(|task| KnowledgeTaskObject {
+                note: self.note.clone(),
+                task,
+            })
+            .collect()
+    }
+
+    async fn is_published(&self) -> bool {
+        self.note.is_published
+    }
+
+    async fn modified_at(&self) -> Option<DateTimeScalar> {
+        self.note.modified_at.map(Into::into)
+    }
+
+    async fn created_at(&self) -> Option<DateTimeScalar> {
+        self.note.created_at.map(Into::into)
+    }
+
+    async fn updated_at(&self) -> Option<DateTimeScalar> {
+        self.note.updated_at.map(Into::into)
+    }
+
+    async fn properties(&self) -> JsonScalar<&Map<String, Json>> {
+        JsonScalar(&self.note.properties)
+    }
+
+    async fn content(&self) -> &str {
+        self.note.content.as_str()
+    }
+
+    async fn html(&self, ctx: &Context<'_>) -> FieldResult<String> {
+        self.resolve_html(ctx).await.map_err(format_error)
+    }
+
+    /// Commits that changed this entry, most recent first.
+    async fn revisions(
+        &self,
+        ctx: &Context<'_>,
+    ) -> FieldResult<Vec<KnowledgeEntryRevisionObject>> {
+        self.resolve_revisions(ctx).await.map_err(format_error)
+    }
+
+    /// The content of this entry at a revision.
+    async fn content_at(
+        &self,
+        ctx: &Context<'_>,
+        revision: String,
+    ) -> FieldResult<Option<String>> {
+        self.resolve_content_at(ctx, revision)
+            .await
+            .map_err(format_error)
+    }
+}
+
+impl KnowledgeEntryObject {
+    async fn resolve_html(&self, ctx: &Context<'_>) -> Result<String> {
+        let identity = ctx.userinfo();
+        let services = ctx.services();
+        let Settings {
+            base_url,
+            web_base_url,
+        } = services.settings();
+
+        // Render links to hidden entries without naming them.
+        let html = services
+            .obsidian()
+            .render_note_html(
+                &self.note,
+                |note| {
+                    if !is_knowledge_entry_visible(note, identity) {
+                        return None;
+                    }
+                    let url = knowledge_entry_url(web_base_url, &note.id);
+                    Some(url)
+                },
+                |attachment| {
+                    let url =
+                        knowledge_attachment_url(base_url, &attachment.id);
+                    Some(url)
+                },
+            )
+            .await
+            .context("failed to render note")?;
+        Ok(html)
+    }
+}
+
+impl KnowledgeEntryObject {
+    async fn resolve_revisions(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<KnowledgeEntryRevisionObject>> {
+        let revisions = ctx
+            .services()
+            .obsidian()
+            .get_note_revisions(&self.note.id)
+            .await
+            .context("failed to get note revisions")?;
+        let revisions = revisions
+            .into_iter()
+            .map(|revision| KnowledgeEntryRevisionObject {
+                note_id: self.note.id.clone(),
+                revision,
+            })
+            .collect();
+        Ok(revisions)
+    }
+
+    async fn resolve_content_at(
+        &self,
+        ctx: &Context<'_>,
+        revision: String,
+    ) -> Result<Option<String>> {
+        let content = ctx
+            .services()
+            .obsidian()
+            .get_note_content_at(&self.note.id, &revision)
+            .await
+            .context("failed to read note at revision")?;
+        Ok(content)
+    }
+}
+
+/// Whether a knowledge entry is visible to the viewer.
+///
+/// Unpublished entries are only visible to admins.
+pub(super) fn is_knowledge_entry_visible(
+    note: &ObsidianNote,
+    identity: Option<&UserInfo>,
+) -> bool {
+    if note.is_published {
+        return true;
+    }
+    identity
+        .map(|identity| identity.is_admin)
+        .unwrap_or_default()
+}
+
+/// The URL of a knowledge entry on the web app.
+pub(super) fn knowledge_entry_url(web_base_url: &Url, id: &str) -> Url {
+    let mut url = web_base_url.clone();
+    if let Ok(mut segments) = url.path_segments_mut() {
+        segments
+            .pop_if_empty()
+            .push("knowledge")
+            .extend(id.split('/'));
+    }
+    url
+}
+
+/// The URL of a vault attachment, served by the API.
+pub(super) fn knowledge_attachment_url(base_url: &Url, id: &str) -> Url {
+    let mut url = base_url.clone();
+    if let Ok(mut segments) = url.path_segments_mut() {
+        segments
+            .pop_if_empty()
+            .push("knowledge")
+            .push("attachments")
+            .extend(id.split('/'));
+    }
+    url
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub(super) enum KnowledgeEntrySort {
+    Id,
+    ModifiedAt,
+    IncomingLinks,
+}
+
+impl Default for KnowledgeEntrySort {
+    fn default() -> Self {
+        Self::Id
+    }
+}
+
+impl From<KnowledgeEntrySort> for ObsidianNoteSorting {
+    fn from(sort: KnowledgeEntrySort) -> Self {
+        use KnowledgeEntrySort::*;
+        match sort {
+            Id => Self::Id,
+            ModifiedAt => Self::ModifiedAt,
+            IncomingLinks => Self::IncomingReferences,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -34,8 +236,12 @@ impl KnowledgeEntryQuery {
     async fn knowledge_entries(
         &self,
         ctx: &Context<'_>,
-    ) -> FieldResult<Vec<KnowledgeEntryObject>> {
-        self.resolve_knowledge_entries(ctx)
+        #[graphql(default = 25)] first: usize,
+        after: Option<String>,
+        #[graphql(default)] sort: KnowledgeEntrySort,
+        folder: Option<String>,
+    ) -> FieldResult<Connection<usize, KnowledgeEntryObject>> {
+        self.resolve_knowledge_entries(ctx, first, after, sort, folder)
             .await
             .map_err(format_error)
     }
@@ -49,17 +255,194 @@ impl KnowledgeEntryQuery {
             .await
             .map_err(format_error)
     }
+
+    async fn search_knowledge_entries(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        #[graphql(default = 10)] first: usize,
+        after: Option<String>,
+    ) -> FieldResult<Connection<usize, KnowledgeEntrySearchResultObject>> {
+        self.resolve_search_knowledge_entries(ctx, query, first, after)
+            .await
+            .map_err(format_error)
+    }
+
+    async fn recent_knowledge_entries(
+        &self,
+        ctx: &Context<'_>,
+        since: Option<DateTimeScalar>,
+        #[graphql(default = 10)] first: usize,
+    ) -> FieldResult<Vec<KnowledgeEntryObject>> {
+        self.resolve_recent_knowledge_entries(ctx, since, first)
+            .await
+            .map_err(format_error)
+    }
+
+    async fn knowledge_entries_by_tag(
+        &self,
+        ctx: &Context<'_>,
+        tag: String,
+        #[graphql(default)] include_descendants: bool,
+    ) -> FieldResult<Vec<KnowledgeEntryObject>> {
+        self.resolve_knowledge_entries_by_tag(ctx, tag, include_descendants)
+            .await
+            .map_err(format_error)
+    }
+
+    async fn knowledge_tags(
+        &self,
+        ctx: &Context<'_>,
+    ) -> FieldResult<Vec<KnowledgeTagObject>> {
+        self.resolve_knowledge_tags(ctx).await.map_err(format_error)
+    }
+
+    async fn knowledge_tasks(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<KnowledgeTaskStatus>,
+        due_before: Option<DateScalar>,
+    ) -> FieldResult<Vec<KnowledgeTaskObject>> {
+        self.resolve_knowledge_tasks(ctx, status, due_before)
+            .await
+            .map_err(format_error)
+    }
 }
 
 impl KnowledgeEntryQuery {
     async fn resolve_knowledge_entries(
         &self,
         ctx: &Context<'_>,
+        first: usize,
+        after: Option<String>,
+        sort: KnowledgeEntrySort,
+        folder: Option<String>,
+    ) -> Result<Connection<usize, KnowledgeEntryObject>> {
+        ensure!(first <= 100, "can only take up to 100 entries");
+        let offset = decode_offset_cursor(after)?;
+        let identity = ctx.userinfo();
+
+        let notes = ctx
+            .services()
+            .obsidian()
+            .find_notes(
+                ObsidianNoteConditions::builder().folder(folder).build(),
+                sort.into(),
+            )
+            .await
+            .context("failed to find notes")?
+            .into_iter()
+            .filter(|note| is_knowledge_entry_visible(note, identity))
+            .collect::<Vec<_>>();
+        let count = notes.len();
+
+        let mut connection =
+            Connection::new(offset > 0, offset + first < count);
+        connection.append(
+            notes
+                .into_iter()
+                .enumerate()
+                .skip(offset)
+                .take(first)
+                .map(|(index, note)| Edge::new(index, note.into())),
+        );
+        Ok(connection)
+    }
+
+    async fn resolve_knowledge_entry(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<Option<KnowledgeEntryObject>> {
+        let identity = ctx.userinfo();
+        let note = ctx.services().obsidian().get_note(&id).await?;
+        let entry = note
+            .filter(|note| is_knowledge_entry_visible(note, identity))
+            .map(KnowledgeEntryObject::from);
+        Ok(entry)
+    }
+
+    async fn resolve_search_knowledge_entries(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        first: usize,
+        after: Option<String>,
+    ) -> Result<Connection<usize, KnowledgeEntrySearchResultObject>> {
+        ensure!(first <= 50, "can only take up to 50 results");
+        let offset = decode_offset_cursor(after)?;
+        let identity = ctx.userinfo();
+
+        let hits = ctx
+            .services()
+            .obsidian()
+            .search_notes(&query)
+            .await
+            .context("failed to search notes")?
+            .into_iter()
+            .filter(|hit| is_knowledge_entry_visible(&hit.note, identity))
+            .collect::<Vec<_>>();
+        let count = hits.len();
+
+        let mut connection =
+            Connection::new(offset > 0, offset + first < count);
+        connection.append(
+            hits.into_iter()
+                .enumerate()
+                .skip(offset)
+                .take(first)
+                .map(|(index, hit)| Edge::new(index, hit.into())),
+        );
+        Ok(connection)
+    }
+
+    async fn resolve_recent_knowledge_entries(
+        &self,
+        ctx: &Context<'_>,
+        since: Option<DateTimeScalar>,
+        first: usize,
     ) -> Result<Vec<KnowledgeEntryObject>> {
-        let notes = ctx.services().obsidian().list_notes().await?;
+        ensure!(first <= 50, "can only take up to 50 entries");
+        let identity = ctx.userinfo();
+
+        let since = since.map(DateTime::from);
+        let notes = ctx
+            .services()
+            .obsidian()
+            .find_notes(
+                ObsidianNoteConditions::builder()
+                    .updated_since(since)
+                    .build(),
+                ObsidianNoteSorting::UpdatedAt,
+            )
+            .await
+            .context("failed to find notes")?;
+        let entries = notes
+            .into_iter()
+            .filter(|note| note.updated_at.is_some())
+            .filter(|note| is_knowledge_entry_visible(note, identity))
+            .take(first)
+            .map(KnowledgeEntryObject::from)
+            .collect();
+        Ok(entries)
+    }
+
+    async fn resolve_knowledge_entries_by_tag(
+        &self,
+        ctx: &Context<'_>,
+        tag: String,
+        include_descendants: bool,
+    ) -> Result<Vec<KnowledgeEntryObject>> {
+        let identity = ctx.userinfo();
+        let notes = ctx
+            .services()
+            .obsidian()
+            .list_notes_by_tag(&tag, include_descendants)
+            .await?;
         let entries = {
             let mut entries = notes
                 .into_iter()
+                .filter(|note| is_knowledge_entry_visible(note, identity))
                 .map(KnowledgeEntryObject::from)
                 .collect::<Vec<_>>();
             entries.sort_by_cached_key(|entry| entry.note.id.clone());
@@ -68,13 +451,109 @@ impl KnowledgeEntryQuery {
         Ok(entries)
     }
 
-    async fn resolve_knowledge_entry(
+    async fn resolve_knowledge_tags(
         &self,
         ctx: &Context<'_>,
-        id: String,
-    ) -> Result<Option<KnowledgeEntryObject>> {
-        let note = ctx.services().obsidian().get_note(&id).await?;
-        let entry = note.map(KnowledgeEntryObject::from);
-        Ok(entry)
+    ) -> Result<Vec<KnowledgeTagObject>> {
+        let is_admin = ctx
+            .userinfo()
+            .map(|identity| identity.is_admin)
+            .unwrap_or_default();
+        let tags = ctx.services().obsidian().list_tags(is_admin).await?;
+        let tags = tags.into_iter().map(KnowledgeTagObject::from).collect();
+        Ok(tags)
+    }
+
+    async fn resolve_knowledge_tasks(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<KnowledgeTaskStatus>,
+        due_before: Option<DateScalar>,
+    ) -> Result<Vec<KnowledgeTaskObject>> {
+        let identity = ctx.userinfo();
+        let status = status.map(Into::into);
+        let due_before = due_before.map(Date::from);
+        let tasks = ctx
+            .services()
+            .obsidian()
+            .find_tasks(
+                ObsidianNoteTaskConditions::builder()
+                    .status(status)
+                    .due_before(due_before)
+                    .build(),
+            )
+            .await
+            .context("failed to find tasks")?;
+        let tasks = tasks
+            .into_iter()
+            .filter(|(note, _)| is_knowledge_entry_visible(note, identity))
+            .map(|(note, task)| KnowledgeTaskObject { note, task })
+            .collect();
+        Ok(tasks)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct KnowledgeEntrySubscription;
+
+#[Subscription]
+impl KnowledgeEntrySubscription {
+    /// Emits knowledge entries as they are added, changed, or deleted.
+    ///
+    /// Only available when the vault is being watched.
+    async fn knowledge_entry_updates(
+        &self,
+        ctx: &Context<'_>,
+    ) -> FieldResult<impl Stream<Item = KnowledgeEntryUpdateObject>> {
+        let identity = ctx.userinfo().cloned();
+        let updates = ctx
+            .services()
+            .obsidian()
+            .watch_notes()
+            .map_err(format_error)?;
+        let updates = updates.filter_map(move |update| {
+            let is_admin = identity
+                .as_ref()
+                .map(|identity| identity.is_admin)
+                .unwrap_or_default();
+            let update = match update {
+                ObsidianNoteUpdate::Changed(note) => {
+                    if is_knowledge_entry_visible(&note, identity.as_ref()) {
+                        Some(KnowledgeEntryUpdateObject {
+                            id: note.id.clone(),
+                            entry: Some((*note).into()),
+                            deleted: false,
+                        })
+                    } else {
+                        None
+                    }
+                }
+                ObsidianNoteUpdate::Removed { id, was_published } => {
+                    if was_published || is_admin {
+                        Some(KnowledgeEntryUpdateObject {
+                            id,
+                            entry: None,
+                            deleted: true,
+                        })
+                    } else {
+                        None
+                    }
+                }
+            };
+            async move { update }
+        });
+        Ok(updates)
     }
 }
+
+/// A knowledge entry that was added, changed, or deleted.
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "KnowledgeEntryUpdate")]
+pub(super) struct KnowledgeEntryUpdateObject {
+    pub id: String,
+
+    /// The entry, unless it was deleted.
+    pub entry: Option<KnowledgeEntryObject>,
+
+    pub deleted: bool,
+}
@@ -0,0 +1,64 @@
+use super::*;
+
+use services::obsidian::NoteLink as ObsidianNoteLink;
+
+#[derive(Debug, Clone, From)]
+pub(super) struct KnowledgeEntryLinkObject(ObsidianNoteLink);
+
+#[Object(name = "KnowledgeEntryLink")]
+impl KnowledgeEntryLinkObject {
+    async fn target(&self) -> &str {
+        let KnowledgeEntryLinkObject(link) = self;
+        link.target.as_str()
+    }
+
+    async fn heading(&self) -> Option<&str> {
+        let KnowledgeEntryLinkObject(link) = self;
+        link.heading.as_deref()
+    }
+
+    async fn block(&self) -> Option<&str> {
+        let KnowledgeEntryLinkObject(link) = self;
+        link.block.as_deref()
+    }
+
+    async fn alias(&self) -> Option<&str> {
+        let KnowledgeEntryLinkObject(link) = self;
+        link.alias.as_deref()
+    }
+
+    async fn is_embed(&self) -> bool {
+        let KnowledgeEntryLinkObject(link) = self;
+        link.is_embed
+    }
+
+    async fn entry(
+        &self,
+        ctx: &Context<'_>,
+    ) -> FieldResult<Option<KnowledgeEntryObject>> {
+        self.resolve_entry(ctx).await.map_err(format_error)
+    }
+}
+
+impl KnowledgeEntryLinkObject {
+    async fn resolve_entry(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<KnowledgeEntryObject>> {
+        let KnowledgeEntryLinkObject(link) = self;
+        let identity = ctx.userinfo();
+        if link.is_attachment() {
+            return Ok(None);
+        }
+        let note = ctx
+            .services()
+            .obsidian()
+            .resolve_note(&link.target)
+            .await
+            .context("failed to resolve note")?;
+        let entry = note
+            .filter(|note| is_knowledge_entry_visible(note, identity))
+            .map(KnowledgeEntryObject::from);
+        Ok(entry)
+    }
+}
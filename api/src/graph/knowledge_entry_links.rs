@@ -9,6 +9,13 @@ pub(super) struct KnowledgeEntryLinksObject {
 
 #[Object(name = "KnowledgeEntryLinks")]
 impl KnowledgeEntryLinksObject {
+    async fn references(
+        &self,
+        ctx: &Context<'_>,
+    ) -> FieldResult<Vec<KnowledgeEntryLinkObject>> {
+        self.resolve_references(ctx).await.map_err(format_error)
+    }
+
     async fn outgoing(
         &self,
         ctx: &Context<'_>,
@@ -25,10 +32,43 @@ impl KnowledgeEntryLinksObject {
 }
 
 impl KnowledgeEntryLinksObject {
+    async fn resolve_references(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<KnowledgeEntryLinkObject>> {
+        let identity = ctx.userinfo();
+        let obsidian = ctx.services().obsidian();
+
+        // Redact links to hidden entries.
+        let targets = self
+            .note
+            .links
+            .iter()
+            .filter(|link| !link.is_attachment())
+            .map(|link| link.target.as_str());
+        let notes = obsidian
+            .resolve_notes(targets)
+            .await
+            .context("failed to resolve notes")?;
+        let links = self
+            .note
+            .links
+            .iter()
+            .filter(|link| match notes.get(&link.target) {
+                Some(note) => is_knowledge_entry_visible(note, identity),
+                None => true,
+            })
+            .cloned()
+            .map(KnowledgeEntryLinkObject::from)
+            .collect();
+        Ok(links)
+    }
+
     async fn resolve_outgoing(
         &self,
         ctx: &Context<'_>,
     ) -> Result<Vec<KnowledgeEntryObject>> {
+        let identity = ctx.userinfo();
         let notes = ctx
             .services()
             .obsidian()
@@ -36,6 +76,7 @@ impl KnowledgeEntryLinksObject {
             .await?;
         let mut entries = notes
             .into_iter()
+            .filter(|note| is_knowledge_entry_visible(note, identity))
             .map(KnowledgeEntryObject::from)
             .collect::<Vec<_>>();
         entries.sort_by_cached_key(|entry| entry.note.id.clone());
@@ -46,6 +87,7 @@ impl KnowledgeEntryLinksObject {
         &self,
         ctx: &Context<'_>,
     ) -> Result<Vec<KnowledgeEntryObject>> {
+        let identity = ctx.userinfo();
         let notes = ctx
             .services()
             .obsidian()
@@ -53,6 +95,7 @@ impl KnowledgeEntryLinksObject {
             .await?;
         let mut entries = notes
             .into_iter()
+            .filter(|note| is_knowledge_entry_visible(note, identity))
             .map(KnowledgeEntryObject::from)
             .collect::<Vec<_>>();
         entries.sort_by_cached_key(|entry| entry.note.id.clone());
@@ -0,0 +1,52 @@
+use super::*;
+
+use services::obsidian::NoteRevision as ObsidianNoteRevision;
+
+#[derive(Debug, Clone)]
+pub(super) struct KnowledgeEntryRevisionObject {
+    pub note_id: String,
+    pub revision: ObsidianNoteRevision,
+}
+
+#[Object(name = "KnowledgeEntryRevision")]
+impl KnowledgeEntryRevisionObject {
+    async fn id(&self) -> &str {
+        self.revision.id.as_str()
+    }
+
+    async fn committed_at(&self) -> DateTimeScalar {
+        self.revision.committed_at.into()
+    }
+
+    async fn message(&self) -> &str {
+        self.revision.message.as_str()
+    }
+
+    async fn insertions(&self) -> u32 {
+        self.revision.insertions as u32
+    }
+
+    async fn deletions(&self) -> u32 {
+        self.revision.deletions as u32
+    }
+
+    /// The content of the entry at this revision.
+    async fn content(&self, ctx: &Context<'_>) -> FieldResult<Option<String>> {
+        self.resolve_content(ctx).await.map_err(format_error)
+    }
+}
+
+impl KnowledgeEntryRevisionObject {
+    async fn resolve_content(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<String>> {
+        let content = ctx
+            .services()
+            .obsidian()
+            .get_note_content_at(&self.note_id, &self.revision.id)
+            .await
+            .context("failed to read note at revision")?;
+        Ok(content)
+    }
+}
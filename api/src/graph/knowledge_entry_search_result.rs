@@ -0,0 +1,47 @@
+use super::*;
+
+use services::obsidian::SearchHighlight as ObsidianSearchHighlight;
+use services::obsidian::SearchHit as ObsidianSearchHit;
+
+#[derive(Debug, Clone, From)]
+pub(super) struct KnowledgeEntrySearchResultObject(ObsidianSearchHit);
+
+#[Object(name = "KnowledgeEntrySearchResult")]
+impl KnowledgeEntrySearchResultObject {
+    async fn entry(&self) -> KnowledgeEntryObject {
+        let KnowledgeEntrySearchResultObject(hit) = self;
+        hit.note.clone().into()
+    }
+
+    async fn score(&self) -> f64 {
+        let KnowledgeEntrySearchResultObject(hit) = self;
+        hit.score
+    }
+
+    async fn snippet(&self) -> &str {
+        let KnowledgeEntrySearchResultObject(hit) = self;
+        hit.snippet.as_str()
+    }
+
+    async fn highlights(&self) -> Vec<KnowledgeEntrySearchHighlightObject> {
+        let KnowledgeEntrySearchResultObject(hit) = self;
+        hit.highlights.iter().copied().map(Into::into).collect()
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "KnowledgeEntrySearchHighlight")]
+pub(super) struct KnowledgeEntrySearchHighlightObject {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl From<ObsidianSearchHighlight> for KnowledgeEntrySearchHighlightObject {
+    fn from(highlight: ObsidianSearchHighlight) -> Self {
+        let ObsidianSearchHighlight { start, end } = highlight;
+        Self {
+            start: start as u32,
+            end: end as u32,
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use super::*;
+
+use services::obsidian::NoteGraph as ObsidianNoteGraph;
+use services::obsidian::NoteGraphEdge as ObsidianNoteGraphEdge;
+use services::obsidian::NoteGraphNode as ObsidianNoteGraphNode;
+
+#[derive(Debug, Clone, From)]
+pub(super) struct KnowledgeGraphObject(ObsidianNoteGraph);
+
+#[Object(name = "KnowledgeGraph")]
+impl KnowledgeGraphObject {
+    async fn nodes(&self) -> Vec<KnowledgeGraphNodeObject> {
+        let KnowledgeGraphObject(graph) = self;
+        graph.nodes.iter().cloned().map(Into::into).collect()
+    }
+
+    async fn edges(&self) -> Vec<KnowledgeGraphEdgeObject> {
+        let KnowledgeGraphObject(graph) = self;
+        graph.edges.iter().cloned().map(Into::into).collect()
+    }
+}
+
+#[derive(Debug, Clone, From)]
+pub(super) struct KnowledgeGraphNodeObject(ObsidianNoteGraphNode);
+
+#[Object(name = "KnowledgeGraphNode")]
+impl KnowledgeGraphNodeObject {
+    async fn id(&self) -> &str {
+        let KnowledgeGraphNodeObject(node) = self;
+        node.id.as_str()
+    }
+
+    async fn names(&self) -> &Set<String> {
+        let KnowledgeGraphNodeObject(node) = self;
+        &node.names
+    }
+
+    async fn tags(&self) -> &Set<String> {
+        let KnowledgeGraphNodeObject(node) = self;
+        &node.tags
+    }
+
+    async fn is_ghost(&self) -> bool {
+        let KnowledgeGraphNodeObject(node) = self;
+        node.is_ghost
+    }
+
+    async fn entry(
+        &self,
+        ctx: &Context<'_>,
+    ) -> FieldResult<Option<KnowledgeEntryObject>> {
+        self.resolve_entry(ctx).await.map_err(format_error)
+    }
+}
+
+impl KnowledgeGraphNodeObject {
+    async fn resolve_entry(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<KnowledgeEntryObject>> {
+        let KnowledgeGraphNodeObject(node) = self;
+        let identity = ctx.userinfo();
+        if node.is_ghost {
+            return Ok(None);
+        }
+        let note = ctx
+            .services()
+            .obsidian()
+            .get_note(&node.id)
+            .await
+            .context("failed to get note")?;
+        let entry = note
+            .filter(|note| is_knowledge_entry_visible(note, identity))
+            .map(KnowledgeEntryObject::from);
+        Ok(entry)
+    }
+}
+
+#[derive(Debug, Clone, From)]
+pub(super) struct KnowledgeGraphEdgeObject(ObsidianNoteGraphEdge);
+
+#[Object(name = "KnowledgeGraphEdge")]
+impl KnowledgeGraphEdgeObject {
+    async fn source(&self) -> &str {
+        let KnowledgeGraphEdgeObject(edge) = self;
+        edge.source.as_str()
+    }
+
+    async fn target(&self) -> &str {
+        let KnowledgeGraphEdgeObject(edge) = self;
+        edge.target.as_str()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct KnowledgeGraphQuery;
+
+#[Object]
+impl KnowledgeGraphQuery {
+    async fn knowledge_graph(
+        &self,
+        ctx: &Context<'_>,
+    ) -> FieldResult<KnowledgeGraphObject> {
+        self.resolve_knowledge_graph(ctx)
+            .await
+            .map_err(format_error)
+    }
+}
+
+impl KnowledgeGraphQuery {
+    async fn resolve_knowledge_graph(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<KnowledgeGraphObject> {
+        let is_admin = ctx
+            .userinfo()
+            .map(|identity| identity.is_admin)
+            .unwrap_or_default();
+        let graph = ctx
+            .services()
+            .obsidian()
+            .get_graph(is_admin)
+            .await
+            .context("failed to load knowledge graph")?;
+        Ok(graph.into())
+    }
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+use services::obsidian::NoteTag as ObsidianNoteTag;
+
+#[derive(Debug, Clone, From)]
+pub(super) struct KnowledgeTagObject(ObsidianNoteTag);
+
+#[Object(name = "KnowledgeTag")]
+impl KnowledgeTagObject {
+    async fn name(&self) -> &str {
+        let KnowledgeTagObject(tag) = self;
+        tag.name.as_str()
+    }
+
+    async fn label(&self) -> &str {
+        let KnowledgeTagObject(tag) = self;
+        tag.label()
+    }
+
+    async fn count(&self) -> u32 {
+        let KnowledgeTagObject(tag) = self;
+        tag.count as u32
+    }
+
+    async fn total_count(&self) -> u32 {
+        let KnowledgeTagObject(tag) = self;
+        tag.total_count as u32
+    }
+
+    async fn children(&self) -> Vec<KnowledgeTagObject> {
+        let KnowledgeTagObject(tag) = self;
+        let children = tag.children.iter().cloned();
+        children.map(KnowledgeTagObject::from).collect()
+    }
+}
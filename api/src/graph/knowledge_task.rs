@@ -0,0 +1,67 @@
+use super::*;
+
+use services::obsidian::Note as ObsidianNote;
+use services::obsidian::NoteTask as ObsidianNoteTask;
+use services::obsidian::NoteTaskStatus as ObsidianNoteTaskStatus;
+
+#[derive(Debug, Clone)]
+pub(super) struct KnowledgeTaskObject {
+    pub note: ObsidianNote,
+    pub task: ObsidianNoteTask,
+}
+
+#[Object(name = "KnowledgeTask")]
+impl KnowledgeTaskObject {
+    async fn text(&self) -> &str {
+        self.task.text.as_str()
+    }
+
+    async fn status(&self) -> KnowledgeTaskStatus {
+        self.task.status.into()
+    }
+
+    async fn due(&self) -> Option<DateScalar> {
+        self.task.due.map(Into::into)
+    }
+
+    /// The line of the entry that the task is on, starting from 1.
+    async fn line(&self) -> u32 {
+        self.task.line as u32
+    }
+
+    async fn entry(&self) -> KnowledgeEntryObject {
+        self.note.clone().into()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub(super) enum KnowledgeTaskStatus {
+    Todo,
+    InProgress,
+    Done,
+    Cancelled,
+}
+
+impl From<ObsidianNoteTaskStatus> for KnowledgeTaskStatus {
+    fn from(status: ObsidianNoteTaskStatus) -> Self {
+        use ObsidianNoteTaskStatus::*;
+        match status {
+            Todo => Self::Todo,
+            InProgress => Self::InProgress,
+            Done => Self::Done,
+            Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+impl From<KnowledgeTaskStatus> for ObsidianNoteTaskStatus {
+    fn from(status: KnowledgeTaskStatus) -> Self {
+        use KnowledgeTaskStatus::*;
+        match status {
+            Todo => Self::Todo,
+            InProgress => Self::InProgress,
+            Done => Self::Done,
+            Cancelled => Self::Cancelled,
+        }
+    }
+}
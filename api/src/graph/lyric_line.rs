@@ -1,6 +1,6 @@
 use super::*;
 
-use services::lyricly::LyricLine as LyriclyLyricLine;
+use services::lyrics::LyricLine as ServiceLyricLine;
 
 #[derive(Debug, Clone, SimpleObject)]
 pub(super) struct LyricLine {
@@ -8,9 +8,9 @@ pub(super) struct LyricLine {
     pub position: u32,
 }
 
-impl From<LyriclyLyricLine> for LyricLine {
-    fn from(line: LyriclyLyricLine) -> Self {
-        let LyriclyLyricLine { text, position } = line;
+impl From<ServiceLyricLine> for LyricLine {
+    fn from(line: ServiceLyricLine) -> Self {
+        let ServiceLyricLine { text, position } = line;
         Self { text, position }
     }
 }
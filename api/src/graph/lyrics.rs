@@ -1,20 +1,125 @@
 use super::*;
 
-use services::lyricly::Lyrics as LyriclyLyrics;
+use services::lyrics::Lyrics as ServiceLyrics;
+use services::lyrics::{parse_lrc, LyricsKey};
+
+use graphql::MaybeUndefined;
 
 #[derive(Debug, Clone, SimpleObject)]
 pub(super) struct Lyrics {
     pub lines: Vec<LyricLine>,
+
+    /// Whether the lines have positions; if not, every line's position is
+    /// zero.
+    pub synced: bool,
+}
+
+impl From<ServiceLyrics> for Lyrics {
+    fn from(lyrics: ServiceLyrics) -> Self {
+        match lyrics {
+            ServiceLyrics::Synced(lines) => {
+                let lines = lines.into_iter().map(LyricLine::from).collect();
+                Self {
+                    lines,
+                    synced: true,
+                }
+            }
+            ServiceLyrics::Plain(text) => {
+                let lines = text
+                    .lines()
+                    .map(|line| LyricLine {
+                        text: line.to_owned(),
+                        position: 0,
+                    })
+                    .collect();
+                Self {
+                    lines,
+                    synced: false,
+                }
+            }
+        }
+    }
 }
 
-impl From<LyriclyLyrics> for Lyrics {
-    fn from(lyrics: LyriclyLyrics) -> Self {
-        let LyriclyLyrics { lines } = lyrics;
-        let lines = lines
-            .unwrap_or_default()
-            .into_iter()
-            .map(LyricLine::from)
-            .collect::<Vec<_>>();
-        Self { lines }
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct LyricsMutation;
+
+#[Object]
+impl LyricsMutation {
+    /// Override the lyrics for a track, i.e. to correct them.
+    async fn update_track_lyrics(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateTrackLyricsInput,
+    ) -> FieldResult<UpdateTrackLyricsPayload> {
+        self.resolve_update_track_lyrics(ctx, input)
+            .await
+            .map_err(format_error)
     }
 }
+
+impl LyricsMutation {
+    async fn resolve_update_track_lyrics(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateTrackLyricsInput,
+    ) -> Result<UpdateTrackLyricsPayload> {
+        let identity = ctx.userinfo();
+        let services = ctx.services();
+
+        if let Some(identity) = identity {
+            ensure!(identity.is_admin, "not authorized");
+        } else {
+            bail!("not authenticated");
+        }
+
+        let UpdateTrackLyricsInput {
+            spotify_track_id,
+            track_name,
+            artist_name,
+            lyrics,
+        } = input;
+        let key = LyricsKey::builder()
+            .spotify_track_id(spotify_track_id)
+            .track_name(track_name)
+            .artist_name(artist_name)
+            .build();
+        let lyrics = match lyrics {
+            MaybeUndefined::Value(text) => {
+                let lyrics = parse_lrc(&text).context("empty lyrics")?;
+                Some(lyrics)
+            }
+            MaybeUndefined::Null => None,
+            MaybeUndefined::Undefined => bail!("missing lyrics"),
+        };
+        services
+            .lyrics()
+            .set_lyrics(&key, lyrics.clone())
+            .await
+            .context("failed to update lyrics")?;
+
+        let lyrics = lyrics.map(Lyrics::from);
+        let payload = UpdateTrackLyricsPayload { lyrics, ok: true };
+        Ok(payload)
+    }
+}
+
+#[derive(Debug, Clone, InputObject)]
+pub(super) struct UpdateTrackLyricsInput {
+    /// The track's ID on Spotify; without it, the lyrics are used for any
+    /// track with the same name that has no lyrics of its own.
+    pub spotify_track_id: Option<String>,
+
+    pub track_name: String,
+    pub artist_name: String,
+
+    /// The lyrics, in LRC format (i.e. `[01:23.45] Line`) or as plain text;
+    /// if null, the track is recorded as having no lyrics.
+    pub lyrics: MaybeUndefined<String>,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub(super) struct UpdateTrackLyricsPayload {
+    pub lyrics: Option<Lyrics>,
+    pub ok: bool,
+}
@@ -0,0 +1,38 @@
+use super::*;
+
+use services::spotify::PlaybackContext as SpotifyPlaybackContext;
+
+/// The playlist, album, artist, or show that music is playing from.
+#[derive(Debug, Clone, From)]
+pub(super) struct MusicContextObject(SpotifyPlaybackContext);
+
+#[Object(name = "MusicContext")]
+impl MusicContextObject {
+    /// The kind of context, i.e. `playlist` or `album`.
+    #[graphql(name = "type")]
+    async fn kind(&self) -> &str {
+        let MusicContextObject(context) = self;
+        context.kind.as_str()
+    }
+
+    async fn spotify_uri(&self) -> &str {
+        let MusicContextObject(context) = self;
+        context.uri.as_str()
+    }
+
+    async fn spotify_url(&self) -> FieldResult<Option<Url>> {
+        self.resolve_spotify_url().await.map_err(format_error)
+    }
+}
+
+impl MusicContextObject {
+    async fn resolve_spotify_url(&self) -> Result<Option<Url>> {
+        let MusicContextObject(context) = self;
+        let urls = match &context.external_urls {
+            Some(urls) => urls,
+            None => return Ok(None),
+        };
+        let url: Url = urls.spotify.parse().context("failed to parse URL")?;
+        Ok(Some(url))
+    }
+}
@@ -0,0 +1,27 @@
+use super::*;
+
+use services::spotify::Device as SpotifyDevice;
+
+#[derive(Debug, Clone, From)]
+pub(super) struct MusicDeviceObject(SpotifyDevice);
+
+#[Object(name = "MusicDevice")]
+impl MusicDeviceObject {
+    async fn name(&self) -> &str {
+        let MusicDeviceObject(device) = self;
+        device.name.as_str()
+    }
+
+    /// The kind of device, i.e. `Computer` or `Smartphone`.
+    #[graphql(name = "type")]
+    async fn kind(&self) -> &str {
+        let MusicDeviceObject(device) = self;
+        device.kind.as_str()
+    }
+
+    /// The device's volume, as a percentage.
+    async fn volume(&self) -> Option<u32> {
+        let MusicDeviceObject(device) = self;
+        device.volume
+    }
+}
@@ -1,12 +1,86 @@
 use super::*;
 
 use services::spotify::CurrentlyPlaying;
+use services::spotify::PlayingItem as SpotifyPlayingItem;
+use services::spotify::RepeatMode as SpotifyRepeatMode;
 
 #[derive(Debug, Clone, SimpleObject)]
 pub(super) struct MusicInfo {
     pub is_playing: bool,
-    pub track: MusicTrackObject,
+
+    #[graphql(deprecation = "Use `item` instead.")]
+    pub track: Option<MusicTrackObject>,
+
+    /// The track or podcast episode that is playing, which is missing while
+    /// an ad is playing.
+    pub item: Option<MusicItem>,
+
+    /// The position in the item, in milliseconds.
     pub progress: u32,
+
+    pub device: Option<MusicDeviceObject>,
+    pub shuffle: bool,
+    pub repeat: MusicRepeatMode,
+    pub context: Option<MusicContextObject>,
+}
+
+impl From<CurrentlyPlaying> for MusicInfo {
+    fn from(currently_playing: CurrentlyPlaying) -> Self {
+        let track = currently_playing.track().cloned();
+        let CurrentlyPlaying {
+            is_playing,
+            item,
+            progress,
+            device,
+            shuffle,
+            repeat,
+            context,
+        } = currently_playing;
+        MusicInfo {
+            is_playing,
+            track: track.map(Into::into),
+            item: item.map(Into::into),
+            progress,
+            device: device.map(Into::into),
+            shuffle,
+            repeat: repeat.into(),
+            context: context.map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Union)]
+pub(super) enum MusicItem {
+    Track(MusicTrackObject),
+    Episode(PodcastEpisodeObject),
+}
+
+impl From<SpotifyPlayingItem> for MusicItem {
+    fn from(item: SpotifyPlayingItem) -> Self {
+        use SpotifyPlayingItem::*;
+        match item {
+            Track(track) => Self::Track(track.into()),
+            Episode(episode) => Self::Episode(episode.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub(super) enum MusicRepeatMode {
+    Off,
+    Track,
+    Context,
+}
+
+impl From<SpotifyRepeatMode> for MusicRepeatMode {
+    fn from(mode: SpotifyRepeatMode) -> Self {
+        use SpotifyRepeatMode::*;
+        match mode {
+            Off => Self::Off,
+            Track => Self::Track,
+            Context => Self::Context,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -33,22 +107,26 @@ impl MusicInfoQuery {
             .get_currently_playing()
             .await
             .context("failed to load currently playing track from Spotify")?;
-        let currently_playing = match currently_playing {
-            Some(currently_playing) => currently_playing,
-            None => return Ok(None),
-        };
-        let info = {
-            let CurrentlyPlaying {
-                is_playing,
-                track,
-                progress,
-            } = currently_playing;
-            MusicInfo {
-                is_playing,
-                track: track.try_into().context("invalid track")?,
-                progress,
-            }
-        };
-        Ok(info.into())
+        let info = currently_playing.map(MusicInfo::from);
+        Ok(info)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct MusicInfoSubscription;
+
+#[Subscription]
+impl MusicInfoSubscription {
+    /// Emits the current music info, and then emits it again whenever the
+    /// item changes, is paused or resumed, or its progress jumps, or when
+    /// the device, shuffle, repeat, or context changes.
+    async fn music_info(
+        &self,
+        ctx: &Context<'_>,
+    ) -> impl Stream<Item = Option<MusicInfo>> {
+        ctx.services()
+            .spotify()
+            .watch_currently_playing()
+            .map(|currently_playing| currently_playing.map(MusicInfo::from))
     }
 }
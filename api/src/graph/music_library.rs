@@ -0,0 +1,120 @@
+use super::*;
+
+use services::spotify::TimeRange as SpotifyTimeRange;
+
+/// The time range over which top tracks and artists are calculated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+#[allow(clippy::enum_variant_names)] // mirrors Spotify's time ranges
+pub(super) enum MusicTimeRange {
+    /// Approximately the last 4 weeks.
+    ShortTerm,
+
+    /// Approximately the last 6 months.
+    MediumTerm,
+
+    /// Several years of listening.
+    LongTerm,
+}
+
+impl From<MusicTimeRange> for SpotifyTimeRange {
+    fn from(range: MusicTimeRange) -> Self {
+        use MusicTimeRange::*;
+        match range {
+            ShortTerm => Self::ShortTerm,
+            MediumTerm => Self::MediumTerm,
+            LongTerm => Self::LongTerm,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct MusicLibraryQuery;
+
+#[Object]
+impl MusicLibraryQuery {
+    /// The most played tracks on Spotify, most played first.
+    async fn music_top_tracks(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default_with = "MusicTimeRange::MediumTerm")]
+        range: MusicTimeRange,
+        #[graphql(default = 10)] first: usize,
+    ) -> FieldResult<Vec<MusicTrackObject>> {
+        self.resolve_music_top_tracks(ctx, range, first)
+            .await
+            .map_err(format_error)
+    }
+
+    /// The most played artists on Spotify, most played first.
+    async fn music_top_artists(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default_with = "MusicTimeRange::MediumTerm")]
+        range: MusicTimeRange,
+        #[graphql(default = 10)] first: usize,
+    ) -> FieldResult<Vec<MusicArtistObject>> {
+        self.resolve_music_top_artists(ctx, range, first)
+            .await
+            .map_err(format_error)
+    }
+
+    /// Public playlists on Spotify that are owned by the user.
+    async fn music_playlists(
+        &self,
+        ctx: &Context<'_>,
+    ) -> FieldResult<Vec<MusicPlaylistObject>> {
+        self.resolve_music_playlists(ctx)
+            .await
+            .map_err(format_error)
+    }
+}
+
+impl MusicLibraryQuery {
+    async fn resolve_music_top_tracks(
+        &self,
+        ctx: &Context<'_>,
+        range: MusicTimeRange,
+        first: usize,
+    ) -> Result<Vec<MusicTrackObject>> {
+        ensure!(first <= 50, "can only take up to 50 tracks");
+        let tracks = ctx
+            .services()
+            .spotify()
+            .get_top_tracks(range.into())
+            .await
+            .context("failed to load top tracks from Spotify")?;
+        let tracks = tracks.into_iter().take(first).map(Into::into).collect();
+        Ok(tracks)
+    }
+
+    async fn resolve_music_top_artists(
+        &self,
+        ctx: &Context<'_>,
+        range: MusicTimeRange,
+        first: usize,
+    ) -> Result<Vec<MusicArtistObject>> {
+        ensure!(first <= 50, "can only take up to 50 artists");
+        let artists = ctx
+            .services()
+            .spotify()
+            .get_top_artists(range.into())
+            .await
+            .context("failed to load top artists from Spotify")?;
+        let artists = artists.into_iter().take(first).map(Into::into).collect();
+        Ok(artists)
+    }
+
+    async fn resolve_music_playlists(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<MusicPlaylistObject>> {
+        let playlists = ctx
+            .services()
+            .spotify()
+            .get_playlists()
+            .await
+            .context("failed to load playlists from Spotify")?;
+        let playlists = playlists.into_iter().map(Into::into).collect();
+        Ok(playlists)
+    }
+}
@@ -0,0 +1,317 @@
+use super::*;
+
+use services::spotify::Album as SpotifyAlbum;
+use services::spotify::Artist as SpotifyArtist;
+use services::spotify::Track as SpotifyTrack;
+use services::spotify::{ExternalURLs, Image};
+
+#[derive(Debug, Clone, From)]
+pub(super) struct MusicPlayObject(MusicPlay);
+
+#[Object(name = "MusicPlay")]
+impl MusicPlayObject {
+    async fn id(&self) -> Id<MusicPlay> {
+        let MusicPlayObject(play) = self;
+        play.id.into()
+    }
+
+    async fn track(&self) -> MusicTrackObject {
+        let MusicPlayObject(play) = self;
+        play.track.clone().into()
+    }
+
+    async fn started_at(&self) -> DateTimeScalar {
+        let MusicPlayObject(play) = self;
+        play.started_at.into()
+    }
+
+    async fn ended_at(&self) -> DateTimeScalar {
+        let MusicPlayObject(play) = self;
+        play.ended_at.into()
+    }
+
+    /// The furthest position reached in the track, in milliseconds.
+    async fn progress(&self) -> u32 {
+        let MusicPlayObject(play) = self;
+        play.progress
+    }
+}
+
+/// Listening statistics over a window of time.
+#[derive(Debug, Clone)]
+pub(super) struct MusicPlayStatsObject {
+    conditions: MusicPlayConditions,
+}
+
+#[Object(name = "MusicPlayStats")]
+impl MusicPlayStatsObject {
+    async fn play_count(&self, ctx: &Context<'_>) -> FieldResult<u32> {
+        self.resolve_play_count(ctx).await.map_err(format_error)
+    }
+
+    /// The total time spent listening, in milliseconds.
+    async fn listening_time(&self, ctx: &Context<'_>) -> FieldResult<u64> {
+        self.resolve_listening_time(ctx).await.map_err(format_error)
+    }
+
+    /// The most played tracks, most played first.
+    async fn top_tracks(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 10)] first: u32,
+    ) -> FieldResult<Vec<MusicTrackPlayCount>> {
+        self.resolve_top_tracks(ctx, first)
+            .await
+            .map_err(format_error)
+    }
+
+    /// The most played artists, most played first.
+    async fn top_artists(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 10)] first: u32,
+    ) -> FieldResult<Vec<MusicArtistPlayCount>> {
+        self.resolve_top_artists(ctx, first)
+            .await
+            .map_err(format_error)
+    }
+}
+
+impl MusicPlayStatsObject {
+    async fn resolve_play_count(&self, ctx: &Context<'_>) -> Result<u32> {
+        let MusicPlayTotals { play_count, .. } =
+            self.resolve_totals(ctx).await?;
+        Ok(play_count)
+    }
+
+    async fn resolve_listening_time(&self, ctx: &Context<'_>) -> Result<u64> {
+        let MusicPlayTotals { listening_time, .. } =
+            self.resolve_totals(ctx).await?;
+        Ok(listening_time)
+    }
+
+    async fn resolve_totals(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<MusicPlayTotals> {
+        let services = ctx.services();
+        let ctx = EntityContext::new(services.clone());
+        MusicPlay::totals(&ctx, &self.conditions)
+            .await
+            .context("failed to count plays")
+    }
+
+    async fn resolve_top_tracks(
+        &self,
+        ctx: &Context<'_>,
+        first: u32,
+    ) -> Result<Vec<MusicTrackPlayCount>> {
+        ensure!(first <= 50, "can only take up to 50 tracks");
+        let services = ctx.services();
+        let ctx = EntityContext::new(services.clone());
+        let counts = MusicPlay::top_tracks(&ctx, &self.conditions, first)
+            .await
+            .context("failed to count track plays")?;
+        let counts = counts
+            .into_iter()
+            .map(|count| {
+                let MusicPlayTrackCount { track, play_count } = count;
+                MusicTrackPlayCount {
+                    track: track.into(),
+                    play_count,
+                }
+            })
+            .collect();
+        Ok(counts)
+    }
+
+    async fn resolve_top_artists(
+        &self,
+        ctx: &Context<'_>,
+        first: u32,
+    ) -> Result<Vec<MusicArtistPlayCount>> {
+        ensure!(first <= 50, "can only take up to 50 artists");
+        let services = ctx.services();
+        let ctx = EntityContext::new(services.clone());
+        let counts = MusicPlay::top_artists(&ctx, &self.conditions, first)
+            .await
+            .context("failed to count artist plays")?;
+        let counts = counts
+            .into_iter()
+            .map(|count| {
+                let MusicPlayArtistCount { artist, play_count } = count;
+                MusicArtistPlayCount {
+                    artist: artist.into(),
+                    play_count,
+                }
+            })
+            .collect();
+        Ok(counts)
+    }
+}
+
+/// The URL of an item on Spotify, which isn't recorded with plays.
+fn spotify_url(kind: &str, id: &str) -> ExternalURLs {
+    ExternalURLs {
+        spotify: format!("https://open.spotify.com/{}/{}", kind, id),
+    }
+}
+
+impl From<MusicPlayTrack> for MusicTrackObject {
+    fn from(track: MusicPlayTrack) -> Self {
+        let MusicPlayTrack {
+            id,
+            name,
+            duration,
+            album,
+            artists,
+        } = track;
+        let MusicPlayAlbum {
+            id: album_id,
+            name: album_name,
+            image_url: album_image_url,
+        } = album;
+        let album = SpotifyAlbum {
+            external_urls: spotify_url("album", &album_id),
+            id: album_id,
+            name: album_name,
+            images: album_image_url
+                .into_iter()
+                .map(|url| Image {
+                    url,
+                    width: None,
+                    height: None,
+                })
+                .collect(),
+        };
+        let artists = artists.into_iter().map(spotify_artist).collect();
+        let track = SpotifyTrack {
+            external_urls: spotify_url("track", &id),
+            id,
+            name,
+            duration,
+            album,
+            artists,
+        };
+        track.into()
+    }
+}
+
+impl From<MusicPlayArtist> for MusicArtistObject {
+    fn from(artist: MusicPlayArtist) -> Self {
+        spotify_artist(artist).into()
+    }
+}
+
+fn spotify_artist(artist: MusicPlayArtist) -> SpotifyArtist {
+    let MusicPlayArtist { id, name } = artist;
+    SpotifyArtist {
+        external_urls: spotify_url("artist", &id),
+        id,
+        name,
+    }
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub(super) struct MusicTrackPlayCount {
+    pub track: MusicTrackObject,
+    pub play_count: u32,
+}
+
+#[derive(Debug, Clone, SimpleObject)]
+pub(super) struct MusicArtistPlayCount {
+    pub artist: MusicArtistObject,
+    pub play_count: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct MusicPlayQuery;
+
+#[Object]
+impl MusicPlayQuery {
+    async fn recent_music_plays(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 25)] first: usize,
+        after: Option<String>,
+    ) -> FieldResult<Connection<usize, MusicPlayObject>> {
+        self.resolve_recent_music_plays(ctx, first, after)
+            .await
+            .map_err(format_error)
+    }
+
+    /// Listening statistics for plays that started within a window of time,
+    /// which defaults to the last week.
+    async fn music_play_stats(
+        &self,
+        since: Option<DateTimeScalar>,
+        until: Option<DateTimeScalar>,
+    ) -> FieldResult<MusicPlayStatsObject> {
+        self.resolve_music_play_stats(since, until)
+            .await
+            .map_err(format_error)
+    }
+}
+
+impl MusicPlayQuery {
+    async fn resolve_recent_music_plays(
+        &self,
+        ctx: &Context<'_>,
+        first: usize,
+        after: Option<String>,
+    ) -> Result<Connection<usize, MusicPlayObject>> {
+        ensure!(first <= 100, "can only take up to 100 plays");
+        let offset = decode_offset_cursor(after)?;
+        let services = ctx.services();
+        let ctx = EntityContext::new(services.clone());
+
+        let count = MusicPlay::find(MusicPlayConditions::default())
+            .count(&ctx)
+            .await
+            .context("failed to count plays")?;
+        let plays = MusicPlay::find(MusicPlayConditions::default())
+            .skip(offset as u64)
+            .take(first as u64)
+            .sort(MusicPlaySorting::StartedAt(SortingDirection::Desc))
+            .load(&ctx)
+            .await
+            .context("failed to find plays")?;
+        let plays = plays
+            .try_collect::<Vec<_>>()
+            .await
+            .context("failed to load plays")?;
+
+        let count = count as usize;
+        let mut connection =
+            Connection::new(offset > 0, offset + first < count);
+        connection.append(
+            plays
+                .into_iter()
+                .enumerate()
+                .map(|(index, play)| Edge::new(offset + index, play.into())),
+        );
+        Ok(connection)
+    }
+
+    async fn resolve_music_play_stats(
+        &self,
+        since: Option<DateTimeScalar>,
+        until: Option<DateTimeScalar>,
+    ) -> Result<MusicPlayStatsObject> {
+        let until: DateTime = until.map(Into::into).unwrap_or_else(now);
+        let since: DateTime = since
+            .map(Into::into)
+            .unwrap_or_else(|| until - Duration::weeks(1));
+        ensure!(since < until, "window must start before it ends");
+        ensure!(
+            until - since <= Duration::days(366),
+            "window can be at most a year long"
+        );
+
+        let conditions = MusicPlayConditions::builder()
+            .started_since(since)
+            .started_until(until)
+            .build();
+        Ok(MusicPlayStatsObject { conditions })
+    }
+}
@@ -0,0 +1,68 @@
+use super::*;
+
+use services::spotify::Playlist as SpotifyPlaylist;
+
+#[derive(Debug, Clone, From)]
+pub(super) struct MusicPlaylistObject(SpotifyPlaylist);
+
+#[Object(name = "MusicPlaylist")]
+impl MusicPlaylistObject {
+    async fn spotify_id(&self) -> &str {
+        let MusicPlaylistObject(playlist) = self;
+        playlist.id.as_str()
+    }
+
+    async fn spotify_url(&self) -> FieldResult<Url> {
+        self.resolve_spotify_url().await.map_err(format_error)
+    }
+
+    async fn name(&self) -> &str {
+        let MusicPlaylistObject(playlist) = self;
+        playlist.name.as_str()
+    }
+
+    /// The playlist's description, which may contain HTML.
+    async fn description(&self) -> Option<&str> {
+        let MusicPlaylistObject(playlist) = self;
+        playlist
+            .description
+            .as_deref()
+            .filter(|description| !description.is_empty())
+    }
+
+    async fn owner_name(&self) -> Option<&str> {
+        let MusicPlaylistObject(playlist) = self;
+        playlist.owner.display_name.as_deref()
+    }
+
+    async fn track_count(&self) -> u32 {
+        let MusicPlaylistObject(playlist) = self;
+        playlist.tracks.total
+    }
+
+    async fn image_url(&self) -> FieldResult<Option<Url>> {
+        self.resolve_image_url().await.map_err(format_error)
+    }
+}
+
+impl MusicPlaylistObject {
+    async fn resolve_spotify_url(&self) -> Result<Url> {
+        let MusicPlaylistObject(playlist) = self;
+        let url: Url = playlist
+            .external_urls
+            .spotify
+            .parse()
+            .context("failed to parse URL")?;
+        Ok(url)
+    }
+
+    async fn resolve_image_url(&self) -> Result<Option<Url>> {
+        let MusicPlaylistObject(playlist) = self;
+        let image = match playlist.images.first() {
+            Some(image) => image,
+            None => return Ok(None),
+        };
+        let url: Url = image.url.parse().context("failed to parse URL")?;
+        Ok(Some(url))
+    }
+}
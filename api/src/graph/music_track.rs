@@ -1,6 +1,6 @@
 use super::*;
 
-use services::lyricly::Lyrics as LyriclyLyrics;
+use services::lyrics::LyricsKey;
 use services::spotify::Track as SpotifyTrack;
 
 #[derive(Debug, Clone, From)]
@@ -62,21 +62,13 @@ impl MusicTrackObject {
             Some(artist) => artist,
             None => return Ok(None),
         };
-        let lyrics = ctx
-            .services()
-            .lyricly()
-            .get_lyrics(&track.name, &artist.name)
-            .await?;
-        let lyrics = lyrics
-            .map(|lyrics| {
-                let LyriclyLyrics { lines } = &lyrics;
-                if lines.is_some() {
-                    Some(Lyrics::from(lyrics))
-                } else {
-                    None
-                }
-            })
-            .flatten();
+        let key = LyricsKey::builder()
+            .spotify_track_id(track.id.clone())
+            .track_name(track.name.clone())
+            .artist_name(artist.name.clone())
+            .build();
+        let lyrics = ctx.services().lyrics().get_lyrics(&key).await?;
+        let lyrics = lyrics.map(Lyrics::from);
         Ok(lyrics)
     }
 }
@@ -1,7 +1,7 @@
 use super::*;
 
 #[derive(Debug, Clone, Copy, Default, MergedObject)]
-pub struct Mutation(TestMutation, FormMutation);
+pub struct Mutation(TestMutation, FormMutation, LyricsMutation);
 
 impl Mutation {
     pub fn new() -> Self {
@@ -0,0 +1,64 @@
+use super::*;
+
+use services::spotify::Episode as SpotifyEpisode;
+
+#[derive(Debug, Clone, From)]
+pub(super) struct PodcastEpisodeObject(SpotifyEpisode);
+
+#[Object(name = "PodcastEpisode")]
+impl PodcastEpisodeObject {
+    async fn spotify_id(&self) -> &str {
+        let PodcastEpisodeObject(episode) = self;
+        episode.id.as_str()
+    }
+
+    async fn spotify_url(&self) -> FieldResult<Url> {
+        self.resolve_spotify_url().await.map_err(format_error)
+    }
+
+    async fn name(&self) -> &str {
+        let PodcastEpisodeObject(episode) = self;
+        episode.name.as_str()
+    }
+
+    async fn description(&self) -> &str {
+        let PodcastEpisodeObject(episode) = self;
+        episode.description.as_str()
+    }
+
+    async fn duration(&self) -> u32 {
+        let PodcastEpisodeObject(episode) = self;
+        episode.duration
+    }
+
+    async fn image_url(&self) -> FieldResult<Option<Url>> {
+        self.resolve_image_url().await.map_err(format_error)
+    }
+
+    async fn show(&self) -> PodcastShowObject {
+        let PodcastEpisodeObject(episode) = self;
+        episode.show.clone().into()
+    }
+}
+
+impl PodcastEpisodeObject {
+    async fn resolve_spotify_url(&self) -> Result<Url> {
+        let PodcastEpisodeObject(episode) = self;
+        let url: Url = episode
+            .external_urls
+            .spotify
+            .parse()
+            .context("failed to parse URL")?;
+        Ok(url)
+    }
+
+    async fn resolve_image_url(&self) -> Result<Option<Url>> {
+        let PodcastEpisodeObject(episode) = self;
+        let image = match episode.images.first() {
+            Some(image) => image,
+            None => return Ok(None),
+        };
+        let url: Url = image.url.parse().context("failed to parse URL")?;
+        Ok(Some(url))
+    }
+}
@@ -0,0 +1,54 @@
+use super::*;
+
+use services::spotify::Show as SpotifyShow;
+
+#[derive(Debug, Clone, From)]
+pub(super) struct PodcastShowObject(SpotifyShow);
+
+#[Object(name = "PodcastShow")]
+impl PodcastShowObject {
+    async fn spotify_id(&self) -> &str {
+        let PodcastShowObject(show) = self;
+        show.id.as_str()
+    }
+
+    async fn spotify_url(&self) -> FieldResult<Url> {
+        self.resolve_spotify_url().await.map_err(format_error)
+    }
+
+    async fn name(&self) -> &str {
+        let PodcastShowObject(show) = self;
+        show.name.as_str()
+    }
+
+    async fn publisher(&self) -> &str {
+        let PodcastShowObject(show) = self;
+        show.publisher.as_str()
+    }
+
+    async fn image_url(&self) -> FieldResult<Option<Url>> {
+        self.resolve_image_url().await.map_err(format_error)
+    }
+}
+
+impl PodcastShowObject {
+    async fn resolve_spotify_url(&self) -> Result<Url> {
+        let PodcastShowObject(show) = self;
+        let url: Url = show
+            .external_urls
+            .spotify
+            .parse()
+            .context("failed to parse URL")?;
+        Ok(url)
+    }
+
+    async fn resolve_image_url(&self) -> Result<Option<Url>> {
+        let PodcastShowObject(show) = self;
+        let image = match show.images.first() {
+            Some(image) => image,
+            None => return Ok(None),
+        };
+        let url: Url = image.url.parse().context("failed to parse URL")?;
+        Ok(Some(url))
+    }
+}
@@ -6,7 +6,11 @@ pub struct Query(
     BuildQuery,
     HeartRateQuery,
     MusicInfoQuery,
+    MusicLibraryQuery,
+    MusicPlayQuery,
     KnowledgeEntryQuery,
+    KnowledgeGraphQuery,
+    JournalEntryQuery,
     FormQuery,
     FormResponseQuery,
     UserQuery,
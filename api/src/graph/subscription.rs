@@ -3,7 +3,11 @@ use super::*;
 use graphql::EmptySubscription;
 
 #[derive(Debug, Clone, Copy, Default, MergedSubscription)]
-pub struct Subscription(TestSubscription);
+pub struct Subscription(
+    TestSubscription,
+    MusicInfoSubscription,
+    KnowledgeEntrySubscription,
+);
 
 impl Subscription {
     pub fn new() -> Self {
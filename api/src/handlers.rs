@@ -1,10 +1,16 @@
 mod graphql;
 mod graphql_playground;
 mod health_webhook;
+mod knowledge_attachment;
+mod knowledge_graph;
+mod spotify_auth;
 
 pub use self::graphql::*;
 pub use graphql_playground::*;
 pub use health_webhook::*;
+pub use knowledge_attachment::*;
+pub use knowledge_graph::*;
+pub use spotify_auth::*;
 
 use super::*;
 
@@ -29,6 +35,18 @@ pub type HandlerResult<T> = Result<T, HandlerError>;
 
 #[derive(Debug, Error)]
 pub enum HandlerError {
+    #[error("not found")]
+    NotFound,
+
+    #[error("not authenticated")]
+    Unauthorized,
+
+    #[error("not authorized")]
+    Forbidden,
+
+    #[error("{0}")]
+    BadRequest(String),
+
     #[error(transparent)]
     Other(#[from] Error),
 }
@@ -37,6 +55,12 @@ impl IntoResponse for HandlerError {
     fn into_response(self) -> Response {
         use HandlerError::*;
         let (status_code, message) = match self {
+            NotFound => (StatusCode::NOT_FOUND, "not found".to_owned()),
+            Unauthorized => {
+                (StatusCode::UNAUTHORIZED, "not authenticated".to_owned())
+            }
+            Forbidden => (StatusCode::FORBIDDEN, "not authorized".to_owned()),
+            BadRequest(message) => (StatusCode::BAD_REQUEST, message),
             Other(error) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", &error))
             }
@@ -0,0 +1,226 @@
+use super::*;
+
+use std::io::SeekFrom;
+use std::time::UNIX_EPOCH;
+
+use http::header::{ACCEPT_RANGES, CONTENT_LENGTH, CONTENT_RANGE};
+use http::header::{CONTENT_SECURITY_POLICY, X_CONTENT_TYPE_OPTIONS};
+use http::header::{CONTENT_TYPE, ETAG, IF_NONE_MATCH, RANGE};
+use http::HeaderMap;
+
+use axum::body::{Empty, Full, StreamBody};
+use axum::extract::Path as PathExtractor;
+
+use tokio::fs::metadata as read_metadata;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+
+use services::obsidian::{Attachment, AttachmentContent};
+
+/// Stops attachments that are opened directly (i.e. SVGs) from running
+/// scripts or loading anything, since they're served from the API's origin.
+const ATTACHMENT_CONTENT_SECURITY_POLICY: &str =
+    "default-src 'none'; style-src 'unsafe-inline'; sandbox";
+
+#[derive(Clone, Builder)]
+pub struct KnowledgeAttachmentExtension {
+    services: Services,
+}
+
+pub async fn knowledge_attachment_handler(
+    Extension(extension): Extension<KnowledgeAttachmentExtension>,
+    PathExtractor(id): PathExtractor<String>,
+    headers: HeaderMap,
+) -> HandlerResult<Response<BoxBody>> {
+    let KnowledgeAttachmentExtension { services } = extension;
+    let id = id.trim_start_matches('/');
+
+    // Attachments are looked up by ID in an index of the vault (rather than
+    // by joining paths), so files outside of the vault are never served.
+    let attachment = services
+        .obsidian()
+        .get_published_attachment(id)
+        .await
+        .context("failed to get attachment")?
+        .ok_or(HandlerError::NotFound)?;
+    let Attachment { id, content } = attachment;
+
+    let (size, modified) = match &content {
+        AttachmentContent::File(path) => {
+            let metadata = read_metadata(path)
+                .await
+                .context("failed to read attachment metadata")?;
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .map(|modified| modified.as_secs());
+            (metadata.len(), modified)
+        }
+        AttachmentContent::Memory { data, modified_at } => {
+            let modified = modified_at
+                .and_then(|modified| u64::try_from(modified.timestamp()).ok());
+            (data.len() as u64, modified)
+        }
+    };
+    let etag = format!(r#""{:x}-{:x}""#, modified.unwrap_or_default(), size);
+    let content_type = mime_guess::from_path(&id).first_or_octet_stream();
+
+    // Skip sending the attachment if the client's copy is up-to-date.
+    let is_fresh = headers
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| {
+            value.split(',').any(|tag| {
+                let tag = tag.trim();
+                tag == etag || tag == "*"
+            })
+        })
+        .unwrap_or_default();
+    if is_fresh {
+        let response = Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .header(ETAG, &etag)
+            .body(boxed(Empty::new()))
+            .context("failed to build response")?;
+        return Ok(response);
+    }
+
+    let range = headers
+        .get(RANGE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| parse_byte_range(value, size))
+        .unwrap_or(ByteRange::Full);
+    let (start, end) = match range {
+        ByteRange::Full => (0, size),
+        ByteRange::Partial { start, end } => (start, end),
+        ByteRange::Unsatisfiable => {
+            let response = Response::builder()
+                .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                .header(CONTENT_RANGE, format!("bytes */{}", size))
+                .body(boxed(Empty::new()))
+                .context("failed to build response")?;
+            return Ok(response);
+        }
+    };
+
+    let body = match content {
+        AttachmentContent::File(path) => {
+            let mut file = File::open(&path)
+                .await
+                .context("failed to open attachment")?;
+            file.seek(SeekFrom::Start(start))
+                .await
+                .context("failed to seek attachment")?;
+            let stream = ReaderStream::new(file.take(end - start));
+            boxed(StreamBody::new(stream))
+        }
+        AttachmentContent::Memory { data, .. } => {
+            let data = data[(start as usize)..(end as usize)].to_vec();
+            boxed(Full::from(data))
+        }
+    };
+    let mut response = Response::builder()
+        .header(CONTENT_TYPE, content_type.as_ref())
+        .header(CONTENT_LENGTH, end - start)
+        .header(ACCEPT_RANGES, "bytes")
+        .header(ETAG, &etag)
+        .header(X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .header(CONTENT_SECURITY_POLICY, ATTACHMENT_CONTENT_SECURITY_POLICY);
+    if let ByteRange::Partial { .. } = range {
+        response = response.status(StatusCode::PARTIAL_CONTENT).header(
+            CONTENT_RANGE,
+            format!("bytes {}-{}/{}", start, end - 1, size),
+        );
+    }
+    let response = response.body(body).context("failed to build response")?;
+    Ok(response)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ByteRange {
+    Full,
+
+    /// A range of bytes, from `start` (inclusive) to `end` (exclusive).
+    Partial {
+        start: u64,
+        end: u64,
+    },
+
+    Unsatisfiable,
+}
+
+/// Parse the value of a `Range` header, for a file of the given size.
+///
+/// Only single ranges are supported; requests for multiple ranges are
+/// served the entire file.
+fn parse_byte_range(value: &str, size: u64) -> ByteRange {
+    let spec = match value.trim().strip_prefix("bytes=") {
+        Some(spec) if !spec.contains(',') => spec.trim(),
+        _ => return ByteRange::Full,
+    };
+    let (start, end) = match spec.split_once('-') {
+        Some(bounds) => bounds,
+        None => return ByteRange::Full,
+    };
+    let (start, end) = (start.trim(), end.trim());
+
+    // Parse a suffix range (i.e. `bytes=-500`), which requests the last
+    // bytes of the file.
+    if start.is_empty() {
+        let length = match end.parse::<u64>() {
+            Ok(length) => length,
+            Err(_) => return ByteRange::Full,
+        };
+        if length == 0 || size == 0 {
+            return ByteRange::Unsatisfiable;
+        }
+        let start = size.saturating_sub(length);
+        return ByteRange::Partial { start, end: size };
+    }
+
+    let start = match start.parse::<u64>() {
+        Ok(start) => start,
+        Err(_) => return ByteRange::Full,
+    };
+    let end = if end.is_empty() {
+        size
+    } else {
+        match end.parse::<u64>() {
+            Ok(end) if end >= start => end.saturating_add(1).min(size),
+            _ => return ByteRange::Full,
+        }
+    };
+    if start >= size {
+        return ByteRange::Unsatisfiable;
+    }
+    ByteRange::Partial { start, end }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_byte_ranges() {
+        use ByteRange::*;
+        let partial = |start, end| Partial { start, end };
+        assert_eq!(parse_byte_range("bytes=0-99", 1000), partial(0, 100));
+        assert_eq!(parse_byte_range("bytes=500-", 1000), partial(500, 1000));
+        assert_eq!(parse_byte_range("bytes=-200", 1000), partial(800, 1000));
+        assert_eq!(
+            parse_byte_range("bytes=900-2000", 1000),
+            partial(900, 1000)
+        );
+        assert_eq!(
+            parse_byte_range("bytes=0-18446744073709551615", 1000),
+            partial(0, 1000)
+        );
+        assert_eq!(parse_byte_range("bytes=1000-", 1000), Unsatisfiable);
+        assert_eq!(parse_byte_range("bytes=-0", 1000), Unsatisfiable);
+        assert_eq!(parse_byte_range("bytes=0-1,5-9", 1000), Full);
+        assert_eq!(parse_byte_range("bytes=9-5", 1000), Full);
+        assert_eq!(parse_byte_range("items=0-9", 1000), Full);
+    }
+}
@@ -0,0 +1,128 @@
+use super::*;
+
+use http::header::CONTENT_TYPE;
+use services::obsidian::{NoteGraph, NoteGraphEdge, NoteGraphNode};
+
+#[derive(Clone, Builder)]
+pub struct KnowledgeGraphExtension {
+    services: Services,
+}
+
+pub async fn knowledge_graph_json_handler(
+    Extension(extension): Extension<KnowledgeGraphExtension>,
+) -> HandlerResult<JsonResponse<NoteGraph>> {
+    let KnowledgeGraphExtension { services } = extension;
+
+    // Only export published notes.
+    let graph = services
+        .obsidian()
+        .get_graph(false)
+        .await
+        .context("failed to load knowledge graph")?;
+    Ok(JsonResponse(graph))
+}
+
+pub async fn knowledge_graph_graphml_handler(
+    Extension(extension): Extension<KnowledgeGraphExtension>,
+) -> HandlerResult<Response<BoxBody>> {
+    let KnowledgeGraphExtension { services } = extension;
+
+    // Only export published notes.
+    let graph = services
+        .obsidian()
+        .get_graph(false)
+        .await
+        .context("failed to load knowledge graph")?;
+    let body = format_graphml(&graph);
+    let response = Response::builder()
+        .header(CONTENT_TYPE, "application/graphml+xml")
+        .body(boxed(Full::from(body)))
+        .context("failed to build response")?;
+    Ok(response)
+}
+
+fn format_graphml(graph: &NoteGraph) -> String {
+    let NoteGraph { nodes, edges } = graph;
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#);
+    xml.push('\n');
+    let keys = [
+        ("names", "string"),
+        ("tags", "string"),
+        ("ghost", "boolean"),
+    ];
+    for (key, kind) in keys {
+        xml.push_str(&format!(
+            r#"  <key id="{0}" for="node" attr.name="{0}" attr.type="{1}"/>"#,
+            key, kind
+        ));
+        xml.push('\n');
+    }
+    xml.push_str(r#"  <graph id="knowledge" edgedefault="directed">"#);
+    xml.push('\n');
+    for node in nodes {
+        let NoteGraphNode {
+            id,
+            names,
+            tags,
+            is_ghost,
+        } = node;
+        let names = {
+            let mut names = names.iter().cloned().collect::<Vec<_>>();
+            names.sort();
+            names.join(", ")
+        };
+        let tags = {
+            let mut tags = tags.iter().cloned().collect::<Vec<_>>();
+            tags.sort();
+            tags.join(", ")
+        };
+        xml.push_str(&format!(r#"    <node id="{}">"#, escape_xml(id)));
+        xml.push('\n');
+        xml.push_str(&format!(
+            r#"      <data key="names">{}</data>"#,
+            escape_xml(&names)
+        ));
+        xml.push('\n');
+        xml.push_str(&format!(
+            r#"      <data key="tags">{}</data>"#,
+            escape_xml(&tags)
+        ));
+        xml.push('\n');
+        xml.push_str(&format!(
+            r#"      <data key="ghost">{}</data>"#,
+            is_ghost
+        ));
+        xml.push('\n');
+        xml.push_str("    </node>\n");
+    }
+    for edge in edges {
+        let NoteGraphEdge { source, target } = edge;
+        xml.push_str(&format!(
+            r#"    <edge source="{}" target="{}"/>"#,
+            escape_xml(source),
+            escape_xml(target)
+        ));
+        xml.push('\n');
+    }
+    xml.push_str("  </graph>\n");
+    xml.push_str("</graphml>\n");
+    xml
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(char),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,130 @@
+use super::*;
+
+use axum::extract::Query as QueryExtractor;
+
+use cookie::{Cookie, SameSite};
+use tower_cookies::Cookies;
+
+use headers::authorization::Bearer;
+use headers::Authorization;
+
+const STATE_COOKIE: &str = "spotify_auth_state";
+const VERIFIER_COOKIE: &str = "spotify_auth_verifier";
+const COOKIE_PATH: &str = "/auth/spotify";
+
+#[derive(Clone, Builder)]
+pub struct SpotifyAuthExtension {
+    services: Services,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpotifyAuthStartResponse {
+    /// The URL to send the admin to, to authorize access on Spotify.
+    url: String,
+}
+
+/// Start authorizing access to an admin's Spotify account.
+///
+/// This is requested by the web app (which holds the admin's access token),
+/// and responds with the URL to send the admin to. Spotify then redirects
+/// back to the callback on this server, so the state and verifier cookies
+/// are only sent back if the web app and this server share a site.
+pub async fn spotify_auth_start_handler(
+    Extension(extension): Extension<SpotifyAuthExtension>,
+    authorization: Option<HeaderExtractor<Authorization<Bearer>>>,
+    cookies: Cookies,
+) -> HandlerResult<JsonResponse<SpotifyAuthStartResponse>> {
+    let SpotifyAuthExtension { services } = extension;
+
+    // Authorizing is pointless if the credentials can't be stored
+    if !services.spotify().has_token_store() {
+        let error = Error::msg("missing token store");
+        return Err(error.into());
+    }
+
+    // Only admins may authorize
+    let HeaderExtractor(Authorization(bearer)) =
+        authorization.ok_or(HandlerError::Unauthorized)?;
+    let userinfo = services
+        .auth0()
+        .userinfo(bearer.token())
+        .await
+        .map_err(|_| HandlerError::Unauthorized)?;
+    if !userinfo.is_admin {
+        return Err(HandlerError::Forbidden);
+    }
+
+    // Remember the state and verifier until Spotify redirects back
+    let request = services.spotify().authorize_url();
+    let is_secure = services.settings().base_url.scheme() == "https";
+    for (name, value) in [
+        (STATE_COOKIE, request.state),
+        (VERIFIER_COOKIE, request.verifier),
+    ] {
+        let cookie = Cookie::build(name, value)
+            .path(COOKIE_PATH)
+            .http_only(true)
+            .secure(is_secure)
+            .same_site(SameSite::Lax)
+            .finish();
+        cookies.add(cookie);
+    }
+
+    let response = SpotifyAuthStartResponse {
+        url: request.url.into(),
+    };
+    Ok(JsonResponse(response))
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpotifyAuthCallbackQuery {
+    code: Option<String>,
+    state: Option<String>,
+    error: Option<String>,
+}
+
+/// Finish authorizing with the code that Spotify redirected back with.
+pub async fn spotify_auth_callback_handler(
+    Extension(extension): Extension<SpotifyAuthExtension>,
+    QueryExtractor(query): QueryExtractor<SpotifyAuthCallbackQuery>,
+    cookies: Cookies,
+) -> HandlerResult<&'static str> {
+    let SpotifyAuthExtension { services } = extension;
+    let SpotifyAuthCallbackQuery { code, state, error } = query;
+
+    // Read (and forget) the state and verifier from the start of the flow,
+    // which only admins could have started
+    let (expected_state, verifier) = {
+        let mut values = [STATE_COOKIE, VERIFIER_COOKIE].map(|name| {
+            let value =
+                cookies.get(name).map(|cookie| cookie.value().to_owned());
+            cookies.remove(Cookie::build(name, "").path(COOKIE_PATH).finish());
+            value
+        });
+        (values[0].take(), values[1].take())
+    };
+    if let Some(error) = error {
+        let message = format!("authorization failed: {}", error);
+        return Err(HandlerError::BadRequest(message));
+    }
+    let (expected_state, verifier) = match (expected_state, verifier) {
+        (Some(state), Some(verifier)) => (state, verifier),
+        _ => {
+            let message = "authorization not started".to_owned();
+            return Err(HandlerError::BadRequest(message));
+        }
+    };
+    if state.as_deref() != Some(expected_state.as_str()) {
+        let message = "invalid state".to_owned();
+        return Err(HandlerError::BadRequest(message));
+    }
+    let code = code.ok_or_else(|| {
+        HandlerError::BadRequest("missing authorization code".to_owned())
+    })?;
+
+    // Authorize, which swaps in (and stores) the new credentials right away
+    services.spotify().authorize(&code, &verifier).await?;
+
+    info!("authorized with Spotify");
+    Ok("Authorized with Spotify.")
+}
@@ -2,9 +2,11 @@
 
 pub mod auth;
 pub mod config;
+pub mod crypto;
 pub mod entities;
 pub mod graph;
 pub mod handlers;
+pub mod pollers;
 pub mod services;
 pub mod util;
 
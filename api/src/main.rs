@@ -1,24 +1,38 @@
 use api::config::{env, env_opt, load_env, set_env};
 use api::config::{PACKAGE_NAME, PROJECT_NAME};
+use api::crypto::Cipher;
 use api::entities::BuildInfo;
+use api::entities::{AuthTokenStore, MusicPlayStore, TrackLyricsStore};
 use api::graph::{Mutation, Query, Subscription};
 use api::handlers::graphql_handler;
 use api::handlers::graphql_playground_handler;
 use api::handlers::health_webhook_handler;
+use api::handlers::knowledge_attachment_handler;
+use api::handlers::knowledge_graph_graphml_handler;
+use api::handlers::knowledge_graph_json_handler;
+use api::handlers::spotify_auth_callback_handler;
+use api::handlers::spotify_auth_start_handler;
 use api::handlers::GraphQLExtension;
 use api::handlers::GraphQLPlaygroundExtension;
 use api::handlers::HealthWebhookExtension;
+use api::handlers::KnowledgeAttachmentExtension;
+use api::handlers::KnowledgeGraphExtension;
+use api::handlers::SpotifyAuthExtension;
+use api::pollers::{MusicPlayPoller, MusicPlayPollerConfig};
+use api::services::lyrics::ProviderKind as LyricsProviderKind;
 use api::services::Config as ServicesConfig;
-use api::services::LyriclyService;
+use api::services::ObsidianVaultFormat;
 use api::services::Services;
 use api::services::Settings;
 use api::services::{Auth0Service, Auth0ServiceConfig};
+use api::services::{LyricsService, LyricsServiceConfig};
 use api::services::{ObsidianService, ObsidianServiceConfig};
 use api::services::{SegmentService, SegmentServiceConfig};
 use api::services::{SpotifyService, SpotifyServiceConfig};
 use api::util::default;
 
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use anyhow::Context as AnyhowContext;
 use anyhow::Result;
@@ -65,6 +79,7 @@ use sentry_tracing::layer as sentry_tracing_layer;
 use bson::doc;
 use chrono::{DateTime, FixedOffset};
 use tokio::main as tokio;
+use tokio::task::spawn;
 use url::Url;
 
 async fn run() -> Result<()> {
@@ -146,6 +161,12 @@ async fn run() -> Result<()> {
         })
         .build();
 
+    // Read encryption key, which is used to store secrets
+    let cipher = env_opt("ENCRYPTION_KEY")?
+        .map(|key| key.parse::<Cipher>())
+        .transpose()
+        .context("failed to parse ENCRYPTION_KEY")?;
+
     // Connect to database
     let database_client = MongoClient::with_options({
         let uri = env_opt("MONGO_URI")?;
@@ -185,8 +206,50 @@ async fn run() -> Result<()> {
     // Build Obsidian service
     let obsidian = ObsidianService::new({
         let vault_path = env("OBSIDIAN_VAULT_PATH")?;
+        let vault_format = env_opt("OBSIDIAN_VAULT_FORMAT")?
+            .map(|format| format.parse::<ObsidianVaultFormat>())
+            .transpose()
+            .context("failed to parse OBSIDIAN_VAULT_FORMAT")?
+            .unwrap_or_default();
+        let watch = env_opt("OBSIDIAN_VAULT_WATCH")?
+            .map(|watch| watch.parse::<bool>())
+            .transpose()
+            .context("failed to parse OBSIDIAN_VAULT_WATCH")?
+            .unwrap_or_default();
+        let history = env_opt("OBSIDIAN_VAULT_HISTORY")?
+            .map(|history| history.parse::<bool>())
+            .transpose()
+            .context("failed to parse OBSIDIAN_VAULT_HISTORY")?
+            .unwrap_or_default();
+        let publish_include = env_opt("OBSIDIAN_PUBLISH_INCLUDE")?
+            .map(|patterns| {
+                patterns
+                    .split(',')
+                    .map(ToOwned::to_owned)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_else(|| vec!["**".to_owned()]);
+        let publish_exclude = env_opt("OBSIDIAN_PUBLISH_EXCLUDE")?
+            .map(|patterns| {
+                patterns
+                    .split(',')
+                    .map(ToOwned::to_owned)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let journal_folder =
+            env_opt("OBSIDIAN_JOURNAL_FOLDER")?.unwrap_or_default();
+        let journal_date_format = env_opt("OBSIDIAN_JOURNAL_DATE_FORMAT")?
+            .unwrap_or_else(|| "%Y-%m-%d".to_owned());
         ObsidianServiceConfig::builder()
             .vault_path(vault_path)
+            .vault_format(vault_format)
+            .watch(watch)
+            .publish_include(publish_include)
+            .publish_exclude(publish_exclude)
+            .history(history)
+            .journal_folder(journal_folder)
+            .journal_date_format(journal_date_format)
             .build()
     })
     .context("failed to initialize Obsidian client")?;
@@ -195,16 +258,38 @@ async fn run() -> Result<()> {
     let spotify = SpotifyService::new({
         let client_id = env("SPOTIFY_CLIENT_ID")?;
         let client_secret = env("SPOTIFY_CLIENT_SECRET")?;
-        let refresh_token = env("SPOTIFY_REFRESH_TOKEN")?;
+        let refresh_token = env_opt("SPOTIFY_REFRESH_TOKEN")?;
+        let redirect_url = settings
+            .base_url
+            .join("/auth/spotify/callback")
+            .context("failed to build Spotify redirect URL")?;
         SpotifyServiceConfig::builder()
             .client_id(client_id)
             .client_secret(client_secret)
             .refresh_token(refresh_token)
+            .redirect_url(redirect_url)
             .build()
-    });
-
-    // Build Lyricly service
-    let lyricly = LyriclyService::new();
+    })
+    .context("failed to initialize Spotify service")?;
+
+    // Build lyrics service
+    let lyrics = LyricsService::new({
+        let providers = env_opt("LYRICS_PROVIDERS")?
+            .map(|providers| {
+                providers
+                    .split(',')
+                    .map(|provider| provider.parse::<LyricsProviderKind>())
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()
+            .context("failed to parse LYRICS_PROVIDERS")?;
+        let lrc_dir = env_opt("LYRICS_LRC_DIR")?;
+        LyricsServiceConfig::builder()
+            .providers(providers)
+            .lrc_dir(lrc_dir)
+            .build()
+    })
+    .context("failed to initialize lyrics service")?;
 
     // Build Auth0 service
     let auth0 = Auth0Service::new({
@@ -225,11 +310,47 @@ async fn run() -> Result<()> {
             .obsidian(obsidian)
             .segment(segment)
             .spotify(spotify)
-            .lyricly(lyricly)
+            .lyrics(lyrics)
             .auth0(auth0)
             .build()
     });
 
+    // Persist credentials for OAuth providers, restoring them from the last
+    // run
+    match &cipher {
+        Some(cipher) => {
+            let store = AuthTokenStore::new(services.clone(), cipher.clone());
+            services
+                .spotify()
+                .set_token_store(Arc::new(store))
+                .await
+                .context("failed to restore Spotify credentials")?;
+        }
+        None => warn!("skipping credential storage (missing encryption key)"),
+    }
+
+    // Store lyrics, so that they survive restarts
+    services
+        .lyrics()
+        .set_store(Arc::new(TrackLyricsStore::new(services.clone())));
+
+    // Record music plays in the background
+    let record_music_plays = env_opt("SPOTIFY_RECORD_PLAYS")?
+        .map(|record| record.parse::<bool>())
+        .transpose()
+        .context("failed to parse SPOTIFY_RECORD_PLAYS")?
+        .unwrap_or_default();
+    if record_music_plays {
+        let poller = MusicPlayPoller::new({
+            MusicPlayPollerConfig::builder()
+                .spotify(services.spotify().clone())
+                .store(Arc::new(MusicPlayStore::new(services.clone())))
+                .build()
+        })
+        .context("failed to initialize music play poller")?;
+        spawn(poller.run());
+    }
+
     // Build GraphQL schema
     let graphql_schema = {
         let query = Query::default();
@@ -253,10 +374,20 @@ async fn run() -> Result<()> {
         .schema(graphql_schema.clone())
         .services(services.clone())
         .build();
+    let knowledge_attachment_extension =
+        KnowledgeAttachmentExtension::builder()
+            .services(services.clone())
+            .build();
+    let knowledge_graph_extension = KnowledgeGraphExtension::builder()
+        .services(services.clone())
+        .build();
+    let spotify_auth_extension = SpotifyAuthExtension::builder()
+        .services(services.clone())
+        .build();
     let graphql_playground_extension =
         GraphQLPlaygroundExtension::new(&services)
             .context("failed to initialize GraphQL playground")?;
-    let graphql_layer = CorsLayer::new()
+    let cors_layer = CorsLayer::new()
         .allow_methods(vec![Method::GET, Method::POST])
         .allow_headers(vec![
             CONTENT_TYPE,
@@ -320,7 +451,7 @@ async fn run() -> Result<()> {
                     | MethodFilter::OPTIONS
                     | MethodFilter::GET
                     | MethodFilter::POST,
-                graphql_handler.layer(graphql_layer),
+                graphql_handler.layer(cors_layer.clone()),
             ),
         )
         .route(
@@ -329,7 +460,24 @@ async fn run() -> Result<()> {
                 MethodFilter::HEAD | MethodFilter::OPTIONS | MethodFilter::POST,
                 health_webhook_handler,
             ),
-        );
+        )
+        .route("/knowledge/graph.json", get(knowledge_graph_json_handler))
+        .route(
+            "/knowledge/graph.graphml",
+            get(knowledge_graph_graphml_handler),
+        )
+        .route(
+            "/knowledge/attachments/*id",
+            get(knowledge_attachment_handler),
+        )
+        .route(
+            "/auth/spotify/start",
+            on(
+                MethodFilter::OPTIONS | MethodFilter::POST,
+                spotify_auth_start_handler.layer(cors_layer),
+            ),
+        )
+        .route("/auth/spotify/callback", get(spotify_auth_callback_handler));
 
     // Build service
     let service = routes
@@ -338,6 +486,9 @@ async fn run() -> Result<()> {
                 .layer(AddExtensionLayer::new(health_webhook_extension))
                 .layer(AddExtensionLayer::new(graphql_extension))
                 .layer(AddExtensionLayer::new(graphql_playground_extension))
+                .layer(AddExtensionLayer::new(knowledge_attachment_extension))
+                .layer(AddExtensionLayer::new(knowledge_graph_extension))
+                .layer(AddExtensionLayer::new(spotify_auth_extension))
                 .layer(CookieManagerLayer::new())
                 .layer(TraceLayer::new_for_http())
         })
@@ -0,0 +1,10 @@
+mod music_play;
+
+pub use music_play::*;
+
+use super::*;
+
+use entities::*;
+
+use tokio::time::interval as tick_interval;
+use tokio::time::MissedTickBehavior;
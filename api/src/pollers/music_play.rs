@@ -0,0 +1,161 @@
+use super::*;
+
+use services::spotify::{CurrentlyPlaying, PlayingItem};
+use services::SpotifyService;
+
+/// Progress (in milliseconds) below which a track that jumps backwards is
+/// considered to have been replayed, rather than rewound.
+pub const REPLAY_THRESHOLD: u32 = 10_000;
+
+/// Persists recorded plays, both while they're in progress and once they've
+/// finished.
+#[async_trait]
+pub trait PlayStore: Debug + Send + Sync {
+    async fn save(&self, play: &MusicPlay) -> Result<()>;
+}
+
+#[derive(Debug, Clone, Builder)]
+pub struct MusicPlayPollerConfig {
+    spotify: SpotifyService,
+    store: Arc<dyn PlayStore>,
+
+    #[builder(default = Duration::seconds(15))]
+    interval: Duration,
+
+    /// How often to save the progress of a play that's still in progress,
+    /// so that it isn't lost on a restart.
+    #[builder(default = Duration::minutes(1))]
+    save_interval: Duration,
+}
+
+/// Records tracks played on Spotify as `MusicPlay`s, by periodically
+/// polling the currently playing track.
+#[derive(Debug)]
+pub struct MusicPlayPoller {
+    spotify: SpotifyService,
+    store: Arc<dyn PlayStore>,
+    interval: StdDuration,
+    save_interval: Duration,
+
+    /// The play that is currently in progress (or paused).
+    current: Option<MusicPlay>,
+
+    /// When the current play was last saved.
+    saved_at: Option<DateTime>,
+}
+
+impl MusicPlayPoller {
+    pub fn new(config: MusicPlayPollerConfig) -> Result<Self> {
+        let MusicPlayPollerConfig {
+            spotify,
+            store,
+            interval,
+            save_interval,
+        } = config;
+        let interval = interval.to_std().context("invalid interval")?;
+        let poller = Self {
+            spotify,
+            store,
+            interval,
+            save_interval,
+            current: None,
+            saved_at: None,
+        };
+        Ok(poller)
+    }
+
+    /// Poll for plays until the task is cancelled.
+    pub async fn run(mut self) {
+        let mut ticks = tick_interval(self.interval);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            if let Err(error) = self.poll().await {
+                error!(
+                    error = %format!("{:#}", error),
+                    "failed to record music plays"
+                );
+            }
+        }
+    }
+
+    /// Check the currently playing track once, and record any change to
+    /// the current play.
+    pub async fn poll(&mut self) -> Result<()> {
+        let currently_playing = self
+            .spotify
+            .get_currently_playing()
+            .await
+            .context("failed to get currently playing track")?;
+        let now = now();
+
+        // Only tracks are recorded, so anything else (i.e. an episode or an
+        // ad) finishes the current play.
+        let (is_playing, track, progress) = match currently_playing {
+            Some(CurrentlyPlaying {
+                is_playing,
+                item: Some(PlayingItem::Track(track)),
+                progress,
+                ..
+            }) => (is_playing, track, progress),
+            _ => return self.finish().await,
+        };
+
+        // Continue the current play if the same track is still playing (or
+        // paused).
+        if let Some(play) = &mut self.current {
+            let is_replay =
+                progress < play.progress && progress < REPLAY_THRESHOLD;
+            if play.track.id == track.id && !is_replay {
+                if is_playing {
+                    play.ended_at = now;
+                    play.progress = play.progress.max(progress);
+                }
+                let is_due = match self.saved_at {
+                    Some(saved_at) => now - saved_at >= self.save_interval,
+                    None => true,
+                };
+                if is_due {
+                    self.store
+                        .save(play)
+                        .await
+                        .context("failed to save play")?;
+                    self.saved_at = Some(now);
+                }
+                return Ok(());
+            }
+        }
+        self.finish().await?;
+        if !is_playing {
+            return Ok(());
+        }
+
+        let play = MusicPlay::builder()
+            .track(track.into())
+            .started_at(now - Duration::milliseconds(progress.into()))
+            .ended_at(now)
+            .progress(progress)
+            .build();
+        self.store
+            .save(&play)
+            .await
+            .context("failed to save play")?;
+        debug!(track = %play.track.name, "started music play");
+        self.current = Some(play);
+        self.saved_at = Some(now);
+        Ok(())
+    }
+
+    /// Save the current play, if any, with its final progress.
+    async fn finish(&mut self) -> Result<()> {
+        if let Some(play) = self.current.take() {
+            self.saved_at = None;
+            self.store
+                .save(&play)
+                .await
+                .context("failed to save play")?;
+            debug!(track = %play.track.name, "finished music play");
+        }
+        Ok(())
+    }
+}
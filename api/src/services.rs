@@ -1,5 +1,6 @@
 pub mod auth0;
 pub mod lyricly;
+pub mod lyrics;
 pub mod obsidian;
 pub mod segment;
 pub mod spotify;
@@ -9,8 +10,12 @@ pub use self::segment::ServiceConfig as SegmentServiceConfig;
 pub use auth0::Service as Auth0Service;
 pub use auth0::ServiceConfig as Auth0ServiceConfig;
 pub use lyricly::Service as LyriclyService;
+pub use lyricly::ServiceConfig as LyriclyServiceConfig;
+pub use lyrics::Service as LyricsService;
+pub use lyrics::ServiceConfig as LyricsServiceConfig;
 pub use obsidian::Service as ObsidianService;
 pub use obsidian::ServiceConfig as ObsidianServiceConfig;
+pub use obsidian::VaultFormat as ObsidianVaultFormat;
 pub use spotify::Service as SpotifyService;
 pub use spotify::ServiceConfig as SpotifyServiceConfig;
 
@@ -27,7 +32,7 @@ pub struct Config {
     pub obsidian: ObsidianService,
     pub segment: SegmentService,
     pub spotify: SpotifyService,
-    pub lyricly: LyriclyService,
+    pub lyrics: LyricsService,
     pub auth0: Auth0Service,
 }
 
@@ -39,7 +44,7 @@ struct ServicesInner {
     obsidian: ObsidianService,
     segment: SegmentService,
     spotify: SpotifyService,
-    lyricly: LyriclyService,
+    lyrics: LyricsService,
     auth0: Auth0Service,
 }
 
@@ -68,8 +73,8 @@ impl ServicesInner {
         &self.spotify
     }
 
-    fn lyricly(&self) -> &LyriclyService {
-        &self.lyricly
+    fn lyrics(&self) -> &LyricsService {
+        &self.lyrics
     }
 
     fn auth0(&self) -> &Auth0Service {
@@ -89,7 +94,7 @@ impl Services {
             obsidian,
             segment,
             spotify,
-            lyricly,
+            lyrics,
             auth0,
         } = config;
 
@@ -100,7 +105,7 @@ impl Services {
             obsidian,
             segment,
             spotify,
-            lyricly,
+            lyrics,
             auth0,
         };
         Services(inner.into())
@@ -114,7 +119,7 @@ impl Services {
             pub fn obsidian(&self) -> &ObsidianService;
             pub fn segment(&self) -> &SegmentService;
             pub fn spotify(&self) -> &SpotifyService;
-            pub fn lyricly(&self) -> &LyriclyService;
+            pub fn lyrics(&self) -> &LyricsService;
             pub fn auth0(&self) -> &Auth0Service;
         }
     }
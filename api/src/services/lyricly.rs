@@ -1,11 +1,29 @@
 use super::*;
 
+use lyrics::LyricLine as ProviderLyricLine;
+use lyrics::Lyrics as ProviderLyrics;
+use lyrics::LyricsProvider;
+
 use http::StatusCode;
 
+#[derive(Debug, Clone, Builder)]
+pub struct ServiceConfig {
+    /// The base URL of the Lyricly API, which may be replaced with a local
+    /// stand-in during tests.
+    #[builder(
+        default = "https://lyricly.azurewebsites.net/api/".parse().unwrap()
+    )]
+    pub base_url: Url,
+
+    #[builder(default = Duration::hours(1))]
+    pub ttl: Duration,
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct Service {
     client: HttpClient,
+    base_url: Url,
 
     #[derivative(Debug = "ignore")]
     lyrics_cache: Cache<LyricsKey, Lyrics>,
@@ -21,11 +39,13 @@ struct LyricsKey {
 }
 
 impl Service {
-    pub fn new() -> Self {
+    pub fn new(config: ServiceConfig) -> Self {
+        let ServiceConfig { base_url, ttl } = config;
         Service {
             client: default(),
+            base_url,
             lyrics_cache: Cache::builder(1000)
-                .time_to_live(Duration::hours(1).to_std().unwrap())
+                .time_to_live(ttl.to_std().unwrap())
                 .build(),
             lyrics_sem: Semaphore::new(1),
         }
@@ -34,7 +54,7 @@ impl Service {
 
 impl Default for Service {
     fn default() -> Self {
-        Self::new()
+        Self::new(ServiceConfig::builder().build())
     }
 }
 
@@ -46,6 +66,7 @@ impl Service {
     ) -> Result<Option<Lyrics>> {
         let Self {
             client,
+            base_url,
             lyrics_sem: sem,
             lyrics_cache: cache,
             ..
@@ -70,9 +91,8 @@ impl Service {
 
         // Fetch new lyrics.
         let url = {
-            let mut url: Url = "https://lyricly.azurewebsites.net/api/lyrics"
-                .parse()
-                .unwrap();
+            let mut url =
+                base_url.join("lyrics").context("failed to build URL")?;
             {
                 let mut query = url.query_pairs_mut();
                 query.append_pair("id", "0");
@@ -103,6 +123,33 @@ impl Service {
     }
 }
 
+#[async_trait]
+impl LyricsProvider for Service {
+    fn name(&self) -> &'static str {
+        "lyricly"
+    }
+
+    async fn get_lyrics(
+        &self,
+        track_name: &str,
+        artist_name: &str,
+    ) -> Result<Option<ProviderLyrics>> {
+        let lyrics = Service::get_lyrics(self, track_name, artist_name).await?;
+        let lines = match lyrics.and_then(|lyrics| lyrics.lines) {
+            Some(lines) => lines,
+            None => return Ok(None),
+        };
+        let lines = lines
+            .into_iter()
+            .map(|line| {
+                let LyricLine { text, position } = line;
+                ProviderLyricLine { text, position }
+            })
+            .collect();
+        Ok(Some(ProviderLyrics::Synced(lines)))
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Lyrics {
     #[serde(alias = "synced")]
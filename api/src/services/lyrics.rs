@@ -0,0 +1,349 @@
+use super::*;
+
+use lyricly::Service as LyriclyService;
+use lyricly::ServiceConfig as LyriclyServiceConfig;
+
+use std::sync::RwLock as SyncRwLock;
+
+mod lrc;
+
+pub use lrc::{parse_lrc, LrcProvider};
+
+/// A source of lyrics for tracks.
+#[async_trait]
+pub trait LyricsProvider: Debug + Send + Sync {
+    /// The name of the provider, for logging.
+    fn name(&self) -> &'static str;
+
+    /// Get the lyrics for a track, or `None` if the provider doesn't have
+    /// them.
+    async fn get_lyrics(
+        &self,
+        track_name: &str,
+        artist_name: &str,
+    ) -> Result<Option<Lyrics>>;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "content", rename_all = "camelCase")]
+pub enum Lyrics {
+    /// Lines with the positions at which they're sung.
+    Synced(Vec<LyricLine>),
+
+    /// Plain text, without any timing.
+    Plain(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LyricLine {
+    pub text: String,
+
+    /// The position in the track at which the line is sung, in
+    /// milliseconds.
+    pub position: u32,
+}
+
+/// Identifies the track whose lyrics are wanted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Builder)]
+pub struct LyricsKey {
+    /// The track's ID on Spotify, which is preferred to its name (since
+    /// different tracks may share a name).
+    #[builder(default, setter(into))]
+    pub spotify_track_id: Option<String>,
+
+    pub track_name: String,
+    pub artist_name: String,
+}
+
+/// Persists lyrics (including the lack of them), so that they survive
+/// restarts.
+#[async_trait]
+pub trait LyricsStore: Debug + Send + Sync {
+    /// Load the stored lyrics for a track, whether or not they've expired.
+    ///
+    /// Lyrics are looked up by Spotify track ID first, and then by name
+    /// among lyrics that were stored without a track ID.
+    async fn load(&self, key: &LyricsKey) -> Result<Option<StoredLyrics>>;
+
+    async fn save(&self, key: &LyricsKey, lyrics: StoredLyrics) -> Result<()>;
+}
+
+#[derive(Debug, Clone)]
+pub struct StoredLyrics {
+    /// The lyrics, or `None` if they weren't found.
+    pub lyrics: Option<Lyrics>,
+
+    /// When to look the lyrics up again, or `None` to keep them for good
+    /// (i.e. when they were set by hand).
+    pub expires_at: Option<DateTime>,
+}
+
+impl StoredLyrics {
+    pub fn is_expired(&self) -> bool {
+        match self.expires_at {
+            Some(expires_at) => now() >= expires_at,
+            None => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderKind {
+    /// The Lyricly API.
+    Lyricly,
+
+    /// A directory of `.lrc` files, named like `Artist - Title.lrc`.
+    Lrc,
+}
+
+impl FromStr for ProviderKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use ProviderKind::*;
+        let kind = match s.trim().to_lowercase().as_str() {
+            "lyricly" => Lyricly,
+            "lrc" => Lrc,
+            _ => bail!("unknown lyrics provider {:?}", s),
+        };
+        Ok(kind)
+    }
+}
+
+#[derive(Debug, Clone, Builder)]
+pub struct ServiceConfig {
+    /// The providers to try, in order.
+    ///
+    /// Defaults to the `.lrc` directory (if there is one), and then
+    /// Lyricly.
+    #[builder(default, setter(into))]
+    pub providers: Option<Vec<ProviderKind>>,
+
+    #[builder(default = LyriclyServiceConfig::builder().build())]
+    pub lyricly: LyriclyServiceConfig,
+
+    /// The directory to read `.lrc` files from.
+    #[builder(default, setter(into))]
+    pub lrc_dir: Option<String>,
+
+    /// How long to keep stored lyrics before looking them up again.
+    #[builder(default = Duration::days(30))]
+    pub ttl: Duration,
+
+    /// How long to remember that lyrics weren't found, which is shorter
+    /// than `ttl` since providers may pick them up later.
+    #[builder(default = Duration::days(1))]
+    pub not_found_ttl: Duration,
+
+    /// How long to keep lyrics in memory, in front of the store.
+    #[builder(default = Duration::minutes(10))]
+    pub cache_ttl: Duration,
+}
+
+/// Gets lyrics from a chain of providers, falling back to the next provider
+/// whenever one doesn't have the lyrics (or fails).
+///
+/// With a `LyricsStore`, lyrics are stored once they're found (or not), and
+/// served from the store until they expire.
+#[derive(Derivative, Clone)]
+#[derivative(Debug)]
+pub struct Service {
+    providers: Vec<Arc<dyn LyricsProvider>>,
+    store: Arc<SyncRwLock<Option<Arc<dyn LyricsStore>>>>,
+    ttl: Duration,
+    not_found_ttl: Duration,
+
+    #[derivative(Debug = "ignore")]
+    cache: Cache<LyricsKey, Option<Lyrics>>,
+}
+
+impl Service {
+    pub fn new(config: ServiceConfig) -> Result<Self> {
+        let ServiceConfig {
+            providers,
+            lyricly,
+            lrc_dir,
+            ttl,
+            not_found_ttl,
+            cache_ttl,
+        } = config;
+        let kinds = providers.unwrap_or_else(|| {
+            use ProviderKind::*;
+            match lrc_dir {
+                Some(_) => vec![Lrc, Lyricly],
+                None => vec![Lyricly],
+            }
+        });
+        let providers = kinds
+            .into_iter()
+            .map(|kind| {
+                use ProviderKind::*;
+                let provider: Arc<dyn LyricsProvider> = match kind {
+                    Lyricly => Arc::new(LyriclyService::new(lyricly.clone())),
+                    Lrc => {
+                        let dir = lrc_dir
+                            .as_deref()
+                            .context("missing directory for .lrc files")?;
+                        Arc::new(LrcProvider::new(dir)?)
+                    }
+                };
+                Ok(provider)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let service = Self {
+            providers,
+            store: default(),
+            ttl,
+            not_found_ttl,
+            cache: Cache::builder(1000)
+                .time_to_live(cache_ttl.to_std().context("invalid cache TTL")?)
+                .build(),
+        };
+        Ok(service)
+    }
+}
+
+impl Service {
+    /// Store lyrics in `store` from now on.
+    pub fn set_store(&self, store: Arc<dyn LyricsStore>) {
+        *self.store.write().unwrap() = Some(store);
+    }
+
+    fn store(&self) -> Option<Arc<dyn LyricsStore>> {
+        self.store.read().unwrap().clone()
+    }
+
+    pub async fn get_lyrics(&self, key: &LyricsKey) -> Result<Option<Lyrics>> {
+        // Concurrent lookups for the same track share a single load, so that
+        // they don't race to store the same lyrics.
+        let service = self.clone();
+        let init_key = key.clone();
+        self.cache
+            .get_or_try_insert_with(key.clone(), async move {
+                service.load_lyrics(&init_key).await
+            })
+            .await
+            .map_err(|error| Error::msg(format!("{:#}", error)))
+    }
+
+    /// Load lyrics from the store, or else from the providers.
+    async fn load_lyrics(&self, key: &LyricsKey) -> Result<Option<Lyrics>> {
+        let store = self.store();
+
+        // Try to load lyrics from the store.
+        let stored = match &store {
+            Some(store) => match store.load(key).await {
+                Ok(stored) => stored,
+                Err(error) => {
+                    warn!(
+                        artist = %key.artist_name,
+                        track = %key.track_name,
+                        error = %format!("{:#}", error),
+                        "failed to load stored lyrics",
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+        if let Some(stored) = &stored {
+            if !stored.is_expired() {
+                trace!(
+                    artist = %key.artist_name,
+                    track = %key.track_name,
+                    "got lyrics from store",
+                );
+                return Ok(stored.lyrics.clone());
+            }
+        }
+
+        // Fetch new lyrics, falling back to expired lyrics if that fails.
+        let lyrics = match self.fetch_lyrics(key).await {
+            Ok(lyrics) => lyrics,
+            Err(error) => match stored {
+                Some(StoredLyrics { lyrics, .. }) => return Ok(lyrics),
+                None => return Err(error),
+            },
+        };
+        if let Some(store) = &store {
+            let ttl = match lyrics {
+                Some(_) => self.ttl,
+                None => self.not_found_ttl,
+            };
+            let stored = StoredLyrics {
+                lyrics: lyrics.clone(),
+                expires_at: Some(now() + ttl),
+            };
+            if let Err(error) = store.save(key, stored).await {
+                warn!(
+                    artist = %key.artist_name,
+                    track = %key.track_name,
+                    error = %format!("{:#}", error),
+                    "failed to store lyrics",
+                );
+            }
+        }
+        Ok(lyrics)
+    }
+
+    /// Override the lyrics for a track (or record that it has none), which
+    /// are kept until they're overridden again.
+    pub async fn set_lyrics(
+        &self,
+        key: &LyricsKey,
+        lyrics: Option<Lyrics>,
+    ) -> Result<()> {
+        let store = self.store().context("missing lyrics store")?;
+        let stored = StoredLyrics {
+            lyrics,
+            expires_at: None,
+        };
+        store.save(key, stored).await?;
+
+        // Lyrics set by name apply to tracks with any ID, so forget all
+        // lyrics rather than just those under this key.
+        self.cache.invalidate_all();
+        Ok(())
+    }
+
+    /// Get lyrics from each provider in turn, until one has them.
+    async fn fetch_lyrics(&self, key: &LyricsKey) -> Result<Option<Lyrics>> {
+        let LyricsKey {
+            track_name,
+            artist_name,
+            ..
+        } = key;
+        let mut last_error: Option<Error> = None;
+        let mut has_answer = false;
+        for provider in &self.providers {
+            match provider.get_lyrics(track_name, artist_name).await {
+                Ok(Some(lyrics)) => return Ok(Some(lyrics)),
+                Ok(None) => {
+                    trace!(
+                        provider = provider.name(),
+                        artist = %artist_name,
+                        track = %track_name,
+                        "lyrics not found",
+                    );
+                    has_answer = true;
+                }
+                Err(error) => {
+                    warn!(
+                        provider = provider.name(),
+                        artist = %artist_name,
+                        track = %track_name,
+                        error = %format!("{:#}", error),
+                        "failed to get lyrics",
+                    );
+                    last_error = Some(error);
+                }
+            }
+        }
+
+        // Only fail if no provider could say whether it has the lyrics.
+        match last_error {
+            Some(error) if !has_answer => Err(error),
+            _ => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use super::*;
+
+use std::io::ErrorKind as IoErrorKind;
+use std::path::PathBuf;
+
+use tokio::fs::read_dir;
+use tokio::fs::read_to_string;
+
+use regex::Captures;
+
+lazy_static! {
+    static ref TIMESTAMP_REGEX: Regex =
+        Regex::new(r"^\[(\d+):(\d{1,2})(?:[.:](\d{1,3}))?\]").unwrap();
+    static ref TAG_REGEX: Regex =
+        Regex::new(r"^\[([A-Za-z]+):\s*(.*?)\s*\]$").unwrap();
+    static ref WORD_TIMESTAMP_REGEX: Regex =
+        Regex::new(r"<\d+:\d{1,2}(?:[.:]\d{1,3})?>").unwrap();
+}
+
+/// Reads lyrics from a directory of `.lrc` files, named like
+/// `Artist - Title.lrc`.
+///
+/// Names are matched ignoring case, whitespace, and characters that can't
+/// appear in file names.
+#[derive(Debug)]
+pub struct LrcProvider {
+    dir: PathBuf,
+}
+
+impl LrcProvider {
+    pub fn new(dir: &str) -> Result<Self> {
+        let dir = PathBuf::from(dir);
+        ensure!(dir.is_dir(), "not a directory: {}", dir.display());
+        let provider = Self { dir };
+        Ok(provider)
+    }
+
+    /// Find the file with lyrics for a track.
+    async fn find_file(
+        &self,
+        track_name: &str,
+        artist_name: &str,
+    ) -> Result<Option<PathBuf>> {
+        let name = normalize_name(&format!("{} - {}", artist_name, track_name));
+        let mut entries = read_dir(&self.dir)
+            .await
+            .context("failed to read directory")?;
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .context("failed to read directory entry")?
+        {
+            let path = entry.path();
+            let is_lrc = path
+                .extension()
+                .map(|extension| extension.eq_ignore_ascii_case("lrc"))
+                .unwrap_or_default();
+            if !is_lrc {
+                continue;
+            }
+            let stem = match path.file_stem().and_then(|stem| stem.to_str()) {
+                Some(stem) => stem,
+                None => continue,
+            };
+            if normalize_name(stem) == name {
+                return Ok(Some(path));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl LyricsProvider for LrcProvider {
+    fn name(&self) -> &'static str {
+        "lrc"
+    }
+
+    async fn get_lyrics(
+        &self,
+        track_name: &str,
+        artist_name: &str,
+    ) -> Result<Option<Lyrics>> {
+        let path = match self.find_file(track_name, artist_name).await? {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        let text = match read_to_string(&path).await {
+            Ok(text) => text,
+            Err(error) => {
+                // The file may have been removed since it was found.
+                if error.kind() == IoErrorKind::NotFound {
+                    return Ok(None);
+                }
+                return Err(error).context("failed to read file")?;
+            }
+        };
+        debug!(
+            artist = artist_name,
+            track = track_name,
+            path = %path.display(),
+            "got lyrics from file",
+        );
+        Ok(parse_lrc(&text))
+    }
+}
+
+/// Normalize a name for matching against file names.
+fn normalize_name(name: &str) -> String {
+    let name = name
+        .chars()
+        .filter(|char| !matches!(char, '/' | '\\' | ':' | '*' | '?' | '"'))
+        .filter(|char| !matches!(char, '<' | '>' | '|'))
+        .collect::<String>()
+        .to_lowercase();
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse lyrics in the LRC format, where each line is preceded by one or
+/// more timestamps (i.e. `[01:23.45]`), and tags (i.e. `[ar:Artist]`) may
+/// appear on lines of their own.
+///
+/// Lyrics without any timestamps are treated as plain text, and lines with
+/// timestamps that are out of range are skipped. Returns `None` if there are
+/// no lyrics at all.
+pub fn parse_lrc(text: &str) -> Option<Lyrics> {
+    let mut offset: i64 = 0;
+    let mut lines = Vec::<LyricLine>::new();
+    let mut plain_lines = Vec::<&str>::new();
+    for line in text.lines() {
+        let line = line.trim();
+
+        // Read any timestamps at the start of the line.
+        let mut positions = Vec::<u32>::new();
+        let mut rest = line;
+        let mut is_valid = true;
+        while let Some(captures) = TIMESTAMP_REGEX.captures(rest) {
+            match parse_timestamp(&captures) {
+                Some(position) => positions.push(position),
+                None => is_valid = false,
+            }
+            rest = &rest[captures[0].len()..];
+        }
+        if !is_valid {
+            continue;
+        }
+        if !positions.is_empty() {
+            let text = WORD_TIMESTAMP_REGEX.replace_all(rest, "");
+            let text = text.trim();
+            for position in positions {
+                lines.push(LyricLine {
+                    text: text.to_owned(),
+                    position,
+                });
+            }
+            continue;
+        }
+
+        // Otherwise, the line is either a tag or plain text.
+        if let Some(captures) = TAG_REGEX.captures(line) {
+            if captures[1].eq_ignore_ascii_case("offset") {
+                let value = captures[2].trim_start_matches('+');
+                if let Ok(value) = value.parse() {
+                    offset = value;
+                }
+            }
+            continue;
+        }
+        plain_lines.push(line);
+    }
+
+    if !lines.is_empty() {
+        // A positive offset means that lines should be shown sooner.
+        for line in &mut lines {
+            let position = i64::from(line.position).saturating_sub(offset);
+            line.position = u32::try_from(position.max(0)).unwrap_or(u32::MAX);
+        }
+        lines.sort_by_key(|line| line.position);
+        return Some(Lyrics::Synced(lines));
+    }
+    let text = plain_lines.join("\n");
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(Lyrics::Plain(text.to_owned()))
+}
+
+/// Read the position of a timestamp (i.e. `[01:23.45]`) in milliseconds,
+/// returning `None` if it is out of range.
+fn parse_timestamp(captures: &Captures) -> Option<u32> {
+    let minutes: u32 = captures[1].parse().ok()?;
+    let seconds: u32 = captures[2].parse().ok()?;
+    let millis = match captures.get(3) {
+        Some(fraction) => {
+            let fraction = fraction.as_str();
+            let scale = 10_u32.pow(3 - u32::try_from(fraction.len()).ok()?);
+            fraction.parse::<u32>().ok()? * scale
+        }
+        None => 0,
+    };
+    minutes
+        .checked_mul(60)?
+        .checked_add(seconds)?
+        .checked_mul(1000)?
+        .checked_add(millis)
+}
@@ -1,21 +1,272 @@
 use super::*;
 
+use std::path::Path;
+
+mod archive;
+mod attachments;
 mod client;
+mod history;
+mod journal;
+mod links;
+mod logseq;
+mod publish;
 mod reader;
+mod render;
+mod search;
+mod source;
+mod syntax;
+mod tags;
+mod watcher;
 
+use archive::*;
+use attachments::*;
 use client::*;
+use history::*;
+use journal::*;
+use links::*;
+use logseq::*;
+use publish::*;
 use reader::*;
+use render::*;
+use search::*;
+use source::*;
+use syntax::*;
+use tags::*;
+use watcher::*;
 
-pub use client::{Service, ServiceConfig};
+pub use attachments::{Attachment, AttachmentContent};
+pub use client::{NoteUpdate, Service, ServiceConfig};
+pub use history::NoteRevision;
+pub use links::{NoteGraph, NoteGraphEdge, NoteGraphNode};
+pub use search::{SearchHighlight, SearchHit};
+pub use source::VaultFormat;
+pub use tags::NoteTag;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
 pub struct Note {
     pub id: String,
     pub names: Set<String>,
 
+    /// Alternative names for the note, from its front matter.
+    #[builder(default)]
+    pub aliases: Set<String>,
+
+    #[builder(default)]
+    pub title: Option<String>,
+
     #[builder(default)]
-    pub links: Set<String>,
+    pub links: Vec<NoteLink>,
 
     #[builder(default)]
     pub tags: Set<String>,
+
+    #[builder(default)]
+    pub tasks: Vec<NoteTask>,
+
+    /// Whether the note is visible to the public.
+    #[builder(default)]
+    pub is_published: bool,
+
+    /// When the note was created, according to its front matter (or else
+    /// its file).
+    #[builder(default)]
+    pub created_at: Option<DateTime>,
+
+    /// When the note was last updated, according to its front matter (or
+    /// else its file).
+    #[builder(default)]
+    pub updated_at: Option<DateTime>,
+
+    /// When the note's file was last modified.
+    #[builder(default)]
+    pub modified_at: Option<DateTime>,
+
+    /// Front matter properties that aren't otherwise parsed.
+    #[builder(default)]
+    pub properties: Map<String, Json>,
+
+    #[builder(default)]
+    pub content: String,
+}
+
+impl Note {
+    /// Links to other notes (as opposed to attachments).
+    pub fn note_links(&self) -> impl Iterator<Item = &NoteLink> {
+        self.links.iter().filter(|link| !link.is_attachment())
+    }
+}
+
+/// File extensions of vault attachments, which may be embedded in notes.
+const ATTACHMENT_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "bmp", "svg", "webp", "pdf", "mp3", "wav",
+    "m4a", "ogg", "flac", "webm", "mp4", "mov", "mkv",
+];
+
+/// A link from a note to another note (or a part of it).
+#[derive(
+    Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, Builder,
+)]
+pub struct NoteLink {
+    /// The name of the linked note.
+    pub target: String,
+
+    #[builder(default)]
+    pub heading: Option<String>,
+
+    #[builder(default)]
+    pub block: Option<String>,
+
+    /// The text displayed in place of the link.
+    #[builder(default)]
+    pub alias: Option<String>,
+
+    #[builder(default)]
+    pub is_embed: bool,
+}
+
+impl NoteLink {
+    /// Whether this link refers to an attachment (i.e. an image), rather
+    /// than a note.
+    pub fn is_attachment(&self) -> bool {
+        is_attachment_name(&self.target)
+    }
+}
+
+/// Whether a file name (or link target) refers to an attachment, based on
+/// its extension.
+fn is_attachment_name(name: &str) -> bool {
+    let extension = name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_lowercase());
+    match extension {
+        Some(extension) => ATTACHMENT_EXTENSIONS.contains(&extension.as_str()),
+        None => false,
+    }
+}
+
+/// A task (i.e. `- [ ] Do something`) in a note.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Builder)]
+pub struct NoteTask {
+    /// The line of the note's file that the task is on, starting from 1.
+    pub line: usize,
+
+    pub text: String,
+    pub status: NoteTaskStatus,
+
+    /// When the task is due, in the style of the Tasks plugin (i.e.
+    /// `📅 2021-12-31`).
+    #[builder(default)]
+    pub due: Option<Date>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NoteTaskStatus {
+    Todo,
+    InProgress,
+    Done,
+    Cancelled,
+}
+
+impl NoteTaskStatus {
+    /// Parse the status of a task from the character inside its checkbox.
+    ///
+    /// Unrecognized statuses are treated as `Todo`, like in Obsidian.
+    fn from_marker(marker: char) -> Self {
+        use NoteTaskStatus::*;
+        match marker {
+            'x' | 'X' => Done,
+            '/' => InProgress,
+            '-' => Cancelled,
+            _ => Todo,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Builder)]
+pub struct NoteTaskConditions {
+    #[builder(default, setter(into))]
+    pub status: Option<NoteTaskStatus>,
+
+    /// Only include tasks that are due before this date.
+    #[builder(default, setter(into))]
+    pub due_before: Option<Date>,
+}
+
+impl NoteTaskConditions {
+    fn matches(&self, task: &NoteTask) -> bool {
+        let NoteTaskConditions { status, due_before } = self;
+        if let Some(status) = status {
+            if task.status != *status {
+                return false;
+            }
+        }
+        if let Some(due_before) = due_before {
+            match task.due {
+                Some(due) if due < *due_before => (),
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+#[derive(Debug, Clone, Default, Builder)]
+pub struct NoteConditions {
+    /// Only include notes within this folder (or its subfolders).
+    #[builder(default, setter(into))]
+    pub folder: Option<String>,
+
+    /// Only include notes updated at or after this time.
+    #[builder(default, setter(into))]
+    pub updated_since: Option<DateTime>,
+}
+
+impl NoteConditions {
+    fn matches(&self, note: &Note) -> bool {
+        let NoteConditions {
+            folder,
+            updated_since,
+        } = self;
+        if let Some(folder) = folder {
+            let folder = folder.trim_matches('/');
+            if !folder.is_empty() {
+                let is_within = note
+                    .id
+                    .strip_prefix(folder)
+                    .map(|rest| rest.starts_with('/'))
+                    .unwrap_or_default();
+                if !is_within {
+                    return false;
+                }
+            }
+        }
+        if let Some(since) = updated_since {
+            match note.updated_at {
+                Some(updated_at) if updated_at >= *since => (),
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteSorting {
+    /// Sort by ID, in ascending order.
+    Id,
+
+    /// Sort by modification time, most recent first.
+    ModifiedAt,
+
+    /// Sort by update time, most recent first.
+    UpdatedAt,
+
+    /// Sort by the number of incoming references, most referenced first.
+    IncomingReferences,
+}
+
+impl Default for NoteSorting {
+    fn default() -> Self {
+        Self::Id
+    }
 }
@@ -0,0 +1,244 @@
+use super::*;
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, PathBuf};
+use std::str::from_utf8 as str_from_utf8;
+
+use flate2::read::GzDecoder;
+use tar::Archive as TarArchive;
+use zip::ZipArchive;
+
+/// Reads a vault from a `.zip`, `.tar`, or `.tar.gz` archive.
+///
+/// The archive is loaded into memory up front, so changes to it are not
+/// picked up.
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub(super) struct ArchiveReader {
+    archive_path: String,
+
+    #[derivative(Debug = "ignore")]
+    files: Map<String, ArchiveFile>,
+
+    publish_policy: PublishPolicy,
+}
+
+#[derive(Clone)]
+struct ArchiveFile {
+    data: Arc<Vec<u8>>,
+    modified_at: Option<DateTime>,
+}
+
+impl ArchiveReader {
+    pub fn new(
+        archive_path: &str,
+        publish_policy: PublishPolicy,
+    ) -> Result<Self> {
+        let file =
+            File::open(archive_path).context("failed to open archive")?;
+        let name = archive_path.to_lowercase();
+        let files = if name.ends_with(".zip") {
+            read_zip(file)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            read_tar(GzDecoder::new(file))
+        } else if name.ends_with(".tar") {
+            read_tar(file)
+        } else {
+            bail!("unsupported archive format")
+        }
+        .context("failed to read archive")?;
+
+        let root = archive_root(files.keys().map(String::as_str));
+        let files = match root {
+            Some(root) => files
+                .into_iter()
+                .map(|(path, file)| {
+                    let path = path[root.len()..].to_owned();
+                    (path, file)
+                })
+                .collect(),
+            None => files,
+        };
+
+        let reader = Self {
+            archive_path: archive_path.to_owned(),
+            files,
+            publish_policy,
+        };
+        Ok(reader)
+    }
+}
+
+impl KnowledgeSource for ArchiveReader {
+    fn list_notes(&self) -> Result<Set<String>> {
+        let notes = self
+            .files
+            .keys()
+            .filter_map(|path| path.strip_suffix(".md"))
+            .map(ToOwned::to_owned)
+            .collect();
+        Ok(notes)
+    }
+
+    fn read_note(&self, id: &str) -> Result<Option<Note>> {
+        let file = match self.files.get(&format!("{}.md", id)) {
+            Some(file) => file,
+            None => return Ok(None),
+        };
+        let text = str_from_utf8(&file.data).context("note is not UTF-8")?;
+        let times = FileTimes {
+            created_at: None,
+            modified_at: file.modified_at,
+        };
+        let note = parse_note(id, text, times, &self.publish_policy)?;
+        Ok(Some(note))
+    }
+
+    fn list_attachments(&self) -> Result<Vec<Attachment>> {
+        let attachments = self
+            .files
+            .iter()
+            .filter(|(path, _)| is_attachment_name(path))
+            .map(|(path, file)| {
+                let ArchiveFile { data, modified_at } = file.clone();
+                Attachment {
+                    id: path.to_owned(),
+                    content: AttachmentContent::Memory { data, modified_at },
+                }
+            })
+            .collect();
+        Ok(attachments)
+    }
+}
+
+fn read_zip(file: File) -> Result<Map<String, ArchiveFile>> {
+    let mut archive = ZipArchive::new(file)?;
+    let mut files: Map<String, ArchiveFile> = default();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if !entry.is_file() {
+            continue;
+        }
+        let path = match entry.enclosed_name().and_then(archive_file_path) {
+            Some(path) => path,
+            None => continue,
+        };
+        let modified_at = {
+            let modified = entry.last_modified();
+            Date::from_ymd_opt(
+                modified.year().into(),
+                modified.month().into(),
+                modified.day().into(),
+            )
+            .and_then(|date| {
+                date.and_hms_opt(
+                    modified.hour().into(),
+                    modified.minute().into(),
+                    modified.second().into(),
+                )
+            })
+            .map(|date_time| Utc.from_utc_datetime(&date_time))
+        };
+        let mut data = Vec::new();
+        entry
+            .read_to_end(&mut data)
+            .with_context(|| format!("failed to read {}", &path))?;
+        let file = ArchiveFile {
+            data: Arc::new(data),
+            modified_at,
+        };
+        files.insert(path, file);
+    }
+    Ok(files)
+}
+
+fn read_tar(reader: impl Read) -> Result<Map<String, ArchiveFile>> {
+    let mut archive = TarArchive::new(reader);
+    let mut files: Map<String, ArchiveFile> = default();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = {
+            let path = entry.path()?;
+            archive_file_path(&path)
+        };
+        let path = match path {
+            Some(path) => path,
+            None => continue,
+        };
+        let modified_at = entry
+            .header()
+            .mtime()
+            .ok()
+            .and_then(|mtime| i64::try_from(mtime).ok())
+            .map(|mtime| Utc.timestamp(mtime, 0));
+        let mut data = Vec::new();
+        entry
+            .read_to_end(&mut data)
+            .with_context(|| format!("failed to read {}", &path))?;
+        let file = ArchiveFile {
+            data: Arc::new(data),
+            modified_at,
+        };
+        files.insert(path, file);
+    }
+    Ok(files)
+}
+
+/// Returns the folder that a vault was archived in (i.e. `Vault/`), if the
+/// archive contains the vault's folder rather than its contents.
+///
+/// The archive is only considered to contain the vault's folder if that is
+/// its sole entry, and it isn't the vault's `.obsidian/` settings folder.
+fn archive_root<'a>(
+    paths: impl IntoIterator<Item = &'a str>,
+) -> Option<String> {
+    let mut roots: Set<&str> = default();
+    for path in paths {
+        let (root, _) = path.split_once('/')?;
+        roots.insert(root);
+    }
+    let mut roots = roots.into_iter();
+    match (roots.next(), roots.next()) {
+        (Some(root), None) if root != ".obsidian" => Some(format!("{}/", root)),
+        _ => None,
+    }
+}
+
+/// Normalize the path of a file in an archive (i.e. `./Folder/Note.md`),
+/// or return `None` if the path points outside of the archive.
+fn archive_file_path(path: impl AsRef<Path>) -> Option<String> {
+    let mut normalized = PathBuf::new();
+    for component in path.as_ref().components() {
+        match component {
+            Component::Normal(part) => normalized.push(part),
+            Component::CurDir => (),
+            _ => return None,
+        }
+    }
+    let path = normalized.to_string_lossy().into_owned();
+    if path.is_empty() {
+        return None;
+    }
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_archive_root() {
+        let root = archive_root(["Vault/Note.md", "Vault/.obsidian/app.json"]);
+        assert_eq!(root.as_deref(), Some("Vault/"));
+        assert_eq!(archive_root(["Folder/Note.md", "Other.md"]), None);
+        assert_eq!(
+            archive_root(["Folder/Note.md", ".obsidian/app.json"]),
+            None
+        );
+        assert_eq!(archive_root([".obsidian/app.json"]), None);
+    }
+}
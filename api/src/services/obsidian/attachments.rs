@@ -0,0 +1,102 @@
+use super::*;
+
+use std::path::PathBuf;
+
+/// A non-note file in a vault (i.e. an image), which may be embedded in
+/// notes.
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    /// The path of the attachment, relative to the vault.
+    pub id: String,
+
+    pub content: AttachmentContent,
+}
+
+/// Where the content of an attachment is stored.
+#[derive(Derivative, Clone)]
+#[derivative(Debug)]
+pub enum AttachmentContent {
+    /// A file, at an absolute path.
+    File(PathBuf),
+
+    /// Data that was loaded into memory (i.e. from an archive).
+    Memory {
+        #[derivative(Debug = "ignore")]
+        data: Arc<Vec<u8>>,
+        modified_at: Option<DateTime>,
+    },
+}
+
+/// An index of the attachments in a vault snapshot.
+#[derive(Debug, Default)]
+pub(super) struct AttachmentIndex {
+    attachments: Map<String, Attachment>,
+    attachments_by_name: Map<String, String>,
+    published: Set<String>,
+}
+
+impl AttachmentIndex {
+    pub fn new(
+        attachments: impl IntoIterator<Item = Attachment>,
+        notes: &[Note],
+    ) -> Self {
+        let attachments = attachments
+            .into_iter()
+            .map(|attachment| (attachment.id.clone(), attachment))
+            .collect::<Map<_, _>>();
+
+        // Attachments may be referred to by any suffix of their path, like
+        // notes. Prefer the attachment closest to the root of the vault.
+        let attachments_by_name = {
+            let mut lookup: Map<String, String> = default();
+            for id in attachments.keys() {
+                let parts = id.split('/').collect::<Vec<_>>();
+                for i in 0..parts.len() {
+                    let name = parts[i..].join("/");
+                    if let Some(target) = lookup.get(&name) {
+                        if (target.len(), target) <= (id.len(), id) {
+                            continue;
+                        }
+                    }
+                    lookup.insert(name, id.clone());
+                }
+            }
+            lookup
+        };
+
+        // Only attachments embedded in published notes are published.
+        let published = notes
+            .iter()
+            .filter(|note| note.is_published)
+            .flat_map(|note| note.links.iter())
+            .filter(|link| link.is_attachment())
+            .filter_map(|link| attachments_by_name.get(&link.target))
+            .cloned()
+            .collect::<Set<_>>();
+
+        Self {
+            attachments,
+            attachments_by_name,
+            published,
+        }
+    }
+
+    /// Resolve a link target (i.e. `diagram.png`) to an attachment.
+    pub fn resolve(&self, name: &str) -> Option<&Attachment> {
+        let id = self.attachments_by_name.get(name)?;
+        self.attachments.get(id)
+    }
+
+    /// Whether the attachment with the given ID is published.
+    pub fn is_published(&self, id: &str) -> bool {
+        self.published.contains(id)
+    }
+
+    /// Returns the attachment with the given ID, if it is published.
+    pub fn get_published(&self, id: &str) -> Option<&Attachment> {
+        if !self.is_published(id) {
+            return None;
+        }
+        self.attachments.get(id)
+    }
+}
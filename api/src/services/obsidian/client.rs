@@ -1,78 +1,259 @@
 use super::*;
 
+use std::cmp::Reverse;
+
+use tokio::sync::broadcast::channel as broadcast_channel;
+use tokio::sync::broadcast::Sender as BroadcastSender;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio_stream::wrappers::BroadcastStream;
+
+/// The maximum number of notes to read from disk at once.
+const NOTES_READ_CONCURRENCY: usize = 16;
+
+/// How long to cache note revisions for.
+const NOTE_REVISIONS_TTL: StdDuration = StdDuration::from_secs(60);
+
+/// The number of note updates to buffer for slow subscribers.
+const NOTE_UPDATES_CAPACITY: usize = 64;
+
 #[derive(Debug, Clone, Builder)]
 pub struct ServiceConfig {
     vault_path: String,
 
+    #[builder(default)]
+    vault_format: VaultFormat,
+
     #[builder(default = Duration::minutes(1))]
     ttl: Duration,
+
+    /// Watch the vault for changes, instead of periodically re-reading it
+    /// from disk.
+    #[builder(default)]
+    watch: bool,
+
+    #[builder(default = Duration::milliseconds(250))]
+    watch_delay: Duration,
+
+    /// Glob patterns for the paths of notes to publish, which include every
+    /// note by default.
+    #[builder(default = vec!["**".to_owned()])]
+    publish_include: Vec<String>,
+
+    /// Glob patterns for the paths of notes to never publish.
+    #[builder(default)]
+    publish_exclude: Vec<String>,
+
+    /// Read the history of notes from the git repository that contains the
+    /// vault.
+    #[builder(default)]
+    history: bool,
+
+    /// The folder that contains daily notes, relative to the vault root.
+    #[builder(default)]
+    journal_folder: String,
+
+    /// The `strftime`-style format of the names of daily notes.
+    #[builder(default = "%Y-%m-%d".to_owned())]
+    journal_date_format: String,
 }
 
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct Service {
-    reader: Arc<Reader>,
+    source: Arc<dyn KnowledgeSource>,
 
     #[derivative(Debug = "ignore")]
-    notes_cache: Cache<String, Option<Note>>,
+    caches: Caches,
 
     #[derivative(Debug = "ignore")]
-    notes_sem: Semaphore,
+    notes_sem: Arc<Semaphore>,
 
     #[derivative(Debug = "ignore")]
-    notes_list_cache: Cache<(), Set<String>>,
+    notes_list_sem: Semaphore,
 
     #[derivative(Debug = "ignore")]
-    notes_list_sem: Semaphore,
+    search_index_sem: Semaphore,
+
+    #[derivative(Debug = "ignore")]
+    link_graph_sem: Semaphore,
+
+    #[derivative(Debug = "ignore")]
+    attachments_sem: Semaphore,
+
+    watcher: Option<Watcher>,
+    history: Option<Arc<History>>,
+    journal: Journal,
+
+    #[derivative(Debug = "ignore")]
+    updates: BroadcastSender<NoteUpdate>,
+}
+
+/// A change to a note in the vault.
+#[derive(Debug, Clone)]
+pub enum NoteUpdate {
+    /// A note was added or changed.
+    Changed(Box<Note>),
+
+    /// A note was removed.
+    Removed {
+        id: String,
+
+        /// Whether the note was published, which is `false` if it wasn't
+        /// known before it was removed.
+        was_published: bool,
+    },
+}
+
+#[derive(Clone)]
+struct Caches {
+    notes: Cache<String, Option<Note>>,
+    notes_list: Cache<(), Set<String>>,
+    search_index: Cache<(), Arc<SearchIndex>>,
+    link_graph: Cache<(), Arc<LinkGraph>>,
+    attachments: Cache<(), Arc<AttachmentIndex>>,
+    note_revisions: Cache<String, Arc<Vec<NoteRevision>>>,
+}
+
+impl Caches {
+    fn new(ttl: Option<StdDuration>) -> Self {
+        Self {
+            notes: build_cache(1000, ttl),
+            notes_list: build_cache(1, ttl),
+            search_index: build_cache(1, ttl),
+            link_graph: build_cache(1, ttl),
+            attachments: build_cache(1, ttl),
+            note_revisions: build_cache(1000, Some(NOTE_REVISIONS_TTL)),
+        }
+    }
+
+    /// Invalidate caches that are derived from the contents of the entire
+    /// vault.
+    async fn invalidate_derived(&self) {
+        self.search_index.invalidate(&()).await;
+        self.link_graph.invalidate(&()).await;
+        self.attachments.invalidate(&()).await;
+    }
+}
+
+fn build_cache<K, V>(capacity: usize, ttl: Option<StdDuration>) -> Cache<K, V>
+where
+    K: Send + Sync + 'static,
+    V: Send + Sync + 'static,
+    K: Eq + Hash,
+    V: Clone,
+{
+    let builder = Cache::builder(capacity);
+    match ttl {
+        Some(ttl) => builder.time_to_live(ttl).build(),
+        None => builder.build(),
+    }
 }
 
 impl Service {
     pub fn new(config: ServiceConfig) -> Result<Self> {
-        let ServiceConfig { vault_path, ttl } = config;
-        let ttl = ttl.to_std().context("invalid TTL")?;
+        let ServiceConfig {
+            vault_path,
+            vault_format,
+            ttl,
+            watch,
+            watch_delay,
+            publish_include,
+            publish_exclude,
+            history,
+            journal_folder,
+            journal_date_format,
+        } = config;
+
+        // Cached notes don't expire when watching the vault, since they are
+        // updated as changes occur.
+        let ttl = if watch {
+            None
+        } else {
+            let ttl = ttl.to_std().context("invalid TTL")?;
+            Some(ttl)
+        };
+
+        let source = {
+            let policy = PublishPolicy::new(&publish_include, &publish_exclude)
+                .context("invalid publish policy")?;
+            open_source(&vault_path, vault_format, policy)?
+        };
+        let history = if history {
+            let vault_path = source
+                .vault_path()
+                .context("vault history requires a vault directory")?;
+            let history = History::new(vault_path)
+                .context("failed to read vault history")?;
+            Some(Arc::new(history))
+        } else {
+            None
+        };
+        let journal = Journal::new(&journal_folder, &journal_date_format)
+            .context("invalid journal settings")?;
+        let caches = Caches::new(ttl);
+        let (updates, _) = broadcast_channel(NOTE_UPDATES_CAPACITY);
+        let watcher = if watch {
+            let delay = watch_delay.to_std().context("invalid watch delay")?;
+            let (watcher, events) =
+                source.watch(delay).context("failed to watch vault")?;
+            spawn(handle_vault_events(
+                source.clone(),
+                caches.clone(),
+                updates.clone(),
+                events,
+            ));
+            Some(watcher)
+        } else {
+            None
+        };
+
         let client = Self {
-            reader: {
-                let reader = Reader::new(&vault_path)?;
-                Arc::new(reader)
-            },
-            notes_cache: Cache::builder(1000).time_to_live(ttl).build(),
-            notes_sem: Semaphore::new(1),
-            notes_list_cache: {
-                CacheBuilder::new(1000).time_to_live(ttl).build()
-            },
+            source,
+            caches,
+            notes_sem: Arc::new(Semaphore::new(NOTES_READ_CONCURRENCY)),
             notes_list_sem: Semaphore::new(1),
+            search_index_sem: Semaphore::new(1),
+            link_graph_sem: Semaphore::new(1),
+            attachments_sem: Semaphore::new(1),
+            watcher,
+            history,
+            journal,
+            updates,
         };
         Ok(client)
     }
 
     pub async fn list_notes(&self) -> Result<Vec<Note>> {
         let Self {
-            reader,
-            notes_list_cache: cache,
+            source,
+            caches,
             notes_list_sem: sem,
             ..
         } = self;
-
-        // Acquire permit.
-        let _permit = sem.acquire().await.unwrap();
+        let cache = &caches.notes_list;
 
         // Retrieve list from cache, otherwise list from disk.
-        let notes_ids = cache.get(&());
-        let notes_ids = match notes_ids {
-            Some(notes) => {
-                trace!(count = notes.len(), "got notes from cache");
-                notes
-            }
-            None => {
-                let notes = {
-                    let reader = reader.clone();
-                    spawn_blocking(move || reader.list_notes())
-                        .await
-                        .unwrap()?
-                };
-                cache.insert((), notes.clone()).await;
-                debug!(count = notes.len(), "got notes");
-                notes
+        let notes_ids = {
+            // Acquire permit.
+            let _permit = sem.acquire().await.unwrap();
+
+            let notes_ids = cache.get(&());
+            match notes_ids {
+                Some(notes) => {
+                    trace!(count = notes.len(), "got notes from cache");
+                    notes
+                }
+                None => {
+                    let notes = {
+                        let source = source.clone();
+                        spawn_blocking(move || source.list_notes())
+                            .await
+                            .unwrap()?
+                    };
+                    cache.insert((), notes.clone()).await;
+                    debug!(count = notes.len(), "got notes");
+                    notes
+                }
             }
         };
 
@@ -92,80 +273,332 @@ impl Service {
     pub async fn get_note(&self, id: &str) -> Result<Option<Note>> {
         let id = id.to_owned();
         let Self {
-            reader,
-            notes_cache: cache,
+            source,
+            caches,
             notes_sem: sem,
             ..
         } = self;
-
-        // Acquire permit.
-        let _permit = sem.acquire().await.unwrap();
+        let cache = &caches.notes;
 
         // Retrieve note from cache, otherwise read note from disk.
-        let note = cache.get(&id);
-        let note = match note {
-            Some(note) => {
-                trace!(note = %id, "got note from cache");
-                note
-            }
-            None => {
-                let note = {
-                    let reader = reader.clone();
-                    let id = id.clone();
-                    spawn_blocking(move || {
-                        reader.read_note(&id).context("failed to read note")
-                    })
+        if let Some(note) = cache.get(&id) {
+            trace!(note = %id, "got note from cache");
+            return Ok(note);
+        }
+        let note = cache
+            .get_or_try_insert_with(id.clone(), {
+                let source = source.clone();
+                let sem = sem.clone();
+                let id = id.clone();
+                async move {
+                    // Acquire permit.
+                    let _permit = sem.acquire().await.unwrap();
+
+                    let note = {
+                        let id = id.clone();
+                        spawn_blocking(move || {
+                            source.read_note(&id).context("failed to read note")
+                        })
+                        .await
+                        .unwrap()?
+                    };
+                    debug!(%id, "got note");
+                    Ok::<_, Error>(note)
+                }
+            })
+            .await
+            .map_err(|error| Error::msg(format!("{:#}", error)))?;
+        Ok(note)
+    }
+
+    pub async fn find_notes(
+        &self,
+        conditions: NoteConditions,
+        sorting: NoteSorting,
+    ) -> Result<Vec<Note>> {
+        let notes = self.list_notes().await.context("failed to list notes")?;
+        let mut notes = notes
+            .into_iter()
+            .filter(|note| conditions.matches(note))
+            .collect::<Vec<_>>();
+
+        use NoteSorting::*;
+        match sorting {
+            Id => notes.sort_by(|a, b| a.id.cmp(&b.id)),
+            ModifiedAt => notes.sort_by(|a, b| {
+                b.modified_at
+                    .cmp(&a.modified_at)
+                    .then_with(|| a.id.cmp(&b.id))
+            }),
+            UpdatedAt => notes.sort_by(|a, b| {
+                b.updated_at
+                    .cmp(&a.updated_at)
+                    .then_with(|| a.id.cmp(&b.id))
+            }),
+            IncomingReferences => {
+                let graph = self
+                    .link_graph()
                     .await
-                    .unwrap()?
-                };
-                cache.insert(id.clone(), note.clone()).await;
-                debug!(%id, "got note");
-                note
+                    .context("failed to load link graph")?;
+                notes.sort_by_cached_key(|note| {
+                    let count = graph.incoming_count(&note.id);
+                    (Reverse(count), note.id.clone())
+                })
             }
-        };
-        Ok(note)
+        }
+        Ok(notes)
     }
 
-    pub async fn get_note_outgoing_references(
+    /// Returns the daily note for a date.
+    pub async fn get_journal_note(&self, date: Date) -> Result<Option<Note>> {
+        let id = self.journal.note_id(date);
+        self.get_note(&id).await
+    }
+
+    /// Returns the daily notes between two dates (inclusive), in order.
+    pub async fn list_journal_notes(
+        &self,
+        from: Date,
+        to: Date,
+    ) -> Result<Vec<(Date, Note)>> {
+        let Self { journal, .. } = self;
+        let notes = self.list_notes().await.context("failed to list notes")?;
+        let mut notes = notes
+            .into_iter()
+            .filter_map(|note| {
+                let date = journal.note_date(&note.id)?;
+                Some((date, note))
+            })
+            .filter(|(date, _)| (from..=to).contains(date))
+            .collect::<Vec<_>>();
+        notes.sort_by_key(|(date, _)| *date);
+        Ok(notes)
+    }
+
+    /// Returns the commits that changed a note, most recent first.
+    ///
+    /// Returns no revisions unless history is enabled.
+    pub async fn get_note_revisions(
         &self,
         note_id: &str,
-    ) -> Result<Vec<Note>> {
-        let note =
-            self.get_note(note_id).await.context("failed to get note")?;
-        let note = match note {
-            Some(note) => note,
+    ) -> Result<Vec<NoteRevision>> {
+        let Self {
+            history, caches, ..
+        } = self;
+        let cache = &caches.note_revisions;
+        let history = match history {
+            Some(history) => history.clone(),
             None => return Ok(default()),
         };
-        let notes = self.list_notes().await.context("failed to list notes")?;
-        let notes_by_name = {
-            let mut lookup: Map<String, Note> = default();
-            for other_note in &notes {
-                for name in &other_note.names {
-                    if let Some(target) = lookup.get(name) {
-                        if target.id.len() <= other_note.id.len() {
-                            continue;
-                        }
+
+        let id = note_id.to_owned();
+        let revisions = cache
+            .get_or_try_insert_with(id.clone(), async {
+                let revisions = spawn_blocking(move || {
+                    history
+                        .note_revisions(&id)
+                        .context("failed to read note revisions")
+                })
+                .await
+                .unwrap()?;
+                Ok::<_, Error>(Arc::new(revisions))
+            })
+            .await
+            .map_err(|error| Error::msg(format!("{:#}", error)))?;
+        Ok(revisions.as_ref().clone())
+    }
+
+    /// Returns the content of a note at a revision, or `None` if the note
+    /// didn't exist at that revision.
+    ///
+    /// Returns `None` unless history is enabled.
+    pub async fn get_note_content_at(
+        &self,
+        note_id: &str,
+        revision: &str,
+    ) -> Result<Option<String>> {
+        let history = match &self.history {
+            Some(history) => history.clone(),
+            None => return Ok(None),
+        };
+        let note_id = note_id.to_owned();
+        let revision = revision.to_owned();
+        spawn_blocking(move || history.note_content_at(&note_id, &revision))
+            .await
+            .unwrap()
+    }
+
+    /// Subscribe to notes as they are added, changed, or removed.
+    ///
+    /// Fails unless the vault is being watched, since updates wouldn't be
+    /// noticed otherwise.
+    pub fn watch_notes(&self) -> Result<impl Stream<Item = NoteUpdate>> {
+        ensure!(self.watcher.is_some(), "vault is not being watched");
+        let updates = self.updates.subscribe();
+        let updates =
+            BroadcastStream::new(updates).filter_map(|update| async move {
+                match update {
+                    Ok(update) => Some(update),
+                    Err(error) => {
+                        warn!(%error, "missed note updates");
+                        None
                     }
-                    lookup.insert(name.clone(), other_note.clone());
                 }
-            }
-            lookup
+            });
+        Ok(updates)
+    }
+
+    pub async fn list_notes_by_tag(
+        &self,
+        tag: &str,
+        include_descendants: bool,
+    ) -> Result<Vec<Note>> {
+        let tag = match normalize_tag(tag) {
+            Some(tag) => tag,
+            None => return Ok(default()),
         };
-        let references = note
-            .links
+        let notes = self.list_notes().await.context("failed to list notes")?;
+        let notes = notes
+            .into_iter()
+            .filter(|note| {
+                note.tags.iter().any(|candidate| {
+                    tag_matches(candidate, &tag, include_descendants)
+                })
+            })
+            .collect::<Vec<_>>();
+        Ok(notes)
+    }
+
+    /// Returns the tasks in all notes that match `conditions`, along with
+    /// the notes they're in.
+    ///
+    /// Tasks are ordered by due date (with undated tasks last), and then by
+    /// their position in the vault.
+    pub async fn find_tasks(
+        &self,
+        conditions: NoteTaskConditions,
+    ) -> Result<Vec<(Note, NoteTask)>> {
+        let notes = self.list_notes().await.context("failed to list notes")?;
+        let mut tasks = notes
             .into_iter()
-            .map(|id| {
-                notes_by_name
-                    .get(&id)
-                    .map(ToOwned::to_owned)
-                    .unwrap_or_else(|| {
-                        Note::builder()
-                            .id(id.clone())
-                            .names(Set::from_iter([id.clone()]))
-                            .build()
-                    })
+            .flat_map(|note| {
+                let tasks = note
+                    .tasks
+                    .iter()
+                    .filter(|task| conditions.matches(task))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                tasks.into_iter().map(move |task| (note.clone(), task))
             })
             .collect::<Vec<_>>();
+        tasks.sort_by(|(a_note, a), (b_note, b)| {
+            let a_key = (a.due.is_none(), a.due, &a_note.id, a.line);
+            let b_key = (b.due.is_none(), b.due, &b_note.id, b.line);
+            a_key.cmp(&b_key)
+        });
+        Ok(tasks)
+    }
+
+    pub async fn list_tags(
+        &self,
+        include_unpublished: bool,
+    ) -> Result<Vec<NoteTag>> {
+        let notes = self.list_notes().await.context("failed to list notes")?;
+        let tags = spawn_blocking(move || {
+            let notes = notes
+                .iter()
+                .filter(|note| include_unpublished || note.is_published);
+            build_tag_tree(notes)
+        })
+        .await
+        .unwrap();
+        Ok(tags)
+    }
+
+    /// Render a note to HTML.
+    ///
+    /// Links to other notes point to the URL returned by `note_url`; if it
+    /// returns `None`, the note is hidden, and the link neither points to it
+    /// nor names it. Embedded attachments are likewise resolved using
+    /// `attachment_url`, except that unpublished attachments are always
+    /// hidden, since they're never served.
+    pub async fn render_note_html(
+        &self,
+        note: &Note,
+        note_url: impl Fn(&Note) -> Option<Url>,
+        attachment_url: impl Fn(&Attachment) -> Option<Url>,
+    ) -> Result<String> {
+        let graph = self
+            .link_graph()
+            .await
+            .context("failed to load link graph")?;
+        let attachments = self
+            .attachment_index()
+            .await
+            .context("failed to load attachments")?;
+        let html = render_html(
+            &note.id,
+            &note.content,
+            |name| LinkResolution::new(graph.resolve(name), &note_url),
+            |name| {
+                let attachment = attachments.resolve(name);
+                LinkResolution::new(attachment, |attachment| {
+                    if !attachments.is_published(&attachment.id) {
+                        return None;
+                    }
+                    attachment_url(attachment)
+                })
+            },
+        );
+        Ok(html)
+    }
+
+    pub async fn search_notes(&self, query: &str) -> Result<Vec<SearchHit>> {
+        let index = self
+            .search_index()
+            .await
+            .context("failed to load search index")?;
+        let query = query.to_owned();
+        let hits = spawn_blocking(move || index.search(&query)).await.unwrap();
+        Ok(hits)
+    }
+
+    async fn search_index(&self) -> Result<Arc<SearchIndex>> {
+        let Self {
+            caches,
+            search_index_sem: sem,
+            ..
+        } = self;
+        let cache = &caches.search_index;
+
+        // Acquire permit.
+        let _permit = sem.acquire().await.unwrap();
+
+        // Retrieve index from cache, otherwise build index from notes.
+        if let Some(index) = cache.get(&()) {
+            trace!("got search index from cache");
+            return Ok(index);
+        }
+        let notes = self.list_notes().await.context("failed to list notes")?;
+        let count = notes.len();
+        let index = spawn_blocking(move || SearchIndex::new(notes))
+            .await
+            .unwrap();
+        let index = Arc::new(index);
+        cache.insert((), index.clone()).await;
+        debug!(notes = count, "built search index");
+        Ok(index)
+    }
+
+    pub async fn get_note_outgoing_references(
+        &self,
+        note_id: &str,
+    ) -> Result<Vec<Note>> {
+        let graph = self
+            .link_graph()
+            .await
+            .context("failed to load link graph")?;
+        let references = graph.outgoing(note_id).unwrap_or_default();
         Ok(references)
     }
 
@@ -173,26 +606,218 @@ impl Service {
         &self,
         note_id: &str,
     ) -> Result<Vec<Note>> {
-        let note =
-            self.get_note(note_id).await.context("failed to get note")?;
-        let note = match note {
-            Some(note) => note,
-            None => return Ok(default()),
-        };
-        let other_notes = {
-            let notes =
-                self.list_notes().await.context("failed to list notes")?;
-            notes
-                .into_iter()
-                .filter(|Note { id, .. }| *id != note.id)
-                .collect::<Vec<_>>()
-        };
-        let references = other_notes
+        let graph = self
+            .link_graph()
+            .await
+            .context("failed to load link graph")?;
+        let references = graph.incoming(note_id).unwrap_or_default();
+        Ok(references)
+    }
+
+    /// Resolve a link target (i.e. a note name) to a note.
+    pub async fn resolve_note(&self, name: &str) -> Result<Option<Note>> {
+        let graph = self
+            .link_graph()
+            .await
+            .context("failed to load link graph")?;
+        let note = graph.resolve(name).cloned();
+        Ok(note)
+    }
+
+    /// Resolve several link targets to notes, against the same snapshot of
+    /// the vault.
+    ///
+    /// Targets that don't resolve to a note are omitted.
+    pub async fn resolve_notes<'a>(
+        &self,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Map<String, Note>> {
+        let graph = self
+            .link_graph()
+            .await
+            .context("failed to load link graph")?;
+        let notes = names
             .into_iter()
-            .filter(|other_note| {
-                other_note.links.intersection(&note.names).count() > 0
+            .filter_map(|name| {
+                let note = graph.resolve(name)?;
+                Some((name.to_owned(), note.clone()))
             })
-            .collect::<Vec<_>>();
-        Ok(references)
+            .collect();
+        Ok(notes)
+    }
+
+    pub async fn get_graph(
+        &self,
+        include_unpublished: bool,
+    ) -> Result<NoteGraph> {
+        let graph = self
+            .link_graph()
+            .await
+            .context("failed to load link graph")?;
+        let graph = spawn_blocking(move || graph.export(include_unpublished))
+            .await
+            .unwrap();
+        Ok(graph)
+    }
+
+    /// Returns the attachment with the given ID, if it is embedded in a
+    /// published note.
+    pub async fn get_published_attachment(
+        &self,
+        id: &str,
+    ) -> Result<Option<Attachment>> {
+        let attachments = self
+            .attachment_index()
+            .await
+            .context("failed to load attachments")?;
+        let attachment = attachments.get_published(id).cloned();
+        Ok(attachment)
+    }
+
+    async fn attachment_index(&self) -> Result<Arc<AttachmentIndex>> {
+        let Self {
+            source,
+            caches,
+            attachments_sem: sem,
+            ..
+        } = self;
+        let cache = &caches.attachments;
+
+        // Acquire permit.
+        let _permit = sem.acquire().await.unwrap();
+
+        // Retrieve index from cache, otherwise index attachments from disk.
+        if let Some(index) = cache.get(&()) {
+            trace!("got attachments from cache");
+            return Ok(index);
+        }
+        let notes = self.list_notes().await.context("failed to list notes")?;
+        let index = {
+            let source = source.clone();
+            spawn_blocking(move || {
+                let attachments = source
+                    .list_attachments()
+                    .context("failed to list attachments")?;
+                let index = AttachmentIndex::new(attachments, &notes);
+                Ok::<_, Error>(index)
+            })
+            .await
+            .unwrap()?
+        };
+        let index = Arc::new(index);
+        cache.insert((), index.clone()).await;
+        debug!("indexed attachments");
+        Ok(index)
+    }
+
+    async fn link_graph(&self) -> Result<Arc<LinkGraph>> {
+        let Self {
+            caches,
+            link_graph_sem: sem,
+            ..
+        } = self;
+        let cache = &caches.link_graph;
+
+        // Acquire permit.
+        let _permit = sem.acquire().await.unwrap();
+
+        // Retrieve graph from cache, otherwise build graph from notes.
+        if let Some(graph) = cache.get(&()) {
+            trace!("got link graph from cache");
+            return Ok(graph);
+        }
+        let notes = self.list_notes().await.context("failed to list notes")?;
+        let count = notes.len();
+        let graph =
+            spawn_blocking(move || LinkGraph::new(notes)).await.unwrap();
+        let graph = Arc::new(graph);
+        cache.insert((), graph.clone()).await;
+        debug!(notes = count, "built link graph");
+        Ok(graph)
+    }
+}
+
+async fn handle_vault_events(
+    source: Arc<dyn KnowledgeSource>,
+    caches: Caches,
+    updates: BroadcastSender<NoteUpdate>,
+    mut events: UnboundedReceiver<VaultEvent>,
+) {
+    while let Some(event) = events.recv().await {
+        trace!(?event, "got vault event");
+        match event {
+            VaultEvent::NoteChanged(id) => {
+                let was_published = was_published(&caches, &id);
+                let note = {
+                    let source = source.clone();
+                    let id = id.clone();
+                    spawn_blocking(move || source.read_note(&id)).await.unwrap()
+                };
+                caches.note_revisions.invalidate(&id).await;
+                match note {
+                    Ok(note) => {
+                        caches.notes.insert(id.clone(), note.clone()).await;
+                        debug!(%id, "refreshed note");
+
+                        // Notify subscribers; it's fine if there are none.
+                        let update = match note {
+                            Some(note) => NoteUpdate::Changed(Box::new(note)),
+                            None => NoteUpdate::Removed {
+                                id: id.clone(),
+                                was_published,
+                            },
+                        };
+                        updates.send(update).ok();
+                    }
+                    Err(error) => {
+                        error!(
+                            %id,
+                            error = %format!("{:#}", error),
+                            "failed to refresh note"
+                        );
+                        caches.notes.invalidate(&id).await;
+                    }
+                }
+                if let Some(mut ids) = caches.notes_list.get(&()) {
+                    if ids.insert(id) {
+                        caches.notes_list.insert((), ids).await;
+                    }
+                }
+            }
+            VaultEvent::NoteRemoved(id) => {
+                let was_published = was_published(&caches, &id);
+                caches.notes.invalidate(&id).await;
+                caches.note_revisions.invalidate(&id).await;
+                if let Some(mut ids) = caches.notes_list.get(&()) {
+                    if ids.remove(&id) {
+                        caches.notes_list.insert((), ids).await;
+                    }
+                }
+                debug!(%id, "evicted note");
+
+                // Notify subscribers; it's fine if there are none.
+                updates.send(NoteUpdate::Removed { id, was_published }).ok();
+            }
+            VaultEvent::AttachmentChanged(id) => {
+                debug!(%id, "attachment changed");
+            }
+            VaultEvent::Rescan => {
+                caches.notes.invalidate_all();
+                caches.notes_list.invalidate_all();
+                caches.note_revisions.invalidate_all();
+                debug!("evicted all notes");
+            }
+        }
+        caches.invalidate_derived().await;
     }
 }
+
+/// Whether a note was published, as far as the cache knows.
+fn was_published(caches: &Caches, id: &String) -> bool {
+    caches
+        .notes
+        .get(id)
+        .flatten()
+        .map(|note| note.is_published)
+        .unwrap_or_default()
+}
@@ -0,0 +1,156 @@
+use super::*;
+
+use std::path::PathBuf;
+
+use git2::ErrorCode as GitErrorCode;
+use git2::{DiffOptions, Oid, Repository, Sort as RevwalkSort};
+use yaml_front_matter::parse_and_find_content as parse_front_matter;
+
+/// A commit that changed a note.
+#[derive(Debug, Clone)]
+pub struct NoteRevision {
+    /// The ID of the commit.
+    pub id: String,
+    pub committed_at: DateTime,
+    pub message: String,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+/// Reads the history of notes from the git repository that contains a
+/// vault.
+#[derive(Debug)]
+pub(super) struct History {
+    repo_path: PathBuf,
+
+    /// The path of the vault, relative to the root of the repository.
+    vault_prefix: PathBuf,
+}
+
+impl History {
+    pub fn new(vault_path: &Path) -> Result<Self> {
+        let repo = Repository::discover(vault_path)
+            .context("failed to open repository")?;
+        let workdir = repo.workdir().context("repository is bare")?;
+        let workdir = workdir
+            .canonicalize()
+            .context("failed to resolve repository")?;
+        let vault_path = vault_path
+            .canonicalize()
+            .context("failed to resolve vault")?;
+        let vault_prefix = vault_path
+            .strip_prefix(&workdir)
+            .context("vault is outside of repository")?
+            .to_path_buf();
+        let history = Self {
+            repo_path: workdir,
+            vault_prefix,
+        };
+        Ok(history)
+    }
+
+    /// Returns the commits that changed a note, most recent first.
+    pub fn note_revisions(&self, note_id: &str) -> Result<Vec<NoteRevision>> {
+        let repo = self.open()?;
+        let path = self.note_path(note_id);
+
+        let mut revwalk = repo.revwalk().context("failed to walk commits")?;
+        revwalk
+            .set_sorting(RevwalkSort::TOPOLOGICAL | RevwalkSort::TIME)
+            .context("failed to sort commits")?;
+        if let Err(error) = revwalk.push_head() {
+            // Repositories without any commits have no history.
+            if repo.is_empty().unwrap_or_default() {
+                return Ok(default());
+            }
+            return Err(error).context("failed to read HEAD");
+        }
+
+        let mut revisions = Vec::new();
+        for id in revwalk {
+            let id = id.context("failed to walk commits")?;
+            let commit =
+                repo.find_commit(id).context("failed to get commit")?;
+            let tree = commit.tree().context("failed to get commit tree")?;
+            let parent_tree = match commit.parents().next() {
+                Some(parent) => {
+                    let tree = parent.tree().context("failed to get tree")?;
+                    Some(tree)
+                }
+                None => None,
+            };
+
+            let mut options = DiffOptions::new();
+            options.pathspec(&path).disable_pathspec_match(true);
+            let diff = repo
+                .diff_tree_to_tree(
+                    parent_tree.as_ref(),
+                    Some(&tree),
+                    Some(&mut options),
+                )
+                .context("failed to diff commit")?;
+            if diff.deltas().len() == 0 {
+                continue;
+            }
+            let stats = diff.stats().context("failed to get diff stats")?;
+
+            let committed_at = {
+                let time = commit.time();
+                Utc.timestamp(time.seconds(), 0)
+            };
+            let revision = NoteRevision {
+                id: id.to_string(),
+                committed_at,
+                message: commit
+                    .message()
+                    .unwrap_or_default()
+                    .trim_end()
+                    .to_owned(),
+                insertions: stats.insertions(),
+                deletions: stats.deletions(),
+            };
+            revisions.push(revision);
+        }
+        Ok(revisions)
+    }
+
+    /// Returns the content of a note at a revision, or `None` if the note
+    /// didn't exist at that revision.
+    pub fn note_content_at(
+        &self,
+        note_id: &str,
+        revision: &str,
+    ) -> Result<Option<String>> {
+        let repo = self.open()?;
+        let id = Oid::from_str(revision).context("invalid revision")?;
+        let commit = repo.find_commit(id).context("failed to get commit")?;
+        let tree = commit.tree().context("failed to get commit tree")?;
+
+        let path = self.note_path(note_id);
+        let entry = match tree.get_path(&path) {
+            Ok(entry) => entry,
+            Err(error) if error.code() == GitErrorCode::NotFound => {
+                return Ok(None)
+            }
+            Err(error) => return Err(error).context("failed to find note"),
+        };
+        let object = entry
+            .to_object(&repo)
+            .context("failed to get note object")?;
+        let blob = object.as_blob().context("note is not a file")?;
+        let text = String::from_utf8_lossy(blob.content());
+        let (_, content) = parse_front_matter(&text)
+            .context("failed to parse front matter")?;
+        Ok(Some(content.to_owned()))
+    }
+
+    // Repositories aren't thread-safe, so open the repository for each
+    // operation.
+    fn open(&self) -> Result<Repository> {
+        Repository::open(&self.repo_path).context("failed to open repository")
+    }
+
+    fn note_path(&self, note_id: &str) -> PathBuf {
+        self.vault_prefix.join(format!("{}.md", note_id))
+    }
+}
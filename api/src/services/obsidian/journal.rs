@@ -0,0 +1,93 @@
+use super::*;
+
+use std::fmt::Write as FmtWrite;
+
+use chrono::format::{Item as FormatItem, StrftimeItems};
+
+/// Locates daily notes (i.e. `Journal/2021-12-31`) in a vault.
+#[derive(Debug, Clone)]
+pub(super) struct Journal {
+    folder: String,
+    date_format: String,
+}
+
+impl Journal {
+    pub fn new(folder: &str, date_format: &str) -> Result<Self> {
+        let is_valid = !date_format.is_empty()
+            && StrftimeItems::new(date_format)
+                .all(|item| item != FormatItem::Error);
+        ensure!(is_valid, "bad date format {:?}", date_format);
+
+        // Ensure that dates survive a round trip through the format, so
+        // that daily notes can be found by their IDs.
+        let date = Date::from_ymd(2021, 12, 31);
+        let mut formatted = String::new();
+        let parsed = match write!(formatted, "{}", date.format(date_format)) {
+            Ok(()) => Date::parse_from_str(&formatted, date_format).ok(),
+            Err(_) => None,
+        };
+        ensure!(
+            parsed == Some(date),
+            "date format {:?} does not identify a date",
+            date_format
+        );
+
+        let folder = folder.trim_matches('/').to_owned();
+        let journal = Self {
+            folder,
+            date_format: date_format.to_owned(),
+        };
+        Ok(journal)
+    }
+
+    /// The ID of the daily note for a date.
+    pub fn note_id(&self, date: Date) -> String {
+        let name = date.format(&self.date_format).to_string();
+        if self.folder.is_empty() {
+            name
+        } else {
+            format!("{}/{}", &self.folder, name)
+        }
+    }
+
+    /// The date of a daily note, or `None` if the note with the given ID
+    /// isn't a daily note.
+    pub fn note_date(&self, note_id: &str) -> Option<Date> {
+        let name = if self.folder.is_empty() {
+            note_id
+        } else {
+            note_id
+                .strip_prefix(self.folder.as_str())?
+                .strip_prefix('/')?
+        };
+        Date::parse_from_str(name, &self.date_format).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_daily_notes() {
+        let journal = Journal::new("/Journal/", "%Y-%m-%d").unwrap();
+        let date = Date::from_ymd(2021, 12, 31);
+        assert_eq!(journal.note_id(date), "Journal/2021-12-31");
+        assert_eq!(journal.note_date("Journal/2021-12-31"), Some(date));
+        assert_eq!(journal.note_date("2021-12-31"), None);
+        assert_eq!(journal.note_date("Journal/Ideas"), None);
+
+        let journal = Journal::new("", "%d %B %Y").unwrap();
+        assert_eq!(journal.note_id(date), "31 December 2021");
+        assert_eq!(journal.note_date("31 December 2021"), Some(date));
+    }
+
+    #[test]
+    fn rejects_bad_date_formats() {
+        assert!(Journal::new("Journal", "").is_err());
+        assert!(Journal::new("Journal", "%Y-%Q").is_err());
+
+        // Dates can't be found from names that leave out the day.
+        assert!(Journal::new("Journal", "%Y-%m").is_err());
+    }
+}
@@ -0,0 +1,234 @@
+use super::*;
+
+/// A bidirectional graph of the links between notes in a vault snapshot.
+#[derive(Debug, Default)]
+pub(super) struct LinkGraph {
+    notes: Map<String, Note>,
+    notes_by_name: Map<String, String>,
+    incoming: Map<String, Set<String>>,
+}
+
+impl LinkGraph {
+    pub fn new(notes: impl IntoIterator<Item = Note>) -> Self {
+        let notes = notes
+            .into_iter()
+            .map(|note| (note.id.clone(), note))
+            .collect::<Map<_, _>>();
+
+        // When several notes share a name, prefer the note with the shortest
+        // ID (i.e. the one closest to the root of the vault).
+        let notes_by_name = {
+            let mut lookup: Map<String, String> = default();
+            for note in notes.values() {
+                for name in &note.names {
+                    if let Some(target) = lookup.get(name) {
+                        let is_preferred =
+                            (target.len(), target) <= (note.id.len(), &note.id);
+                        if is_preferred {
+                            continue;
+                        }
+                    }
+                    lookup.insert(name.clone(), note.id.clone());
+                }
+            }
+            lookup
+        };
+
+        let incoming = {
+            let mut incoming: Map<String, Set<String>> = default();
+            for note in notes.values() {
+                for link in note.note_links() {
+                    if let Some(target) = notes_by_name.get(&link.target) {
+                        if *target != note.id {
+                            incoming
+                                .entry(target.clone())
+                                .or_default()
+                                .insert(note.id.clone());
+                        }
+                    }
+                }
+            }
+            incoming
+        };
+
+        Self {
+            notes,
+            notes_by_name,
+            incoming,
+        }
+    }
+
+    /// Resolve a link target (i.e. a note name) to a note.
+    pub fn resolve(&self, name: &str) -> Option<&Note> {
+        let id = self.notes_by_name.get(name)?;
+        self.notes.get(id)
+    }
+
+    /// Returns the notes that a note links to, or `None` if the note does
+    /// not exist.
+    ///
+    /// Links that don't resolve to a note are represented by placeholder
+    /// notes, named after the link target.
+    pub fn outgoing(&self, id: &str) -> Option<Vec<Note>> {
+        let note = self.notes.get(id)?;
+        let targets = note
+            .note_links()
+            .map(|link| link.target.as_str())
+            .collect::<Set<_>>();
+        let references = targets
+            .into_iter()
+            .map(|name| {
+                self.resolve(name)
+                    .map(ToOwned::to_owned)
+                    .unwrap_or_else(|| placeholder_note(name))
+            })
+            .collect::<Vec<_>>();
+        Some(references)
+    }
+
+    /// Returns the number of notes that link to a note.
+    pub fn incoming_count(&self, id: &str) -> usize {
+        self.incoming.get(id).map(Set::len).unwrap_or_default()
+    }
+
+    /// Returns the notes that link to a note, or `None` if the note does
+    /// not exist.
+    pub fn incoming(&self, id: &str) -> Option<Vec<Note>> {
+        self.notes.get(id)?;
+        let references = self
+            .incoming
+            .get(id)
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| self.notes.get(id))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        Some(references)
+    }
+
+    /// Export all notes and resolved links as a graph.
+    ///
+    /// Unresolved link targets are included as ghost nodes. Unless
+    /// `include_unpublished` is set, unpublished notes and links to them are
+    /// omitted.
+    pub fn export(&self, include_unpublished: bool) -> NoteGraph {
+        let is_included =
+            |note: &Note| include_unpublished || note.is_published;
+        let mut nodes: Map<String, NoteGraphNode> = self
+            .notes
+            .values()
+            .filter(|note| is_included(note))
+            .map(|note| {
+                let node = NoteGraphNode {
+                    id: note.id.clone(),
+                    names: note.names.clone(),
+                    tags: note.tags.clone(),
+                    is_ghost: false,
+                };
+                (note.id.clone(), node)
+            })
+            .collect();
+        let mut edges: Set<NoteGraphEdge> = default();
+        for note in self.notes.values().filter(|note| is_included(note)) {
+            for NoteLink { target: name, .. } in note.note_links() {
+                let target = match self.resolve(name) {
+                    Some(target) if is_included(target) => target.id.clone(),
+                    Some(_) => continue,
+                    None => {
+                        nodes.entry(name.clone()).or_insert_with(|| {
+                            NoteGraphNode {
+                                id: name.clone(),
+                                names: Set::from_iter([name.clone()]),
+                                tags: default(),
+                                is_ghost: true,
+                            }
+                        });
+                        name.clone()
+                    }
+                };
+                edges.insert(NoteGraphEdge {
+                    source: note.id.clone(),
+                    target,
+                });
+            }
+        }
+
+        let mut nodes = nodes.into_values().collect::<Vec<_>>();
+        nodes.sort_by(|a, b| a.id.cmp(&b.id));
+        let mut edges = edges.into_iter().collect::<Vec<_>>();
+        edges.sort();
+        NoteGraph { nodes, edges }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteGraph {
+    pub nodes: Vec<NoteGraphNode>,
+    pub edges: Vec<NoteGraphEdge>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteGraphNode {
+    pub id: String,
+    pub names: Set<String>,
+    pub tags: Set<String>,
+    pub is_ghost: bool,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct NoteGraphEdge {
+    pub source: String,
+    pub target: String,
+}
+
+/// A note that stands in for an unresolved link target.
+///
+/// Placeholders have no content to hide, so they're always published.
+pub(super) fn placeholder_note(name: &str) -> Note {
+    Note::builder()
+        .id(name.to_owned())
+        .names(Set::from_iter([name.to_owned()]))
+        .is_published(true)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn includes_ghosts_in_outgoing_links() {
+        let link = |target: &str| {
+            NoteLink::builder().target(target.to_owned()).build()
+        };
+        let notes = [
+            Note::builder()
+                .id("Folder/Note".to_owned())
+                .names(Set::from_iter(["Note".to_owned()]))
+                .links(vec![link("Draft"), link("Missing")])
+                .is_published(true)
+                .build(),
+            Note::builder()
+                .id("Draft".to_owned())
+                .names(Set::from_iter(["Draft".to_owned()]))
+                .links(vec![link("Note")])
+                .build(),
+        ];
+        let graph = LinkGraph::new(notes);
+
+        let mut outgoing = graph.outgoing("Folder/Note").unwrap();
+        outgoing.sort_by(|a, b| a.id.cmp(&b.id));
+        let outgoing = outgoing
+            .iter()
+            .map(|note| (note.id.as_str(), note.is_published))
+            .collect::<Vec<_>>();
+        assert_eq!(outgoing, [("Draft", false), ("Missing", true)]);
+
+        let incoming = graph.incoming("Folder/Note").unwrap();
+        assert_eq!(incoming.len(), 1);
+        assert_eq!(graph.incoming_count("Folder/Note"), 1);
+    }
+}
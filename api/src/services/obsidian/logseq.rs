@@ -0,0 +1,320 @@
+use super::*;
+
+use std::fs::read_to_string;
+use std::io::ErrorKind as IoErrorKind;
+
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use yaml::Yaml;
+
+lazy_static! {
+    static ref PROPERTY_REGEX: Regex =
+        Regex::new(r"^([A-Za-z0-9_\-]+)::\s*(.*)$").unwrap();
+    static ref TASK_REGEX: Regex = Regex::new(
+        r"^\s*-\s+(TODO|LATER|DOING|NOW|DONE|CANCELED|CANCELLED)\s+(.*)$"
+    )
+    .unwrap();
+    static ref DEADLINE_REGEX: Regex =
+        Regex::new(r"^\s*DEADLINE:\s*<(\d{4}-\d{2}-\d{2})").unwrap();
+}
+
+/// Reads a Logseq graph, which keeps pages in `pages/` and daily notes in
+/// `journals/`.
+///
+/// Pages are identified by their names, where namespaces (i.e.
+/// `Parent/Child`) are written as `Parent___Child` in file names. Daily
+/// notes are identified by their paths (i.e. `journals/2021_12_31`).
+#[derive(Debug, Clone)]
+pub(super) struct LogseqReader {
+    reader: Reader,
+    publish_policy: PublishPolicy,
+}
+
+impl LogseqReader {
+    pub fn new(
+        graph_path: &str,
+        publish_policy: PublishPolicy,
+    ) -> Result<Self> {
+        let reader = Reader::new(graph_path, publish_policy.clone())?;
+        let reader = Self {
+            reader,
+            publish_policy,
+        };
+        Ok(reader)
+    }
+
+    fn note_path(&self, id: &str) -> String {
+        let file_id = if id.starts_with("journals/") {
+            id.to_owned()
+        } else {
+            format!("pages/{}", id.replace('/', "___"))
+        };
+        self.reader.note_path(&file_id)
+    }
+}
+
+impl KnowledgeSource for LogseqReader {
+    fn list_notes(&self) -> Result<Set<String>> {
+        let notes = self.reader.list_notes()?;
+        let notes = notes
+            .iter()
+            .filter_map(|file_id| note_id_from_file_id(file_id))
+            .collect();
+        Ok(notes)
+    }
+
+    fn read_note(&self, id: &str) -> Result<Option<Note>> {
+        let path = self.note_path(id);
+        let text = match read_to_string(&path) {
+            Ok(text) => text,
+            Err(error) => {
+                if error.kind() == IoErrorKind::NotFound {
+                    return Ok(None);
+                }
+                return Err(error).context("failed to read file")?;
+            }
+        };
+        let times = FileTimes::read(Path::new(&path));
+
+        let (matter, content, first_line) = parse_properties(&text);
+        let mut note = build_note(
+            id,
+            &text,
+            matter,
+            content,
+            first_line,
+            times,
+            &self.publish_policy,
+        );
+        note.tasks.extend(parse_logseq_tasks(content, first_line));
+        note.tasks.sort_by_key(|task| task.line);
+        Ok(Some(note))
+    }
+
+    fn list_attachments(&self) -> Result<Vec<Attachment>> {
+        self.reader.list_attachments()
+    }
+
+    fn vault_path(&self) -> Option<&Path> {
+        self.reader.vault_path()
+    }
+
+    fn watch(
+        &self,
+        delay: StdDuration,
+    ) -> Result<(Watcher, UnboundedReceiver<VaultEvent>)> {
+        let graph_path = Path::new(&self.reader.vault_path);
+        Watcher::new(graph_path, self.clone(), delay)
+    }
+}
+
+impl VaultLayout for LogseqReader {
+    fn note_id(&self, path: &Path) -> Option<String> {
+        let file_id = self.reader.note_id(path)?;
+        note_id_from_file_id(&file_id)
+    }
+
+    fn attachment_id(&self, path: &Path) -> Option<String> {
+        self.reader.attachment_id(path)
+    }
+}
+
+/// Convert the ID of a note's file, relative to the graph, to the ID of the
+/// note.
+///
+/// Returns `None` for files outside of `pages/` and `journals/` (i.e.
+/// backups in `logseq/bak/`).
+fn note_id_from_file_id(file_id: &str) -> Option<String> {
+    if let Some(name) = file_id.strip_prefix("journals/") {
+        if name.contains('/') {
+            return None;
+        }
+        return Some(file_id.to_owned());
+    }
+    let name = file_id.strip_prefix("pages/")?;
+    if name.contains('/') {
+        return None;
+    }
+    Some(name.replace("___", "/"))
+}
+
+/// Parse the properties at the start of a page (i.e. `alias:: Other Name`)
+/// as though they were front matter.
+///
+/// Returns the properties, along with the rest of the page and the line
+/// that it starts on.
+fn parse_properties(text: &str) -> (Map<String, Yaml>, &str, usize) {
+    let mut matter: Map<String, Yaml> = default();
+    let mut offset = 0;
+    let mut line_count = 0;
+    for line in text.split_inclusive('\n') {
+        let captures = match PROPERTY_REGEX.captures(line.trim_end()) {
+            Some(captures) => captures,
+            None => break,
+        };
+        let key = captures.get(1).unwrap().as_str().to_lowercase();
+        let value = captures.get(2).unwrap().as_str().trim();
+        let (key, value) = match key.as_str() {
+            "alias" | "tags" => {
+                let key = if key == "alias" { "aliases" } else { "tags" };
+                let values = value
+                    .split(',')
+                    .map(|value| {
+                        let value = value.trim().trim_start_matches('#');
+                        let value = value
+                            .trim_start_matches("[[")
+                            .trim_end_matches("]]");
+                        Yaml::String(value.to_owned())
+                    })
+                    .collect();
+                (key.to_owned(), Yaml::Array(values))
+            }
+            "public" => {
+                let value = match value.parse::<bool>() {
+                    Ok(value) => Yaml::Boolean(value),
+                    Err(_) => Yaml::String(value.to_owned()),
+                };
+                ("publish".to_owned(), value)
+            }
+            _ => (key, Yaml::String(value.to_owned())),
+        };
+        matter.insert(key, value);
+        offset += line.len();
+        line_count += 1;
+    }
+    (matter, &text[offset..], line_count + 1)
+}
+
+/// Parse tasks written with Logseq's markers (i.e. `- TODO Do something`),
+/// which may be followed by a deadline (i.e. `DEADLINE: <2021-12-31 Fri>`).
+fn parse_logseq_tasks(content: &str, first_line: usize) -> Vec<NoteTask> {
+    let lines = content.lines().collect::<Vec<_>>();
+    let mut tasks = Vec::new();
+    for (index, line) in lines_outside_fences(content) {
+        let captures = match TASK_REGEX.captures(line) {
+            Some(captures) => captures,
+            None => continue,
+        };
+        let marker = captures.get(1).unwrap().as_str();
+        let text = captures.get(2).unwrap().as_str();
+        let status = {
+            use NoteTaskStatus::*;
+            match marker {
+                "DOING" | "NOW" => InProgress,
+                "DONE" => Done,
+                "CANCELED" | "CANCELLED" => Cancelled,
+                _ => Todo,
+            }
+        };
+        let due = lines
+            .get(index + 1)
+            .and_then(|line| DEADLINE_REGEX.captures(line))
+            .and_then(|captures| {
+                let due = captures.get(1).unwrap().as_str();
+                Date::parse_from_str(due, "%Y-%m-%d").ok()
+            });
+        let task = NoteTask::builder()
+            .line(first_line + index)
+            .text(text.trim().to_owned())
+            .status(status)
+            .due(due)
+            .build();
+        tasks.push(task);
+    }
+    tasks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_file_ids() {
+        let note_id = |file_id| note_id_from_file_id(file_id);
+        assert_eq!(note_id("pages/Note").as_deref(), Some("Note"));
+        assert_eq!(
+            note_id("pages/Parent___Child").as_deref(),
+            Some("Parent/Child")
+        );
+        assert_eq!(
+            note_id("journals/2021_12_31").as_deref(),
+            Some("journals/2021_12_31")
+        );
+        assert_eq!(note_id("pages/Folder/Note"), None);
+        assert_eq!(note_id("logseq/bak/pages/Note"), None);
+        assert_eq!(note_id("Note"), None);
+    }
+
+    #[test]
+    fn parses_properties() {
+        let text = "alias:: Other Name, [[Another Name]]\n\
+                    tags:: #rust, [[async]]\n\
+                    public:: true\n\
+                    Type:: book\n\
+                    - First block\n\
+                    key:: not a property\n";
+        let (matter, content, first_line) = parse_properties(text);
+        let strings = |values: &[&str]| {
+            let values = values
+                .iter()
+                .map(|value| Yaml::String((*value).to_owned()))
+                .collect();
+            Yaml::Array(values)
+        };
+        let expected: Map<String, Yaml> = [
+            ("aliases", strings(&["Other Name", "Another Name"])),
+            ("tags", strings(&["rust", "async"])),
+            ("publish", Yaml::Boolean(true)),
+            ("type", Yaml::String("book".to_owned())),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_owned(), value))
+        .collect();
+        assert_eq!(matter, expected);
+        assert_eq!(content, "- First block\nkey:: not a property\n");
+        assert_eq!(first_line, 5);
+
+        let (matter, content, first_line) = parse_properties("- Block\n");
+        assert!(matter.is_empty());
+        assert_eq!(content, "- Block\n");
+        assert_eq!(first_line, 1);
+    }
+
+    #[test]
+    fn parses_logseq_tasks() {
+        let content = "- TODO Write tests\n\
+                       DEADLINE: <2021-12-31 Fri>\n\
+                       \t- DOING Read the docs\n\
+                       ```\n\
+                       - TODO Not a task\n\
+                       ```\n\
+                       - DONE Refactor\n\
+                       - CANCELED Give up\n\
+                       - todo Not a task either";
+        let tasks = parse_logseq_tasks(content, 3);
+        let expected = vec![
+            NoteTask::builder()
+                .line(3)
+                .text("Write tests".to_owned())
+                .status(NoteTaskStatus::Todo)
+                .due(Some(Date::from_ymd(2021, 12, 31)))
+                .build(),
+            NoteTask::builder()
+                .line(5)
+                .text("Read the docs".to_owned())
+                .status(NoteTaskStatus::InProgress)
+                .build(),
+            NoteTask::builder()
+                .line(9)
+                .text("Refactor".to_owned())
+                .status(NoteTaskStatus::Done)
+                .build(),
+            NoteTask::builder()
+                .line(10)
+                .text("Give up".to_owned())
+                .status(NoteTaskStatus::Cancelled)
+                .build(),
+        ];
+        assert_eq!(tasks, expected);
+    }
+}
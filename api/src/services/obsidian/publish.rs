@@ -0,0 +1,83 @@
+use super::*;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+/// Decides which notes in a vault are published (i.e. visible to the
+/// public).
+///
+/// A note's `publish` front matter property decides whether it's published,
+/// overriding the patterns below. Otherwise, a note is published if its path
+/// matches an include pattern, and doesn't match an exclude pattern.
+#[derive(Debug, Clone)]
+pub(super) struct PublishPolicy {
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl PublishPolicy {
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        let include =
+            build_glob_set(include).context("invalid include pattern")?;
+        let exclude =
+            build_glob_set(exclude).context("invalid exclude pattern")?;
+        let policy = Self { include, exclude };
+        Ok(policy)
+    }
+
+    /// Whether the note with the given ID is published, given the value of
+    /// its `publish` front matter property.
+    pub fn is_published(&self, note_id: &str, publish: Option<bool>) -> bool {
+        if let Some(publish) = publish {
+            return publish;
+        }
+        let path = format!("{}.md", note_id);
+        self.include.is_match(&path) && !self.exclude.is_match(&path)
+    }
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern)
+            .with_context(|| format!("bad pattern {:?}", pattern))?;
+        builder.add(glob);
+    }
+    let set = builder.build()?;
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn publishes_notes() {
+        let include = ["Blog/**".to_owned()];
+        let exclude = ["**/Drafts/**".to_owned()];
+        let policy = PublishPolicy::new(&include, &exclude).unwrap();
+
+        // Front matter decides, even if the note is excluded.
+        assert!(policy.is_published("Private/Note", Some(true)));
+        assert!(!policy.is_published("Blog/Post", Some(false)));
+        assert!(policy.is_published("Blog/Drafts/Post", Some(true)));
+
+        // Otherwise, notes are published if they're included.
+        assert!(policy.is_published("Blog/Post", None));
+        assert!(!policy.is_published("Private/Note", None));
+        assert!(!policy.is_published("Blog/Drafts/Post", None));
+    }
+
+    #[test]
+    fn publishes_everything_by_default() {
+        let include = ["**".to_owned()];
+        let policy = PublishPolicy::new(&include, &[]).unwrap();
+        assert!(policy.is_published("Note", None));
+        assert!(policy.is_published("Private/Note", None));
+        assert!(!policy.is_published("Private/Note", Some(false)));
+    }
+
+    #[test]
+    fn rejects_bad_patterns() {
+        assert!(PublishPolicy::new(&["Blog/[".to_owned()], &[]).is_err());
+    }
+}
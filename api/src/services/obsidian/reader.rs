@@ -1,26 +1,40 @@
 use super::*;
 
+use std::fs::canonicalize;
+use std::fs::metadata;
 use std::fs::read_to_string;
 use std::fs::File;
 use std::io::ErrorKind as IoErrorKind;
 use std::path::Path;
 
+use chrono::NaiveDateTime;
+
+use tokio::sync::mpsc::UnboundedReceiver;
+
 use yaml::Yaml;
-use yaml_front_matter::parse as parse_front_matter;
+use yaml_front_matter::parse_and_find_content as parse_front_matter;
 
 use walkdir::WalkDir;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(super) struct Reader {
-    vault_path: String,
+    /// The canonical path of the vault, with a trailing slash.
+    pub vault_path: String,
+    publish_policy: PublishPolicy,
 }
 
 impl Reader {
-    pub fn new(vault_path: &str) -> Result<Self> {
+    pub fn new(
+        vault_path: &str,
+        publish_policy: PublishPolicy,
+    ) -> Result<Self> {
+        let vault_path =
+            canonicalize(vault_path).context("failed to resolve vault")?;
+        let vault_path = vault_path.to_string_lossy().into_owned();
         let vault_path = if vault_path.ends_with('/') {
-            vault_path.to_owned()
+            vault_path
         } else {
-            vault_path.to_owned() + "/"
+            vault_path + "/"
         };
 
         let vault_dir =
@@ -29,31 +43,35 @@ impl Reader {
             vault_dir.metadata().context("failed to read vault")?;
         ensure!(vault_dir_meta.is_dir(), "vault must be a directory");
 
-        let reader = Self { vault_path };
+        let reader = Self {
+            vault_path,
+            publish_policy,
+        };
         Ok(reader)
     }
 
-    pub fn list_notes(&self) -> Result<Set<String>> {
+    /// The path of the file for the note with the given ID.
+    pub fn note_path(&self, note_id: &str) -> String {
+        let mut path = Path::new(&self.vault_path).to_path_buf();
+        path.push(format!("{}.md", note_id));
+        path.to_string_lossy().into_owned()
+    }
+}
+
+impl KnowledgeSource for Reader {
+    fn list_notes(&self) -> Result<Set<String>> {
         let mut notes: Set<String> = default();
         for entry in WalkDir::new(&self.vault_path) {
             let entry = entry.context("failed to read directory entry")?;
-            let path = entry.path();
-            if path.extension().unwrap_or_default() != "md" {
-                continue;
+            if let Some(id) = self.note_id(entry.path()) {
+                notes.insert(id);
             }
-            let path = path.with_extension("").to_string_lossy().into_owned();
-            let id = path
-                .strip_prefix(&self.vault_path)
-                .map(ToOwned::to_owned)
-                .unwrap_or(path);
-            notes.insert(id);
         }
         Ok(notes)
     }
 
-    pub fn read_note(&self, id: &str) -> Result<Option<Note>> {
-        let id = id.to_owned();
-        let path = self.note_path(&id);
+    fn read_note(&self, id: &str) -> Result<Option<Note>> {
+        let path = self.note_path(id);
         let text = match read_to_string(&path) {
             Ok(text) => text,
             Err(error) => {
@@ -63,68 +81,266 @@ impl Reader {
                 return Err(error).context("failed to read file")?;
             }
         };
+        let times = FileTimes::read(Path::new(&path));
+        let note = parse_note(id, &text, times, &self.publish_policy)?;
+        Ok(Some(note))
+    }
+
+    fn list_attachments(&self) -> Result<Vec<Attachment>> {
+        let mut attachments = Vec::new();
+        for entry in WalkDir::new(&self.vault_path) {
+            let entry = entry.context("failed to read directory entry")?;
+
+            // Skip symlinks, which may point outside of the vault.
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            if let Some(id) = self.attachment_id(entry.path()) {
+                let attachment = Attachment {
+                    id,
+                    content: AttachmentContent::File(entry.into_path()),
+                };
+                attachments.push(attachment);
+            }
+        }
+        Ok(attachments)
+    }
+
+    fn vault_path(&self) -> Option<&Path> {
+        Some(Path::new(&self.vault_path))
+    }
+
+    fn watch(
+        &self,
+        delay: StdDuration,
+    ) -> Result<(Watcher, UnboundedReceiver<VaultEvent>)> {
+        Watcher::new(Path::new(&self.vault_path), self.clone(), delay)
+    }
+}
+
+impl VaultLayout for Reader {
+    fn note_id(&self, path: &Path) -> Option<String> {
+        if path.extension().unwrap_or_default() != "md" {
+            return None;
+        }
+        let path = path.with_extension("");
+        let path = path.strip_prefix(&self.vault_path).ok()?;
+        let id = path.to_string_lossy().into_owned();
+        Some(id)
+    }
+
+    fn attachment_id(&self, path: &Path) -> Option<String> {
+        let path = path.strip_prefix(&self.vault_path).ok()?;
+        let id = path.to_string_lossy().into_owned();
+        if !is_attachment_name(&id) {
+            return None;
+        }
+        Some(id)
+    }
+}
+
+/// Timestamps of a note's file, if they are known.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct FileTimes {
+    pub created_at: Option<DateTime>,
+    pub modified_at: Option<DateTime>,
+}
 
-        let names = {
-            let mut names: Set<String> = default();
-            let mut parts =
-                id.split('/').map(ToOwned::to_owned).collect::<Vec<_>>();
-            while !parts.is_empty() {
-                names.insert(parts.join("/"));
-                if let Some((_, tail)) = parts.split_first() {
-                    parts = tail.to_vec();
+impl FileTimes {
+    pub fn read(path: &Path) -> Self {
+        match metadata(path) {
+            Ok(meta) => {
+                let created_at = meta.created().map(DateTime::from).ok();
+                let modified_at = meta.modified().map(DateTime::from).ok();
+                Self {
+                    created_at,
+                    modified_at,
                 }
             }
-            names
-        };
+            Err(_) => default(),
+        }
+    }
+}
+
+/// Parse a note from the text of its file, which may start with YAML front
+/// matter.
+pub(super) fn parse_note(
+    id: &str,
+    text: &str,
+    times: FileTimes,
+    policy: &PublishPolicy,
+) -> Result<Note> {
+    let (matter, content) =
+        parse_front_matter(text).context("failed to parse front matter")?;
+    let matter = matter
+        .and_then(Yaml::into_hash)
+        .map(|hash| {
+            let entries = hash
+                .into_iter()
+                .filter_map(|(key, value)| Some((key.into_string()?, value)));
+            entries.collect::<Map<_, _>>()
+        })
+        .unwrap_or_default();
+    let first_line = text[..(text.len() - content.len())].lines().count() + 1;
+    let note = build_note(id, text, matter, content, first_line, times, policy);
+    Ok(note)
+}
 
-        let links = {
-            lazy_static! {
-                static ref RE: Regex =
-                    Regex::new(r"\[\[([^\[\]]+)\]\]").unwrap();
+/// Build a note from its text and properties (i.e. from its front matter).
+///
+/// `content` is the text without its properties, which starts on
+/// `first_line` of the note's file.
+pub(super) fn build_note(
+    id: &str,
+    text: &str,
+    mut matter: Map<String, Yaml>,
+    content: &str,
+    first_line: usize,
+    times: FileTimes,
+    policy: &PublishPolicy,
+) -> Note {
+    let links = parse_links(id, text);
+    let tags = {
+        let mut tags = matter
+            .remove("tags")
+            .map(yaml_strings)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|tag| normalize_tag(tag))
+            .collect::<Set<_>>();
+        tags.extend(parse_tags(content));
+        tags
+    };
+    let tasks = parse_tasks(content, first_line);
+    let aliases = {
+        let mut aliases: Set<String> = default();
+        for key in ["aliases", "alias"] {
+            if let Some(value) = matter.remove(key) {
+                aliases.extend(yaml_strings(value));
             }
-            RE.captures_iter(&text)
-                .map(|m| m.get(1).unwrap().as_str().to_owned())
-                .collect::<Set<_>>()
-        };
-        let tags = {
-            let matter = parse_front_matter(&text)
-                .context("failed to parse front matter")?;
-            matter
-                .map(Yaml::into_hash)
-                .flatten()
-                .map(|mut hash| {
-                    let key = Yaml::String("tags".to_owned());
-                    hash.remove(&key)
-                })
-                .flatten()
-                .map(|tags| {
-                    use Yaml::*;
-                    let tags = match tags {
-                        String(tag) => Set::from_iter([tag]),
-                        Array(tags) => tags
-                            .into_iter()
-                            .filter_map(Yaml::into_string)
-                            .collect::<Set<_>>(),
-                        _ => return None,
-                    };
-                    Some(tags)
-                })
-                .flatten()
-                .unwrap_or_default()
-        };
+        }
+        aliases
+    };
+    let is_published = {
+        let publish = matter.remove("publish").and_then(yaml_bool);
+        policy.is_published(id, publish)
+    };
+    let title = matter.remove("title").and_then(Yaml::into_string);
+    let created_at = matter
+        .remove("created")
+        .and_then(yaml_date_time)
+        .or(times.created_at);
+    let updated_at = matter
+        .remove("updated")
+        .and_then(yaml_date_time)
+        .or(times.modified_at);
+    let properties = matter
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, yaml_to_json(value)?)))
+        .collect::<Map<_, _>>();
 
-        let note = Note::builder()
-            .id(id)
-            .names(names)
-            .links(links)
-            .tags(tags)
-            .build();
-        Ok(Some(note))
+    let names = {
+        let mut names: Set<String> = default();
+        let mut parts =
+            id.split('/').map(ToOwned::to_owned).collect::<Vec<_>>();
+        while !parts.is_empty() {
+            names.insert(parts.join("/"));
+            if let Some((_, tail)) = parts.split_first() {
+                parts = tail.to_vec();
+            }
+        }
+        names.extend(aliases.iter().cloned());
+        names
+    };
+
+    Note::builder()
+        .id(id.to_owned())
+        .names(names)
+        .aliases(aliases)
+        .title(title)
+        .links(links)
+        .tags(tags)
+        .tasks(tasks)
+        .is_published(is_published)
+        .created_at(created_at)
+        .updated_at(updated_at)
+        .modified_at(times.modified_at)
+        .properties(properties)
+        .content(content.to_owned())
+        .build()
+}
+
+/// Read a front matter value as a set of strings, which may be written as
+/// either a single string or a list.
+fn yaml_strings(value: Yaml) -> Set<String> {
+    use Yaml::*;
+    match value {
+        String(value) => Set::from_iter([value]),
+        Array(values) => values
+            .into_iter()
+            .filter_map(Yaml::into_string)
+            .collect::<Set<_>>(),
+        _ => default(),
     }
+}
 
-    fn note_path(&self, note_id: &str) -> String {
-        let mut path = Path::new(&self.vault_path).to_path_buf();
-        path.push(format!("{}.md", note_id));
-        path.to_string_lossy().into_owned()
+fn yaml_bool(value: Yaml) -> Option<bool> {
+    use Yaml::*;
+    match value {
+        Boolean(value) => Some(value),
+        String(value) => value.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Read a front matter value as a date-time, which may be written as either
+/// an RFC 3339 date-time, a local date-time, or a date.
+///
+/// Local date-times and dates are assumed to be in UTC.
+fn yaml_date_time(value: Yaml) -> Option<DateTime> {
+    let value = value.into_string()?;
+    let value = value.trim();
+    if let Ok(date_time) = DateTime::<FixedOffset>::parse_from_rfc3339(value) {
+        return Some(date_time.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"] {
+        if let Ok(date_time) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(Utc.from_utc_datetime(&date_time));
+        }
     }
+    let date = Date::parse_from_str(value, "%Y-%m-%d").ok()?;
+    Some(Utc.from_utc_datetime(&date.and_hms(0, 0, 0)))
+}
+
+fn yaml_to_json(value: Yaml) -> Option<Json> {
+    use Yaml::*;
+    let value = match value {
+        Real(value) => {
+            let value = value.parse::<f64>().ok()?;
+            json!(value)
+        }
+        Integer(value) => json!(value),
+        String(value) => json!(value),
+        Boolean(value) => json!(value),
+        Array(values) => {
+            let values = values.into_iter().filter_map(yaml_to_json);
+            Json::Array(values.collect())
+        }
+        Hash(hash) => {
+            let entries = hash.into_iter().filter_map(|(key, value)| {
+                let key = match key {
+                    String(key) => key,
+                    Integer(key) => key.to_string(),
+                    Real(key) => key,
+                    Boolean(key) => key.to_string(),
+                    _ => return None,
+                };
+                Some((key, yaml_to_json(value)?))
+            });
+            Json::Object(entries.collect())
+        }
+        Null => Json::Null,
+        Alias(_) | BadValue => return None,
+    };
+    Some(value)
 }
@@ -0,0 +1,382 @@
+use super::*;
+
+use markdown::escape::escape_html;
+use markdown::html::push_html;
+use markdown::{CowStr, Event, Options, Parser, Tag};
+
+/// Labels links to hidden notes, which mustn't reveal their names.
+const HIDDEN_NOTE_LABEL: &str = "private note";
+
+/// Labels links to hidden attachments, which mustn't reveal their names.
+const HIDDEN_ATTACHMENT_LABEL: &str = "private attachment";
+
+/// What the target of a link resolved to.
+#[derive(Debug, Clone)]
+pub(super) enum LinkResolution {
+    /// The target is visible at a URL.
+    Visible(Url),
+
+    /// The target exists, but isn't visible (i.e. it isn't published), so
+    /// the link mustn't reveal its name.
+    Hidden,
+
+    /// The target doesn't exist.
+    Missing,
+}
+
+impl LinkResolution {
+    /// Resolve a link to `target` (if it exists), using `url` to find the
+    /// URL of visible targets.
+    pub fn new<T>(
+        target: Option<T>,
+        url: impl FnOnce(T) -> Option<Url>,
+    ) -> Self {
+        match target {
+            Some(target) => match url(target) {
+                Some(url) => Self::Visible(url),
+                None => Self::Hidden,
+            },
+            None => Self::Missing,
+        }
+    }
+}
+
+/// Render a note's Markdown content to HTML.
+///
+/// Links to other notes are resolved using `resolve`. Unresolved links are
+/// rendered with the `is-unresolved` class, and links to hidden notes are
+/// rendered without their targets.
+///
+/// Embedded attachments are resolved using `resolve_attachment`; images are
+/// rendered inline.
+pub(super) fn render_html(
+    note_id: &str,
+    content: &str,
+    resolve: impl Fn(&str) -> LinkResolution,
+    resolve_attachment: impl Fn(&str) -> LinkResolution,
+) -> String {
+    let resolvers = Resolvers {
+        note: &resolve,
+        attachment: &resolve_attachment,
+    };
+
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS;
+    let parser = Parser::new_ext(content, options);
+
+    // Wiki-links may be split across several text events, so merge adjacent
+    // text events before expanding links.
+    let mut events: Vec<Event> = Vec::new();
+    let mut text = String::new();
+    let mut in_code_block = false;
+    for event in parser {
+        match event {
+            Event::Text(fragment) if !in_code_block => {
+                text.push_str(&fragment);
+                continue;
+            }
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(Tag::CodeBlock(_)) => in_code_block = false,
+            _ => (),
+        }
+        if !text.is_empty() {
+            expand_wiki_links(note_id, &text, &resolvers, &mut events);
+            text.clear();
+        }
+        let event = match event {
+            Event::Start(Tag::Link(kind, destination, title)) => {
+                let destination =
+                    markdown_link_destination(note_id, destination, &resolvers);
+                Event::Start(Tag::Link(kind, destination, title))
+            }
+            Event::Start(Tag::Image(kind, destination, title)) => {
+                let destination =
+                    markdown_link_destination(note_id, destination, &resolvers);
+                Event::Start(Tag::Image(kind, destination, title))
+            }
+            event => event,
+        };
+        events.push(event);
+    }
+    if !text.is_empty() {
+        expand_wiki_links(note_id, &text, &resolvers, &mut events);
+    }
+
+    let mut html = String::new();
+    push_html(&mut html, events.into_iter());
+    html
+}
+
+struct Resolvers<'a> {
+    note: &'a dyn Fn(&str) -> LinkResolution,
+    attachment: &'a dyn Fn(&str) -> LinkResolution,
+}
+
+fn expand_wiki_links(
+    note_id: &str,
+    text: &str,
+    resolvers: &Resolvers,
+    events: &mut Vec<Event>,
+) {
+    let mut last = 0;
+    for captures in WIKI_LINK_REGEX.captures_iter(text) {
+        let range = captures.get(0).unwrap().range();
+        let is_embed = !captures.get(1).unwrap().as_str().is_empty();
+        let inner = captures.get(2).unwrap().as_str();
+        let link = match parse_wiki_link(note_id, inner, is_embed) {
+            Some(link) => link,
+            None => continue,
+        };
+        if range.start > last {
+            let text = text[last..range.start].to_owned();
+            events.push(Event::Text(CowStr::from(text)));
+        }
+        let html = if link.is_attachment() {
+            render_attachment_link(&link, resolvers.attachment)
+        } else {
+            render_wiki_link(note_id, &link, resolvers.note)
+        };
+        events.push(Event::Html(CowStr::from(html)));
+        last = range.end;
+    }
+    if last < text.len() {
+        let text = text[last..].to_owned();
+        events.push(Event::Text(CowStr::from(text)));
+    }
+}
+
+fn render_wiki_link(
+    note_id: &str,
+    link: &NoteLink,
+    resolve: &dyn Fn(&str) -> LinkResolution,
+) -> String {
+    let NoteLink {
+        target,
+        alias,
+        is_embed,
+        ..
+    } = link;
+
+    let mut class = "internal-link".to_owned();
+    if *is_embed {
+        class.push_str(" internal-embed");
+    }
+    let resolution = resolve(target);
+    let is_hidden = matches!(resolution, LinkResolution::Hidden);
+    let url = match resolution {
+        LinkResolution::Visible(url) => Some(link_url(url, link)),
+        _ => None,
+    };
+    if url.is_none() {
+        class.push_str(" is-unresolved");
+    }
+
+    let label = match alias {
+        Some(alias) => alias.to_owned(),
+        None if is_hidden => HIDDEN_NOTE_LABEL.to_owned(),
+        None => link_label(note_id, link),
+    };
+
+    let mut html = String::new();
+    html.push_str(r#"<a class=""#);
+    html.push_str(&class);
+    html.push('"');
+    if let Some(url) = url {
+        html.push_str(r#" href=""#);
+        escape_html(&mut html, url.as_str()).unwrap();
+        html.push('"');
+    }
+    if !is_hidden {
+        html.push_str(r#" data-target=""#);
+        escape_html(&mut html, target).unwrap();
+        html.push('"');
+    }
+    html.push('>');
+    escape_html(&mut html, &label).unwrap();
+    html.push_str("</a>");
+    html
+}
+
+fn render_attachment_link(
+    link: &NoteLink,
+    resolve: &dyn Fn(&str) -> LinkResolution,
+) -> String {
+    let NoteLink {
+        target,
+        alias,
+        is_embed,
+        ..
+    } = link;
+    let resolution = resolve(target);
+    let is_hidden = matches!(resolution, LinkResolution::Hidden);
+    let url = match resolution {
+        LinkResolution::Visible(url) => Some(url),
+        _ => None,
+    };
+    let label = match alias {
+        Some(alias) => alias.as_str(),
+        None if is_hidden => HIDDEN_ATTACHMENT_LABEL,
+        None => target.as_str(),
+    };
+
+    let mut html = String::new();
+    if let Some(url) = &url {
+        if *is_embed && is_image_name(target) {
+            html.push_str(r#"<img class="internal-embed" src=""#);
+            escape_html(&mut html, url.as_str()).unwrap();
+            html.push_str(r#"" alt=""#);
+            escape_html(&mut html, label).unwrap();
+            html.push_str(r#"">"#);
+            return html;
+        }
+    }
+
+    let mut class = "internal-link".to_owned();
+    if *is_embed {
+        class.push_str(" internal-embed");
+    }
+    if url.is_none() {
+        class.push_str(" is-unresolved");
+    }
+    html.push_str(r#"<a class=""#);
+    html.push_str(&class);
+    html.push('"');
+    if let Some(url) = &url {
+        html.push_str(r#" href=""#);
+        escape_html(&mut html, url.as_str()).unwrap();
+        html.push('"');
+    }
+    if !is_hidden {
+        html.push_str(r#" data-target=""#);
+        escape_html(&mut html, target).unwrap();
+        html.push('"');
+    }
+    html.push('>');
+    escape_html(&mut html, label).unwrap();
+    html.push_str("</a>");
+    html
+}
+
+fn is_image_name(name: &str) -> bool {
+    let extension = name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_lowercase());
+    matches!(
+        extension.as_deref(),
+        Some("png" | "jpg" | "jpeg" | "gif" | "bmp" | "svg" | "webp")
+    )
+}
+
+/// Rewrite the destination of a Markdown link to a note or an attachment,
+/// leaving other destinations (and missing targets) as they are.
+fn markdown_link_destination<'a>(
+    note_id: &str,
+    destination: CowStr<'a>,
+    resolvers: &Resolvers,
+) -> CowStr<'a> {
+    let link = match parse_markdown_link(note_id, &destination) {
+        Some(link) => link,
+        None => return destination,
+    };
+    let resolution = if link.is_attachment() {
+        (resolvers.attachment)(&link.target)
+    } else {
+        match (resolvers.note)(&link.target) {
+            LinkResolution::Visible(url) => {
+                LinkResolution::Visible(link_url(url, &link))
+            }
+            resolution => resolution,
+        }
+    };
+    match resolution {
+        LinkResolution::Visible(url) => CowStr::from(url.to_string()),
+        LinkResolution::Hidden => CowStr::from(""),
+        LinkResolution::Missing => destination,
+    }
+}
+
+fn link_url(mut url: Url, link: &NoteLink) -> Url {
+    let NoteLink { heading, block, .. } = link;
+    if let Some(heading) = heading {
+        url.set_fragment(Some(heading));
+    } else if let Some(block) = block {
+        url.set_fragment(Some(&format!("^{}", block)));
+    }
+    url
+}
+
+/// The default label of a link, in the style of Obsidian (i.e.
+/// `Note > Heading`).
+fn link_label(note_id: &str, link: &NoteLink) -> String {
+    let NoteLink {
+        target,
+        heading,
+        block,
+        ..
+    } = link;
+    let fragment = match (heading, block) {
+        (Some(heading), _) => Some(heading.to_owned()),
+        (None, Some(block)) => Some(format!("^{}", block)),
+        (None, None) => None,
+    };
+    match fragment {
+        Some(fragment) if target == note_id => fragment,
+        Some(fragment) => format!("{} > {}", target, fragment),
+        None => target.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(content: &str) -> String {
+        render_html(
+            "Note",
+            content,
+            |name| match name {
+                "Public" => LinkResolution::Visible(
+                    "https://example.com/Public".parse().unwrap(),
+                ),
+                "Secret" => LinkResolution::Hidden,
+                _ => LinkResolution::Missing,
+            },
+            |name| match name {
+                "secret.png" => LinkResolution::Hidden,
+                _ => LinkResolution::Missing,
+            },
+        )
+    }
+
+    #[test]
+    fn renders_visible_links() {
+        let html = render("See [[Public]].");
+        assert!(html.contains(r#"href="https://example.com/Public""#));
+        assert!(html.contains(r#"data-target="Public">Public</a>"#));
+    }
+
+    #[test]
+    fn renders_missing_links() {
+        let html = render("See [[Missing]].");
+        assert!(html.contains("is-unresolved"));
+        assert!(html.contains(r#"data-target="Missing">Missing</a>"#));
+    }
+
+    #[test]
+    fn hides_names_of_hidden_links() {
+        let html = render("See [[Secret]] and [[Secret|this]].");
+        assert!(!html.contains("Secret"));
+        assert!(html.contains(r#"is-unresolved">private note</a>"#));
+        assert!(html.contains(r#"is-unresolved">this</a>"#));
+
+        let html = render("See [the secret](Secret.md).");
+        assert!(!html.contains("Secret"));
+        assert!(html.contains("the secret"));
+
+        let html = render("See ![[secret.png]].");
+        assert!(!html.contains("secret.png"));
+        assert!(html.contains(r#"is-unresolved">private attachment</a>"#));
+    }
+}
@@ -0,0 +1,384 @@
+use super::*;
+
+use std::ops::Range;
+
+const NAME_WEIGHT: u32 = 3;
+const SNIPPET_CONTEXT_BEFORE: usize = 60;
+const SNIPPET_CONTEXT_AFTER: usize = 140;
+
+// BM25 parameters.
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+/// An in-memory inverted index over the names and contents of notes.
+#[derive(Debug, Default)]
+pub(super) struct SearchIndex {
+    documents: Map<String, SearchDocument>,
+    postings: Map<String, Map<String, u32>>,
+    total_length: usize,
+}
+
+#[derive(Debug)]
+struct SearchDocument {
+    note: Note,
+    tokens: Vec<String>,
+    length: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub note: Note,
+    pub score: f64,
+    pub snippet: String,
+    pub highlights: Vec<SearchHighlight>,
+}
+
+/// A highlighted region of a snippet, in character offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchHighlight {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SearchTerm {
+    Word(String),
+    Phrase(Vec<String>),
+}
+
+impl SearchTerm {
+    fn words(&self) -> Vec<&String> {
+        use SearchTerm::*;
+        match self {
+            Word(word) => vec![word],
+            Phrase(words) => words.iter().collect(),
+        }
+    }
+}
+
+impl SearchIndex {
+    pub fn new(notes: impl IntoIterator<Item = Note>) -> Self {
+        let mut index = Self::default();
+        for note in notes {
+            index.insert(note);
+        }
+        index
+    }
+
+    fn insert(&mut self, note: Note) {
+        let tokens = tokenize(&note.content)
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect::<Vec<_>>();
+
+        let mut frequencies: Map<String, u32> = default();
+        for token in &tokens {
+            *frequencies.entry(token.clone()).or_default() += 1;
+        }
+        for name in &note.names {
+            for (token, _) in tokenize(name) {
+                *frequencies.entry(token).or_default() += NAME_WEIGHT;
+            }
+        }
+
+        let length = frequencies.values().sum::<u32>() as usize;
+        for (token, frequency) in frequencies {
+            self.postings
+                .entry(token)
+                .or_default()
+                .insert(note.id.clone(), frequency);
+        }
+        self.total_length += length;
+        self.documents.insert(
+            note.id.clone(),
+            SearchDocument {
+                note,
+                tokens,
+                length,
+            },
+        );
+    }
+
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let terms = parse_query(query);
+        if terms.is_empty() || self.documents.is_empty() {
+            return default();
+        }
+
+        // Find documents which contain every word in the query.
+        let words =
+            terms.iter().flat_map(SearchTerm::words).collect::<Set<_>>();
+        let candidates = {
+            let mut candidates: Option<Set<&String>> = None;
+            for word in &words {
+                let ids = match self.postings.get(*word) {
+                    Some(postings) => postings.keys().collect::<Set<_>>(),
+                    None => return default(),
+                };
+                candidates = match candidates {
+                    Some(candidates) => {
+                        Some(candidates.intersection(&ids).copied().collect())
+                    }
+                    None => Some(ids),
+                };
+            }
+            candidates.unwrap_or_default()
+        };
+
+        // Score candidates that match every phrase in the query.
+        let average_length =
+            self.total_length as f64 / self.documents.len() as f64;
+        let mut hits = candidates
+            .into_iter()
+            .filter_map(|id| {
+                let document = self.documents.get(id)?;
+                let matches_phrases = terms.iter().all(|term| match term {
+                    SearchTerm::Phrase(words) => {
+                        contains_phrase(&document.tokens, words)
+                    }
+                    SearchTerm::Word(_) => true,
+                });
+                if !matches_phrases {
+                    return None;
+                }
+
+                let score = words
+                    .iter()
+                    .map(|word| self.score(word, id, document, average_length))
+                    .sum::<f64>();
+                let (snippet, highlights) =
+                    build_snippet(&document.note.content, &terms);
+                let hit = SearchHit {
+                    note: document.note.clone(),
+                    score,
+                    snippet,
+                    highlights,
+                };
+                Some(hit)
+            })
+            .collect::<Vec<_>>();
+        hits.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.note.id.cmp(&b.note.id))
+        });
+        hits
+    }
+
+    fn score(
+        &self,
+        word: &str,
+        id: &str,
+        document: &SearchDocument,
+        average_length: f64,
+    ) -> f64 {
+        let postings = match self.postings.get(word) {
+            Some(postings) => postings,
+            None => return 0.0,
+        };
+        let frequency = match postings.get(id) {
+            Some(frequency) => *frequency as f64,
+            None => return 0.0,
+        };
+        let count = self.documents.len() as f64;
+        let matches = postings.len() as f64;
+        let idf = ((count - matches + 0.5) / (matches + 0.5) + 1.0).ln();
+        let length = document.length as f64;
+        let norm = K1 * (1.0 - B + B * length / average_length.max(1.0));
+        idf * frequency * (K1 + 1.0) / (frequency + norm)
+    }
+}
+
+/// Split text into lowercase alphanumeric tokens, along with their byte
+/// ranges in the original text.
+fn tokenize(text: &str) -> Vec<(String, Range<usize>)> {
+    let mut tokens = Vec::new();
+    let mut start: Option<usize> = None;
+    for (i, char) in text.char_indices() {
+        if char.is_alphanumeric() {
+            if start.is_none() {
+                start = Some(i);
+            }
+        } else if let Some(token_start) = start.take() {
+            let token = text[token_start..i].to_lowercase();
+            tokens.push((token, token_start..i));
+        }
+    }
+    if let Some(token_start) = start {
+        let token = text[token_start..].to_lowercase();
+        tokens.push((token, token_start..text.len()));
+    }
+    tokens
+}
+
+/// Parse a query into words and "quoted phrases".
+fn parse_query(query: &str) -> Vec<SearchTerm> {
+    let mut terms = Vec::new();
+    for (i, part) in query.split('"').enumerate() {
+        let words = tokenize(part)
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect::<Vec<_>>();
+        let is_phrase = i % 2 == 1;
+        if is_phrase && words.len() > 1 {
+            terms.push(SearchTerm::Phrase(words));
+        } else {
+            terms.extend(words.into_iter().map(SearchTerm::Word));
+        }
+    }
+    terms.dedup();
+    terms
+}
+
+fn contains_phrase(tokens: &[String], phrase: &[String]) -> bool {
+    tokens.windows(phrase.len()).any(|window| window == phrase)
+}
+
+/// Build a snippet around the first match of any query term, along with
+/// the ranges of all matches within the snippet.
+fn build_snippet(
+    content: &str,
+    terms: &[SearchTerm],
+) -> (String, Vec<SearchHighlight>) {
+    let tokens = tokenize(content);
+
+    // Find the byte ranges of every match in the content.
+    let mut matches: Vec<Range<usize>> = Vec::new();
+    for (i, (token, range)) in tokens.iter().enumerate() {
+        for term in terms {
+            match term {
+                SearchTerm::Word(word) => {
+                    if token == word {
+                        matches.push(range.clone());
+                    }
+                }
+                SearchTerm::Phrase(words) => {
+                    let window = tokens.get(i..(i + words.len()));
+                    let window = match window {
+                        Some(window) => window,
+                        None => continue,
+                    };
+                    let is_match = window
+                        .iter()
+                        .zip(words)
+                        .all(|((token, _), word)| token == word);
+                    if is_match {
+                        let (_, last) = window.last().unwrap();
+                        matches.push(range.start..last.end);
+                    }
+                }
+            }
+        }
+    }
+
+    // Choose a window of the content around the first match.
+    let first = matches.first().map(|range| range.start).unwrap_or_default();
+    let start = content[..first]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_CONTEXT_BEFORE)
+        .map(|(i, _)| i)
+        .unwrap_or_default();
+    let end = content[first..]
+        .char_indices()
+        .nth(SNIPPET_CONTEXT_AFTER)
+        .map(|(i, _)| first + i)
+        .unwrap_or(content.len());
+
+    let mut snippet = String::new();
+    let mut offset = 0;
+    if start > 0 {
+        snippet.push('…');
+        offset = 1;
+    }
+    let text = &content[start..end];
+    let text = text.replace('\n', " ");
+    snippet.push_str(&text);
+    if end < content.len() {
+        snippet.push('…');
+    }
+
+    let highlights = {
+        let mut highlights = matches
+            .into_iter()
+            .filter(|range| range.start >= start && range.end <= end)
+            .map(|range| {
+                let start_chars = content[start..range.start].chars().count();
+                let len_chars = content[range].chars().count();
+                SearchHighlight {
+                    start: offset + start_chars,
+                    end: offset + start_chars + len_chars,
+                }
+            })
+            .collect::<Vec<_>>();
+        highlights.sort_by_key(|highlight| highlight.start);
+        highlights.dedup();
+        highlights
+    };
+    (snippet, highlights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(id: &str, content: &str) -> Note {
+        Note::builder()
+            .id(id.to_owned())
+            .names([id.to_owned()].into_iter().collect())
+            .content(content.to_owned())
+            .build()
+    }
+
+    fn search(index: &SearchIndex, query: &str) -> Vec<String> {
+        index
+            .search(query)
+            .into_iter()
+            .map(|hit| hit.note.id)
+            .collect()
+    }
+
+    #[test]
+    fn ranks_frequent_matches_first() {
+        let index = SearchIndex::new([
+            note("Once", "Rust is mentioned once among many other words."),
+            note("Twice", "Rust, and then Rust again."),
+            note("Never", "Nothing to see here."),
+        ]);
+        assert_eq!(search(&index, "rust"), ["Twice", "Once"]);
+    }
+
+    #[test]
+    fn ranks_name_matches_first() {
+        let index = SearchIndex::new([
+            note("Gardening", "Notes about plants."),
+            note("Plants", "Notes about gardening."),
+        ]);
+        assert_eq!(search(&index, "gardening"), ["Gardening", "Plants"]);
+    }
+
+    #[test]
+    fn requires_every_word_and_phrase() {
+        let index = SearchIndex::new([
+            note("A", "The quick brown fox."),
+            note("B", "The brown and quick fox."),
+        ]);
+        assert_eq!(search(&index, "fox quick"), ["A", "B"]);
+        assert_eq!(search(&index, "\"quick brown\""), ["A"]);
+        assert!(search(&index, "fox wolf").is_empty());
+    }
+
+    #[test]
+    fn highlights_matches() {
+        let index = SearchIndex::new([note("Note", "Über die Brücke.")]);
+        let hits = index.search("brücke");
+        let SearchHit {
+            snippet,
+            highlights,
+            ..
+        } = &hits[0];
+        assert_eq!(snippet, "Über die Brücke.");
+        assert_eq!(highlights, &[SearchHighlight { start: 9, end: 15 }]);
+    }
+}
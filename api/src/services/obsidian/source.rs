@@ -0,0 +1,84 @@
+use super::*;
+
+use tokio::sync::mpsc::UnboundedReceiver;
+
+/// A collection of notes and attachments (i.e. an Obsidian vault).
+pub(super) trait KnowledgeSource: Debug + Send + Sync {
+    /// Returns the IDs of all notes.
+    fn list_notes(&self) -> Result<Set<String>>;
+
+    /// Read the note with the given ID, or return `None` if it doesn't
+    /// exist.
+    fn read_note(&self, id: &str) -> Result<Option<Note>>;
+
+    fn list_attachments(&self) -> Result<Vec<Attachment>>;
+
+    /// The directory that contains the source's files, if any.
+    ///
+    /// Only sources that are backed by a directory can have their history
+    /// read from git.
+    fn vault_path(&self) -> Option<&Path> {
+        None
+    }
+
+    /// Watch the source for changes to its notes and attachments.
+    ///
+    /// Fails if the source can't be watched (i.e. it isn't backed by a
+    /// directory).
+    fn watch(
+        &self,
+        _delay: StdDuration,
+    ) -> Result<(Watcher, UnboundedReceiver<VaultEvent>)> {
+        bail!("vault can't be watched")
+    }
+}
+
+/// The format of a vault, which determines how it is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VaultFormat {
+    /// A directory of Markdown files, as written by Obsidian.
+    Obsidian,
+
+    /// A `.zip`, `.tar`, or `.tar.gz` archive of an Obsidian vault.
+    Archive,
+
+    /// A Logseq graph, with pages in `pages/` and daily notes in
+    /// `journals/`.
+    Logseq,
+}
+
+impl Default for VaultFormat {
+    fn default() -> Self {
+        Self::Obsidian
+    }
+}
+
+impl FromStr for VaultFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use VaultFormat::*;
+        let format = match s.trim().to_lowercase().as_str() {
+            "obsidian" => Obsidian,
+            "archive" => Archive,
+            "logseq" => Logseq,
+            _ => bail!("unknown vault format {:?}", s),
+        };
+        Ok(format)
+    }
+}
+
+/// Open the vault at `vault_path`, in the given format.
+pub(super) fn open_source(
+    vault_path: &str,
+    format: VaultFormat,
+    policy: PublishPolicy,
+) -> Result<Arc<dyn KnowledgeSource>> {
+    use VaultFormat::*;
+    let source: Arc<dyn KnowledgeSource> = match format {
+        Obsidian => Arc::new(Reader::new(vault_path, policy)?),
+        Archive => Arc::new(ArchiveReader::new(vault_path, policy)?),
+        Logseq => Arc::new(LogseqReader::new(vault_path, policy)?),
+    };
+    Ok(source)
+}
@@ -0,0 +1,385 @@
+use super::*;
+
+use std::path::{Component, Path, PathBuf};
+
+lazy_static! {
+    pub(super) static ref WIKI_LINK_REGEX: Regex =
+        Regex::new(r"(!?)\[\[([^\[\]]+)\]\]").unwrap();
+    static ref MARKDOWN_LINK_REGEX: Regex =
+        Regex::new(r"(!?)\[([^\[\]]*)\]\(<?([^()<>\s]+)>?\)").unwrap();
+    static ref TAG_REGEX: Regex =
+        Regex::new(r"(?:^|[\s(\[])#([\p{L}\p{N}_\-/]+)").unwrap();
+    static ref INLINE_CODE_REGEX: Regex = Regex::new(r"`[^`\n]*`").unwrap();
+    static ref TASK_REGEX: Regex =
+        Regex::new(r"^\s*(?:[-*+]|\d+[.)])\s+\[(.)\]\s+(.*)$").unwrap();
+    static ref TASK_DUE_REGEX: Regex =
+        Regex::new(r"\s*📅\s*(\d{4}-\d{2}-\d{2})").unwrap();
+}
+
+/// Parse all wiki-links and Markdown links to notes and attachments in a
+/// note's text.
+pub(super) fn parse_links(note_id: &str, text: &str) -> Vec<NoteLink> {
+    let mut links = Vec::new();
+    for captures in WIKI_LINK_REGEX.captures_iter(text) {
+        let is_embed = !captures.get(1).unwrap().as_str().is_empty();
+        let inner = captures.get(2).unwrap().as_str();
+        if let Some(link) = parse_wiki_link(note_id, inner, is_embed) {
+            links.push(link);
+        }
+    }
+    for captures in MARKDOWN_LINK_REGEX.captures_iter(text) {
+        let is_embed = !captures.get(1).unwrap().as_str().is_empty();
+        let label = captures.get(2).unwrap().as_str();
+        let destination = captures.get(3).unwrap().as_str();
+        if let Some(mut link) = parse_markdown_link(note_id, destination) {
+            link.is_embed = is_embed;
+            if !label.is_empty() {
+                link.alias = Some(label.to_owned());
+            }
+            links.push(link);
+        }
+    }
+
+    let mut seen: Set<NoteLink> = default();
+    links.retain(|link| seen.insert(link.clone()));
+    links
+}
+
+/// Parse all inline tags (i.e. `#tag` or `#parent/child`) in a note's
+/// content, ignoring code.
+pub(super) fn parse_tags(content: &str) -> Set<String> {
+    let mut tags: Set<String> = default();
+    for (_, line) in lines_outside_fences(content) {
+        let line = INLINE_CODE_REGEX.replace_all(line, "");
+        for captures in TAG_REGEX.captures_iter(&line) {
+            let tag = captures.get(1).unwrap().as_str();
+            if let Some(tag) = normalize_tag(tag) {
+                tags.insert(tag);
+            }
+        }
+    }
+    tags
+}
+
+/// Parse all tasks (i.e. `- [ ] Do something 📅 2021-12-31`) in a note's
+/// content, ignoring code.
+///
+/// Line numbers are offset by `first_line`, which is the line of the file
+/// that the content starts on.
+pub(super) fn parse_tasks(content: &str, first_line: usize) -> Vec<NoteTask> {
+    let mut tasks = Vec::new();
+    for (index, line) in lines_outside_fences(content) {
+        let captures = match TASK_REGEX.captures(line) {
+            Some(captures) => captures,
+            None => continue,
+        };
+        let marker = captures.get(1).unwrap().as_str();
+        let text = captures.get(2).unwrap().as_str();
+        let status =
+            NoteTaskStatus::from_marker(marker.chars().next().unwrap());
+        let due = TASK_DUE_REGEX.captures(text).and_then(|captures| {
+            let due = captures.get(1).unwrap().as_str();
+            Date::parse_from_str(due, "%Y-%m-%d").ok()
+        });
+        let text = TASK_DUE_REGEX.replace_all(text, "").trim().to_owned();
+        let task = NoteTask::builder()
+            .line(first_line + index)
+            .text(text)
+            .status(status)
+            .due(due)
+            .build();
+        tasks.push(task);
+    }
+    tasks
+}
+
+/// Iterate over the lines of a note's content (with their indices), skipping
+/// fenced code blocks.
+pub(super) fn lines_outside_fences(
+    content: &str,
+) -> impl Iterator<Item = (usize, &str)> {
+    let mut fence: Option<&str> = None;
+    content.lines().enumerate().filter(move |(_, line)| {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            return false;
+        }
+        if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker))
+        {
+            fence = Some(marker);
+            return false;
+        }
+        true
+    })
+}
+
+/// Normalize a tag by removing its leading `#` and any empty segments.
+///
+/// Returns `None` if the tag is invalid (i.e. purely numeric, like `#123`).
+pub(super) fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#');
+    let tag = tag
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+    if tag.chars().all(|c| c.is_numeric() || c == '/') {
+        return None;
+    }
+    Some(tag)
+}
+
+/// Parse the inside of a wiki-link, i.e. `Note#Heading|Alias` or
+/// `Note#^block`.
+pub(super) fn parse_wiki_link(
+    note_id: &str,
+    inner: &str,
+    is_embed: bool,
+) -> Option<NoteLink> {
+    let (reference, alias) = match inner.split_once('|') {
+        Some((reference, alias)) => (reference, Some(alias.trim())),
+        None => (inner, None),
+    };
+    let fragment_start = reference.find(|c: char| c == '#' || c == '^');
+    let (target, fragment) = match fragment_start {
+        Some(index) => (&reference[..index], Some(&reference[index..])),
+        None => (reference, None),
+    };
+    let (heading, block) = parse_fragment(fragment);
+
+    // Links like `[[#Heading]]` refer to the note they're in.
+    let target = target.trim();
+    let target = target.strip_suffix(".md").unwrap_or(target);
+    let target = if target.is_empty() {
+        if heading.is_none() && block.is_none() {
+            return None;
+        }
+        note_id
+    } else {
+        target
+    };
+
+    let link = NoteLink::builder()
+        .target(target.to_owned())
+        .heading(heading)
+        .block(block)
+        .alias(
+            alias
+                .filter(|alias| !alias.is_empty())
+                .map(ToOwned::to_owned),
+        )
+        .is_embed(is_embed)
+        .build();
+    Some(link)
+}
+
+/// Parse the destination of a Markdown link, if it refers to a note or an
+/// attachment in the vault (i.e. `../Folder/Note%20Name.md#Heading` or
+/// `images/diagram.png`).
+///
+/// Relative destinations are resolved against the folder of the note that
+/// contains the link.
+pub(super) fn parse_markdown_link(
+    note_id: &str,
+    destination: &str,
+) -> Option<NoteLink> {
+    if destination.contains("://") || destination.starts_with("mailto:") {
+        return None;
+    }
+    let (path, fragment) = match destination.split_once('#') {
+        Some((path, fragment)) => (path, Some(fragment)),
+        None => (destination, None),
+    };
+    let decoded = decode_percent_encoding(path);
+    let path = match decoded.strip_suffix(".md") {
+        Some(path) => path,
+        None if is_attachment_name(&decoded) => decoded.as_str(),
+        None => return None,
+    };
+
+    // Resolve the destination relative to the note's folder.
+    let target = {
+        let mut resolved = PathBuf::new();
+        if !path.starts_with('/') {
+            if let Some((folder, _)) = note_id.rsplit_once('/') {
+                resolved.push(folder);
+            }
+        }
+        for component in Path::new(path).components() {
+            match component {
+                Component::Normal(part) => resolved.push(part),
+                Component::ParentDir => {
+                    resolved.pop();
+                }
+                _ => (),
+            }
+        }
+        resolved.to_string_lossy().into_owned()
+    };
+    if target.is_empty() {
+        return None;
+    }
+
+    let fragment = fragment.map(decode_percent_encoding);
+    let fragment = fragment.map(|fragment| format!("#{}", fragment));
+    let (heading, block) = parse_fragment(fragment.as_deref());
+    let link = NoteLink::builder()
+        .target(target)
+        .heading(heading)
+        .block(block)
+        .build();
+    Some(link)
+}
+
+/// Parse a link fragment (i.e. `#Heading`, `#^block`, or `^block`) into a
+/// heading and a block ID.
+fn parse_fragment(fragment: Option<&str>) -> (Option<String>, Option<String>) {
+    let fragment = match fragment {
+        Some(fragment) => fragment.trim_start_matches('#').trim(),
+        None => return (None, None),
+    };
+    if fragment.is_empty() {
+        return (None, None);
+    }
+    match fragment.strip_prefix('^') {
+        Some(block) => (None, Some(block.to_owned())),
+        None => (Some(fragment.to_owned()), None),
+    }
+}
+
+fn decode_percent_encoding(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let byte = text
+                .get((i + 1)..(i + 3))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            if let Some(byte) = byte {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_wiki_links() {
+        let text = "See [[Other Note#Usage|the docs]], [[#Intro]] and \
+                    ![[Diagram#^figure]]. [[Other Note#Usage|the docs]]";
+        let links = parse_links("Folder/Note", text);
+        let expected = vec![
+            NoteLink::builder()
+                .target("Other Note".to_owned())
+                .heading(Some("Usage".to_owned()))
+                .alias(Some("the docs".to_owned()))
+                .build(),
+            NoteLink::builder()
+                .target("Folder/Note".to_owned())
+                .heading(Some("Intro".to_owned()))
+                .build(),
+            NoteLink::builder()
+                .target("Diagram".to_owned())
+                .block(Some("figure".to_owned()))
+                .is_embed(true)
+                .build(),
+        ];
+        assert_eq!(links, expected);
+    }
+
+    #[test]
+    fn parses_markdown_links() {
+        let text = "[Sibling](Sibling%20Note.md), [Up](../Top.md#^block), \
+                    [Site](https://example.com/page.md), [Root](/Root.md), \
+                    ![](../images/diagram.png) and [Page](page.html)";
+        let links = parse_links("Folder/Note", text);
+        let expected = vec![
+            NoteLink::builder()
+                .target("Folder/Sibling Note".to_owned())
+                .alias(Some("Sibling".to_owned()))
+                .build(),
+            NoteLink::builder()
+                .target("Top".to_owned())
+                .block(Some("block".to_owned()))
+                .alias(Some("Up".to_owned()))
+                .build(),
+            NoteLink::builder()
+                .target("Root".to_owned())
+                .alias(Some("Root".to_owned()))
+                .build(),
+            NoteLink::builder()
+                .target("images/diagram.png".to_owned())
+                .is_embed(true)
+                .build(),
+        ];
+        assert_eq!(links, expected);
+    }
+
+    #[test]
+    fn parses_tags() {
+        let content = "Tagged #project/alpha and (#idea), not#this \
+                       or #123.\n\
+                       `#inline` code is skipped, but #Done isn't.\n\
+                       ```\n\
+                       #fenced\n\
+                       ```\n\
+                       #nested//tag";
+        let tags = parse_tags(content);
+        let expected = ["project/alpha", "idea", "Done", "nested/tag"]
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .collect::<Set<_>>();
+        assert_eq!(tags, expected);
+    }
+
+    #[test]
+    fn parses_tasks() {
+        let content = "- [ ] Write tests 📅 2021-12-31\n\
+                       * [x] Read the docs\n\
+                       ~~~\n\
+                       - [ ] Not a task\n\
+                       ~~~\n\
+                       1. [/] Refactor\n\
+                       - [-] Give up\n\
+                       - [] Not a task either";
+        let tasks = parse_tasks(content, 3);
+        let expected = vec![
+            NoteTask::builder()
+                .line(3)
+                .text("Write tests".to_owned())
+                .status(NoteTaskStatus::Todo)
+                .due(Some(Date::from_ymd(2021, 12, 31)))
+                .build(),
+            NoteTask::builder()
+                .line(4)
+                .text("Read the docs".to_owned())
+                .status(NoteTaskStatus::Done)
+                .build(),
+            NoteTask::builder()
+                .line(8)
+                .text("Refactor".to_owned())
+                .status(NoteTaskStatus::InProgress)
+                .build(),
+            NoteTask::builder()
+                .line(9)
+                .text("Give up".to_owned())
+                .status(NoteTaskStatus::Cancelled)
+                .build(),
+        ];
+        assert_eq!(tasks, expected);
+    }
+}
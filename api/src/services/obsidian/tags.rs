@@ -0,0 +1,99 @@
+use super::*;
+
+/// A tag, along with the tags nested beneath it (i.e. `parent/child` is
+/// nested beneath `parent`).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteTag {
+    pub name: String,
+
+    /// The number of notes with this tag.
+    pub count: usize,
+
+    /// The number of notes with this tag, or any tag nested beneath it.
+    pub total_count: usize,
+
+    pub children: Vec<NoteTag>,
+}
+
+impl NoteTag {
+    /// The last segment of the tag's name.
+    pub fn label(&self) -> &str {
+        match self.name.rsplit_once('/') {
+            Some((_, label)) => label,
+            None => &self.name,
+        }
+    }
+}
+
+/// Build a hierarchy of the tags used in `notes`, returning the top-level
+/// tags.
+pub(super) fn build_tag_tree<'a>(
+    notes: impl IntoIterator<Item = &'a Note>,
+) -> Vec<NoteTag> {
+    let mut counts: Map<String, usize> = default();
+    let mut total_counts: Map<String, usize> = default();
+    for note in notes {
+        let mut ancestors: Set<&str> = default();
+        for tag in &note.tags {
+            *counts.entry(tag.clone()).or_default() += 1;
+            ancestors.insert(tag);
+            let mut name = tag.as_str();
+            while let Some((parent, _)) = name.rsplit_once('/') {
+                ancestors.insert(parent);
+                name = parent;
+            }
+        }
+        for name in ancestors {
+            *total_counts.entry(name.to_owned()).or_default() += 1;
+        }
+    }
+
+    let mut children: Map<Option<String>, Vec<String>> = default();
+    for name in total_counts.keys() {
+        let parent = name.rsplit_once('/').map(|(parent, _)| parent.to_owned());
+        children.entry(parent).or_default().push(name.clone());
+    }
+    build_tag_children(None, &mut children, &counts, &total_counts)
+}
+
+fn build_tag_children(
+    parent: Option<String>,
+    children: &mut Map<Option<String>, Vec<String>>,
+    counts: &Map<String, usize>,
+    total_counts: &Map<String, usize>,
+) -> Vec<NoteTag> {
+    let mut names = children.remove(&parent).unwrap_or_default();
+    names.sort();
+    names
+        .into_iter()
+        .map(|name| NoteTag {
+            count: counts.get(&name).copied().unwrap_or_default(),
+            total_count: total_counts.get(&name).copied().unwrap_or_default(),
+            children: build_tag_children(
+                Some(name.clone()),
+                children,
+                counts,
+                total_counts,
+            ),
+            name,
+        })
+        .collect()
+}
+
+/// Whether `tag` matches `query`, or is nested beneath it if
+/// `include_descendants` is set.
+pub(super) fn tag_matches(
+    tag: &str,
+    query: &str,
+    include_descendants: bool,
+) -> bool {
+    if tag == query {
+        return true;
+    }
+    include_descendants
+        && tag
+            .strip_prefix(query)
+            .map(|rest| rest.starts_with('/'))
+            .unwrap_or_default()
+}
@@ -0,0 +1,123 @@
+use super::*;
+
+use std::path::Path;
+use std::sync::mpsc::channel as std_channel;
+use std::thread::spawn as spawn_thread;
+
+use notify::watcher as notify_watcher;
+use notify::DebouncedEvent;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher as NotifyWatcher};
+
+use tokio::sync::mpsc::unbounded_channel;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+/// Maps the files in a vault's directory to notes and attachments.
+pub(super) trait VaultLayout: Send + 'static {
+    /// Returns the ID of the note at `path`, if `path` is a note within the
+    /// vault.
+    fn note_id(&self, path: &Path) -> Option<String>;
+
+    /// Returns the ID of the attachment at `path`, if `path` is an
+    /// attachment within the vault.
+    fn attachment_id(&self, path: &Path) -> Option<String>;
+}
+
+/// A change to the contents of a vault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum VaultEvent {
+    NoteChanged(String),
+    NoteRemoved(String),
+    AttachmentChanged(String),
+    Rescan,
+}
+
+/// Watches a vault for changes using the platform's filesystem
+/// notification API (i.e. `inotify` on Linux).
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub(super) struct Watcher {
+    #[derivative(Debug = "ignore")]
+    _watcher: RecommendedWatcher,
+}
+
+impl Watcher {
+    pub fn new(
+        vault_path: &Path,
+        layout: impl VaultLayout,
+        delay: StdDuration,
+    ) -> Result<(Self, UnboundedReceiver<VaultEvent>)> {
+        let (notify_tx, notify_rx) = std_channel();
+        let mut watcher = notify_watcher(notify_tx, delay)
+            .context("failed to initialize watcher")?;
+        watcher
+            .watch(vault_path, RecursiveMode::Recursive)
+            .context("failed to watch vault")?;
+
+        // Forward filesystem events to the async runtime.
+        let (tx, rx) = unbounded_channel();
+        spawn_thread(move || {
+            for event in notify_rx {
+                for event in vault_events(&layout, event) {
+                    if tx.send(event).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        let watcher = Self { _watcher: watcher };
+        Ok((watcher, rx))
+    }
+}
+
+fn vault_events(
+    layout: &impl VaultLayout,
+    event: DebouncedEvent,
+) -> Vec<VaultEvent> {
+    let changed = |path: &Path| match layout.note_id(path) {
+        Some(id) => vec![VaultEvent::NoteChanged(id)],
+        None if is_directory(path) => vec![VaultEvent::Rescan],
+        None => attachment_events(layout, path),
+    };
+    let removed = |path: &Path| match layout.note_id(path) {
+        Some(id) => vec![VaultEvent::NoteRemoved(id)],
+        None if path.extension().is_none() => vec![VaultEvent::Rescan],
+        None => attachment_events(layout, path),
+    };
+
+    match event {
+        DebouncedEvent::Create(path) | DebouncedEvent::Write(path) => {
+            changed(&path)
+        }
+        DebouncedEvent::Remove(path) => removed(&path),
+        DebouncedEvent::Rename(from, to) => {
+            let mut events = removed(&from);
+            events.extend(changed(&to));
+            events
+        }
+        DebouncedEvent::Rescan => vec![VaultEvent::Rescan],
+        DebouncedEvent::Error(error, path) => {
+            warn!(%error, ?path, "vault watcher error");
+            vec![VaultEvent::Rescan]
+        }
+        DebouncedEvent::NoticeWrite(_)
+        | DebouncedEvent::NoticeRemove(_)
+        | DebouncedEvent::Chmod(_) => vec![],
+    }
+}
+
+fn attachment_events(
+    layout: &impl VaultLayout,
+    path: &Path,
+) -> Vec<VaultEvent> {
+    match layout.attachment_id(path) {
+        Some(id) => vec![VaultEvent::AttachmentChanged(id)],
+        None => vec![],
+    }
+}
+
+fn is_directory(path: &Path) -> bool {
+    path.metadata()
+        .map(|metadata| metadata.is_dir())
+        .unwrap_or_default()
+}
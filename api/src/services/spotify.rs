@@ -1,74 +1,279 @@
 use super::*;
 
-use auth::AccessToken;
-use auth::{Authenticator, AuthenticatorConfig};
+use auth::{AccessToken, AuthorizationRequest};
+use auth::{Authenticator, AuthenticatorConfig, TokenStore};
+
+use request::{RequestBuilder, Response};
 
 use http::StatusCode;
 
+use std::sync::Mutex as SyncMutex;
+
+use futures_util::stream::once as stream_once;
+use tokio::sync::broadcast::channel as broadcast_channel;
+use tokio::sync::broadcast::Sender as BroadcastSender;
+use tokio::time::interval as tick_interval;
+use tokio::time::MissedTickBehavior;
+use tokio_stream::wrappers::BroadcastStream;
+
+/// The name under which Spotify credentials are stored.
+pub const AUTH_PROVIDER: &str = "spotify";
+
+/// The scopes that the service needs to be authorized with.
+///
+/// Tokens only grant the scopes they were issued with, so the service must
+/// be authorized again after a scope is added (i.e. `user-top-read`, for
+/// top tracks and artists).
+const AUTH_SCOPES: &[&str] = &[
+    "user-read-playback-state",
+    "user-read-currently-playing",
+    "user-top-read",
+];
+
+/// The most items that Spotify returns per page.
+const PAGE_LIMIT: usize = 50;
+
+/// The number of currently playing updates to buffer for slow watchers.
+const CURRENTLY_PLAYING_UPDATES_CAPACITY: usize = 16;
+
+/// How far (in milliseconds) progress can drift from where it's expected to
+/// be before it's considered to have jumped (i.e. by seeking).
+const PROGRESS_JUMP_THRESHOLD: i64 = 3_000;
+
 #[derive(Debug, Clone, Builder)]
 pub struct ServiceConfig {
     pub client_id: String,
     pub client_secret: String,
-    pub refresh_token: String,
+
+    /// A refresh token to start with, until the service is authorized
+    /// through the authorization flow.
+    #[builder(default, setter(into))]
+    pub refresh_token: Option<String>,
+
+    /// The URL that Spotify redirects to at the end of the authorization
+    /// flow.
+    #[builder(default, setter(into))]
+    pub redirect_url: Option<Url>,
+
+    /// The base URL of the Spotify Web API, which may be replaced with a
+    /// local stand-in during tests.
+    #[builder(default = "https://api.spotify.com/v1/".parse().unwrap())]
+    pub api_base_url: Url,
+
+    /// The base URL of the Spotify Accounts service, which issues access
+    /// tokens.
+    #[builder(default = "https://accounts.spotify.com/".parse().unwrap())]
+    pub accounts_base_url: Url,
 
     #[builder(default = Duration::milliseconds(500))]
     pub ttl: Duration,
+
+    /// How often to poll for changes to the currently playing track, while
+    /// it's being watched.
+    #[builder(default = Duration::seconds(1))]
+    pub watch_interval: Duration,
+
+    /// How long to cache top tracks and artists, which change slowly.
+    #[builder(default = Duration::hours(1))]
+    pub top_items_ttl: Duration,
+
+    #[builder(default = Duration::minutes(10))]
+    pub playlists_ttl: Duration,
 }
 
-#[derive(Derivative)]
+#[derive(Derivative, Clone)]
 #[derivative(Debug)]
 pub struct Service {
     client: HttpClient,
-    authenticator: Authenticator,
+    authenticator: Arc<Authenticator>,
+    api_base_url: Url,
+    watch_interval: StdDuration,
 
     #[derivative(Debug = "ignore")]
     cache: Cache<CurrentlyPlayingKey, Option<CurrentlyPlaying>>,
 
     #[derivative(Debug = "ignore")]
-    sem: Semaphore,
+    sem: Arc<Semaphore>,
+
+    #[derivative(Debug = "ignore")]
+    top_tracks_cache: Cache<TimeRange, Vec<Track>>,
+
+    #[derivative(Debug = "ignore")]
+    top_tracks_sem: Arc<Semaphore>,
+
+    #[derivative(Debug = "ignore")]
+    top_artists_cache: Cache<TimeRange, Vec<Artist>>,
+
+    #[derivative(Debug = "ignore")]
+    top_artists_sem: Arc<Semaphore>,
+
+    #[derivative(Debug = "ignore")]
+    playlists_cache: Cache<PlaylistsKey, Vec<Playlist>>,
+
+    #[derivative(Debug = "ignore")]
+    playlists_sem: Arc<Semaphore>,
+
+    #[derivative(Debug = "ignore")]
+    updates: BroadcastSender<Option<CurrentlyPlaying>>,
+
+    /// Whether the currently playing track is being polled for changes.
+    is_polling: Arc<SyncMutex<bool>>,
+
+    /// The last currently playing state that watchers know of, and when it
+    /// was polled.
+    last_update: Arc<SyncMutex<Option<PolledCurrentlyPlaying>>>,
 }
 
+/// A currently playing state, and when it was polled.
+type PolledCurrentlyPlaying = (Option<CurrentlyPlaying>, DateTime);
+
 #[derive(Debug, Clone, Copy, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub struct CurrentlyPlayingKey;
 
+#[derive(Debug, Clone, Copy, Hash, PartialOrd, Ord, PartialEq, Eq)]
+pub struct PlaylistsKey;
+
 impl Service {
-    pub fn new(config: ServiceConfig) -> Self {
+    pub fn new(config: ServiceConfig) -> Result<Self> {
         let ServiceConfig {
             client_id,
             client_secret,
             refresh_token,
+            redirect_url,
+            api_base_url,
+            accounts_base_url,
             ttl,
+            watch_interval,
+            top_items_ttl,
+            playlists_ttl,
         } = config;
 
         let authenticator = Authenticator::new({
-            let token_endpoint: Url =
-                "https://accounts.spotify.com/api/token".parse().unwrap();
+            let token_endpoint = accounts_base_url
+                .join("api/token")
+                .context("failed to build token endpoint URL")?;
+            let authorize_endpoint = accounts_base_url
+                .join("authorize")
+                .context("failed to build authorize endpoint URL")?;
             AuthenticatorConfig::builder()
+                .provider(AUTH_PROVIDER)
                 .client_id(client_id)
                 .client_secret(client_secret)
                 .token_endpoint(token_endpoint)
+                .authorize_endpoint(authorize_endpoint)
+                .redirect_url(redirect_url)
                 .refresh_token(refresh_token)
                 .build()
-        });
+        })
+        .context("failed to initialize authenticator")?;
 
-        Self {
+        let (updates, _) =
+            broadcast_channel(CURRENTLY_PLAYING_UPDATES_CAPACITY);
+        let service = Self {
             client: default(),
             authenticator,
+            api_base_url,
+            watch_interval: watch_interval.to_std().unwrap(),
             cache: Cache::builder(1000)
                 .time_to_live(ttl.to_std().unwrap())
                 .build(),
-            sem: Semaphore::new(1),
-        }
+            sem: Semaphore::new(1).into(),
+            top_tracks_cache: Cache::builder(10)
+                .time_to_live(top_items_ttl.to_std().unwrap())
+                .build(),
+            top_tracks_sem: Semaphore::new(1).into(),
+            top_artists_cache: Cache::builder(10)
+                .time_to_live(top_items_ttl.to_std().unwrap())
+                .build(),
+            top_artists_sem: Semaphore::new(1).into(),
+            playlists_cache: Cache::builder(1)
+                .time_to_live(playlists_ttl.to_std().unwrap())
+                .build(),
+            playlists_sem: Semaphore::new(1).into(),
+            updates,
+            is_polling: default(),
+            last_update: default(),
+        };
+        Ok(service)
     }
 }
 
 impl Service {
+    /// Start authorizing the service to access a Spotify account.
+    pub fn authorize_url(&self) -> AuthorizationRequest {
+        self.authenticator.authorize_url(AUTH_SCOPES)
+    }
+
+    /// Finish authorizing the service, using the code that Spotify
+    /// redirected back with.
+    pub async fn authorize(&self, code: &str, verifier: &str) -> Result<()> {
+        let result = self.authenticator.authorize(code, verifier).await;
+        self.cache.invalidate_all();
+        result.context("failed to authorize")
+    }
+
+    pub async fn set_refresh_token(&self, refresh_token: String) -> Result<()> {
+        let result = self.authenticator.set_refresh_token(refresh_token).await;
+        self.cache.invalidate_all();
+        result.context("failed to set refresh token")
+    }
+
+    /// Persist credentials to `store`, restoring any that were stored
+    /// before.
+    pub async fn set_token_store(
+        &self,
+        store: Arc<dyn TokenStore>,
+    ) -> Result<()> {
+        self.authenticator.set_store(store).await?;
+        self.cache.invalidate_all();
+        Ok(())
+    }
+
+    /// Whether credentials are persisted, such that authorizing the service
+    /// outlasts a restart.
+    pub fn has_token_store(&self) -> bool {
+        self.authenticator.has_store()
+    }
+
+    /// Send a request with an access token, retrying once with a fresh
+    /// token if Spotify rejects it (i.e. because it was revoked early).
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let Service { authenticator, .. } = self;
+        let token = authenticator
+            .access_token()
+            .await
+            .context("failed to get access token")?;
+        let retry = request.try_clone();
+        let response = request
+            .bearer_auth(&token.token)
+            .send()
+            .await
+            .context("request failed")?;
+        let retry = match retry {
+            Some(retry) if response.status() == StatusCode::UNAUTHORIZED => {
+                retry
+            }
+            _ => return Ok(response),
+        };
+        debug!("access token rejected; retrying with a fresh one");
+        let AccessToken { token, .. } = authenticator
+            .refresh_access_token(&token)
+            .await
+            .context("failed to refresh access token")?;
+        let response = retry
+            .bearer_auth(token)
+            .send()
+            .await
+            .context("request failed")?;
+        Ok(response)
+    }
+
     pub async fn get_currently_playing(
         &self,
     ) -> Result<Option<CurrentlyPlaying>> {
         let Service {
             client,
-            authenticator,
+            api_base_url,
             cache,
             sem,
             ..
@@ -79,44 +284,31 @@ impl Service {
 
         // Try to load currently playing from cache.
         if let Some(currently_playing) = cache.get(&CurrentlyPlayingKey) {
-            let (artist_name, track_name) = match &currently_playing {
-                Some(CurrentlyPlaying { track, .. }) => {
-                    let artist = track.artists.first();
-                    (
-                        artist.map(|artist| artist.name.as_str()),
-                        Some(track.name.as_str()),
-                    )
-                }
-                None => (None, None),
-            };
+            let item = currently_playing
+                .as_ref()
+                .and_then(|currently_playing| currently_playing.item.as_ref());
             trace!(
-                artist = %artist_name.unwrap_or_default(),
-                track = %track_name.unwrap_or_default(),
+                creator = %item.map(PlayingItem::creator).unwrap_or_default(),
+                item = %item.map(PlayingItem::name).unwrap_or_default(),
                 "got currently-playing from cache",
             );
             return Ok(currently_playing.to_owned());
         }
 
-        // Fetch new currently playing data.
+        // Fetch new playback state.
         let url = {
-            let url = "https://api.spotify.com/v1/me/player/currently-playing"
-                .to_owned();
-            let mut url: Url = url.parse().unwrap();
+            let mut url = api_base_url
+                .join("me/player")
+                .context("failed to build URL")?;
             {
                 let mut query = url.query_pairs_mut();
                 query.append_pair("market", "CA");
+                query.append_pair("additional_types", "track,episode");
             }
             url
         };
-        let request = {
-            let AccessToken { token, .. } = authenticator
-                .access_token()
-                .await
-                .context("failed to get access token")?;
-            client.get(url).bearer_auth(token)
-        };
         let response = {
-            let response = request.send().await.context("request failed")?;
+            let response = self.send(client.get(url)).await?;
             response.error_for_status().context("bad status")?
         };
         let currently_playing = {
@@ -130,17 +322,17 @@ impl Service {
                     Some(current_playing)
                 }
             };
-            if let Some(CurrentlyPlaying { track, .. }) = &currently_playing {
-                let artist = track.artists.first();
-                if let Some(artist) = artist {
-                    debug!(
-                        artist = %artist.name,
-                        track = %track.name,
-                        "got currently-playing",
-                    );
-                };
+            let item = currently_playing
+                .as_ref()
+                .and_then(|currently_playing| currently_playing.item.as_ref());
+            if let Some(item) = item {
+                debug!(
+                    creator = %item.creator(),
+                    item = %item.name(),
+                    "got currently-playing",
+                );
             } else {
-                trace!("got currently-playing (none)",);
+                trace!("got currently-playing (none)");
             }
             cache
                 .insert(CurrentlyPlayingKey, currently_playing.clone())
@@ -149,17 +341,520 @@ impl Service {
         };
         Ok(currently_playing)
     }
+
+    /// Watch for changes to the currently playing track, starting with the
+    /// track that is playing now.
+    ///
+    /// A change is emitted when the item changes, when it's paused or
+    /// resumed, when its progress jumps (i.e. by seeking), or when the
+    /// player's device, shuffle, repeat, or context changes. All watchers
+    /// share a single poller, which only runs while there are watchers.
+    pub fn watch_currently_playing(
+        &self,
+    ) -> impl Stream<Item = Option<CurrentlyPlaying>> {
+        let updates = self.updates.subscribe();
+        {
+            let mut is_polling = self.is_polling.lock().unwrap();
+            if !*is_polling {
+                *is_polling = true;
+                spawn(self.clone().poll_currently_playing());
+            }
+        }
+
+        let initial = {
+            let service = self.clone();
+            stream_once(async move {
+                let currently_playing = service.get_currently_playing().await;
+                if let Ok(currently_playing) = &currently_playing {
+                    // Changes are tracked from the first state that watchers
+                    // are sent, unless the poller has already seen one.
+                    let mut last_update = service.last_update.lock().unwrap();
+                    if last_update.is_none() {
+                        *last_update = Some((currently_playing.clone(), now()));
+                    }
+                }
+                currently_playing
+            })
+            .filter_map(|currently_playing| async move {
+                match currently_playing {
+                    Ok(currently_playing) => Some(currently_playing),
+                    Err(error) => {
+                        error!(
+                            error = %format!("{:#}", error),
+                            "failed to get currently playing track"
+                        );
+                        None
+                    }
+                }
+            })
+        };
+        let updates = BroadcastStream::new(updates).filter_map(
+            |currently_playing| async move {
+                match currently_playing {
+                    Ok(currently_playing) => Some(currently_playing),
+                    Err(error) => {
+                        warn!(%error, "missed currently playing updates");
+                        None
+                    }
+                }
+            },
+        );
+        initial.chain(updates)
+    }
+
+    /// Poll the currently playing track, and publish changes to watchers
+    /// until there are none left.
+    async fn poll_currently_playing(self) {
+        let mut ticks = tick_interval(self.watch_interval);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            {
+                let mut is_polling = self.is_polling.lock().unwrap();
+                if self.updates.receiver_count() == 0 {
+                    *is_polling = false;
+
+                    // The next poller can't tell what changed while nobody
+                    // was watching, so start over from its first state.
+                    *self.last_update.lock().unwrap() = None;
+                    trace!("stopped polling currently-playing");
+                    return;
+                }
+            }
+
+            let currently_playing = match self.get_currently_playing().await {
+                Ok(currently_playing) => currently_playing,
+                Err(error) => {
+                    error!(
+                        error = %format!("{:#}", error),
+                        "failed to poll currently playing track"
+                    );
+                    continue;
+                }
+            };
+            let polled_at = now();
+
+            // Notify watchers of changes since the last state they know of,
+            // which may predate this poller; it's fine if there are none.
+            let previous = self
+                .last_update
+                .lock()
+                .unwrap()
+                .replace((currently_playing.clone(), polled_at));
+            if let Some((previous, previous_polled_at)) = &previous {
+                let elapsed = polled_at - *previous_polled_at;
+                let is_continuation = match (previous, &currently_playing) {
+                    (Some(previous), Some(current)) => {
+                        current.continues(previous, elapsed)
+                    }
+                    (None, None) => true,
+                    _ => false,
+                };
+                if !is_continuation {
+                    self.updates.send(currently_playing).ok();
+                }
+            }
+        }
+    }
 }
 
+impl Service {
+    /// Get the user's most played tracks over the given time range, most
+    /// played first.
+    pub async fn get_top_tracks(&self, range: TimeRange) -> Result<Vec<Track>> {
+        let Service {
+            top_tracks_cache: cache,
+            top_tracks_sem: sem,
+            ..
+        } = self;
+
+        // Acquire permit.
+        let _permit = sem.acquire().await.unwrap();
+
+        // Try to load top tracks from cache.
+        if let Some(tracks) = cache.get(&range) {
+            trace!(range = range.as_str(), "got top tracks from cache");
+            return Ok(tracks);
+        }
+
+        // Fetch new top tracks.
+        let tracks = self
+            .get_top_items::<Track>("tracks", range)
+            .await
+            .context("failed to get top tracks")?;
+        debug!(
+            range = range.as_str(),
+            count = tracks.len(),
+            "got top tracks"
+        );
+        cache.insert(range, tracks.clone()).await;
+        Ok(tracks)
+    }
+
+    /// Get the user's most played artists over the given time range, most
+    /// played first.
+    pub async fn get_top_artists(
+        &self,
+        range: TimeRange,
+    ) -> Result<Vec<Artist>> {
+        let Service {
+            top_artists_cache: cache,
+            top_artists_sem: sem,
+            ..
+        } = self;
+
+        // Acquire permit.
+        let _permit = sem.acquire().await.unwrap();
+
+        // Try to load top artists from cache.
+        if let Some(artists) = cache.get(&range) {
+            trace!(range = range.as_str(), "got top artists from cache");
+            return Ok(artists);
+        }
+
+        // Fetch new top artists.
+        let artists = self
+            .get_top_items::<Artist>("artists", range)
+            .await
+            .context("failed to get top artists")?;
+        debug!(
+            range = range.as_str(),
+            count = artists.len(),
+            "got top artists",
+        );
+        cache.insert(range, artists.clone()).await;
+        Ok(artists)
+    }
+
+    async fn get_top_items<T>(
+        &self,
+        kind: &str,
+        range: TimeRange,
+    ) -> Result<Vec<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let url = {
+            let mut url = self
+                .api_base_url
+                .join(&format!("me/top/{}", kind))
+                .context("failed to build URL")?;
+            {
+                let mut query = url.query_pairs_mut();
+                query.append_pair("time_range", range.as_str());
+                query.append_pair("limit", &PAGE_LIMIT.to_string());
+            }
+            url
+        };
+        let Page { items, .. } = self.get_page(url).await?;
+        Ok(items)
+    }
+
+    /// Get the public playlists that the user owns, excluding those that
+    /// they only follow.
+    pub async fn get_playlists(&self) -> Result<Vec<Playlist>> {
+        let Service {
+            api_base_url,
+            playlists_cache: cache,
+            playlists_sem: sem,
+            ..
+        } = self;
+
+        // Acquire permit.
+        let _permit = sem.acquire().await.unwrap();
+
+        // Try to load playlists from cache.
+        if let Some(playlists) = cache.get(&PlaylistsKey) {
+            trace!("got playlists from cache");
+            return Ok(playlists);
+        }
+
+        // Fetch new playlists, a page at a time.
+        let user_id = self.get_user_id().await?;
+        let mut playlists = Vec::<Playlist>::new();
+        let mut url = {
+            let mut url = api_base_url
+                .join("me/playlists")
+                .context("failed to build URL")?;
+            url.query_pairs_mut()
+                .append_pair("limit", &PAGE_LIMIT.to_string());
+            Some(url)
+        };
+        while let Some(next_url) = url {
+            let Page { items, next } = self
+                .get_page(next_url)
+                .await
+                .context("failed to get playlists")?;
+            playlists.extend(items);
+            url = next;
+        }
+        playlists.retain(|playlist| {
+            playlist.public == Some(true) && playlist.owner.id == user_id
+        });
+        debug!(count = playlists.len(), "got playlists");
+        cache.insert(PlaylistsKey, playlists.clone()).await;
+        Ok(playlists)
+    }
+
+    /// Get the ID of the user that the service is authorized for.
+    async fn get_user_id(&self) -> Result<String> {
+        let url = self
+            .api_base_url
+            .join("me")
+            .context("failed to build URL")?;
+        let response = {
+            let response = self.send(self.client.get(url)).await?;
+            response.error_for_status().context("bad status")?
+        };
+        let User { id } = response
+            .json()
+            .await
+            .context("failed to decode JSON response")?;
+        Ok(id)
+    }
+
+    async fn get_page<T>(&self, url: Url) -> Result<Page<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let response = {
+            let response = self.send(self.client.get(url)).await?;
+            response.error_for_status().context("bad status")?
+        };
+        let page: Page<T> = response
+            .json()
+            .await
+            .context("failed to decode JSON response")?;
+        Ok(page)
+    }
+}
+
+/// The time range over which top tracks and artists are calculated.
+#[derive(Debug, Clone, Copy, Hash, PartialOrd, Ord, PartialEq, Eq)]
+pub enum TimeRange {
+    /// Approximately the last 4 weeks.
+    ShortTerm,
+
+    /// Approximately the last 6 months.
+    MediumTerm,
+
+    /// Several years of data, including new data as it becomes available.
+    LongTerm,
+}
+
+impl TimeRange {
+    pub fn as_str(&self) -> &'static str {
+        use TimeRange::*;
+        match self {
+            ShortTerm => "short_term",
+            MediumTerm => "medium_term",
+            LongTerm => "long_term",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Page<T> {
+    items: Vec<T>,
+    next: Option<Url>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct User {
+    id: String,
+}
+
+/// The playback state of the Spotify player.
 #[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "CurrentlyPlayingResponse")]
 pub struct CurrentlyPlaying {
     pub is_playing: bool,
 
-    #[serde(alias = "item")]
-    pub track: Track,
+    /// The track or episode that is playing, which is missing while an ad
+    /// (or something else unknown) is playing.
+    pub item: Option<PlayingItem>,
 
-    #[serde(alias = "progress_ms")]
+    /// The position in the item, in milliseconds.
     pub progress: u32,
+
+    pub device: Option<Device>,
+    pub shuffle: bool,
+    pub repeat: RepeatMode,
+
+    /// The playlist, album, artist, or show that the item is playing from.
+    pub context: Option<PlaybackContext>,
+}
+
+impl CurrentlyPlaying {
+    /// The track that is playing, if a track (rather than an episode or an
+    /// ad) is playing.
+    pub fn track(&self) -> Option<&Track> {
+        match &self.item {
+            Some(PlayingItem::Track(track)) => Some(track),
+            _ => None,
+        }
+    }
+
+    /// Whether this follows on from `previous`, some time later, without the
+    /// item changing, being paused or resumed, its progress jumping, or the
+    /// player's settings changing.
+    fn continues(
+        &self,
+        previous: &CurrentlyPlaying,
+        elapsed: Duration,
+    ) -> bool {
+        let item_id = self.item.as_ref().map(PlayingItem::id);
+        let previous_item_id = previous.item.as_ref().map(PlayingItem::id);
+        let device_id = self.device.as_ref().map(|device| &device.id);
+        let previous_device_id =
+            previous.device.as_ref().map(|device| &device.id);
+        let context_uri = self.context.as_ref().map(|context| &context.uri);
+        let previous_context_uri =
+            previous.context.as_ref().map(|context| &context.uri);
+        if item_id != previous_item_id
+            || self.is_playing != previous.is_playing
+            || device_id != previous_device_id
+            || self.shuffle != previous.shuffle
+            || self.repeat != previous.repeat
+            || context_uri != previous_context_uri
+        {
+            return false;
+        }
+        let expected_progress = if previous.is_playing {
+            i64::from(previous.progress) + elapsed.num_milliseconds()
+        } else {
+            i64::from(previous.progress)
+        };
+        let drift = i64::from(self.progress) - expected_progress;
+        drift.abs() <= PROGRESS_JUMP_THRESHOLD
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CurrentlyPlayingResponse {
+    is_playing: bool,
+    currently_playing_type: String,
+    item: Option<Json>,
+    progress_ms: Option<u32>,
+    device: Option<Device>,
+
+    #[serde(default)]
+    shuffle_state: bool,
+
+    #[serde(default)]
+    repeat_state: RepeatMode,
+
+    context: Option<PlaybackContext>,
+}
+
+impl TryFrom<CurrentlyPlayingResponse> for CurrentlyPlaying {
+    type Error = Error;
+
+    fn try_from(response: CurrentlyPlayingResponse) -> Result<Self> {
+        let CurrentlyPlayingResponse {
+            is_playing,
+            currently_playing_type,
+            item,
+            progress_ms,
+            device,
+            shuffle_state,
+            repeat_state,
+            context,
+        } = response;
+        let item = match (currently_playing_type.as_str(), item) {
+            ("track", Some(item)) => {
+                let track = from_json(item).context("invalid track")?;
+                Some(PlayingItem::Track(track))
+            }
+            ("episode", Some(item)) => {
+                let episode = from_json(item).context("invalid episode")?;
+                Some(PlayingItem::Episode(episode))
+            }
+            _ => None,
+        };
+        let currently_playing = CurrentlyPlaying {
+            is_playing,
+            item,
+            progress: progress_ms.unwrap_or_default(),
+            device,
+            shuffle: shuffle_state,
+            repeat: repeat_state,
+            context,
+        };
+        Ok(currently_playing)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum PlayingItem {
+    Track(Track),
+    Episode(Episode),
+}
+
+impl PlayingItem {
+    pub fn id(&self) -> &str {
+        match self {
+            PlayingItem::Track(track) => &track.id,
+            PlayingItem::Episode(episode) => &episode.id,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            PlayingItem::Track(track) => &track.name,
+            PlayingItem::Episode(episode) => &episode.name,
+        }
+    }
+
+    /// The name of the item's (first) artist, or of the show that it's an
+    /// episode of.
+    pub fn creator(&self) -> &str {
+        match self {
+            PlayingItem::Track(track) => track
+                .artists
+                .first()
+                .map(|artist| artist.name.as_str())
+                .unwrap_or_default(),
+            PlayingItem::Episode(episode) => &episode.show.name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Device {
+    pub id: Option<String>,
+    pub name: String,
+
+    /// The kind of device (i.e. `Computer` or `Smartphone`).
+    #[serde(alias = "type")]
+    pub kind: String,
+
+    #[serde(alias = "volume_percent")]
+    pub volume: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RepeatMode {
+    Off,
+    Track,
+    Context,
+}
+
+impl Default for RepeatMode {
+    fn default() -> Self {
+        Self::Off
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlaybackContext {
+    /// The kind of context (i.e. `playlist` or `album`).
+    #[serde(alias = "type")]
+    pub kind: String,
+
+    pub uri: String,
+    pub external_urls: Option<ExternalURLs>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -193,9 +888,106 @@ pub struct Artist {
     pub name: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct Episode {
+    pub id: String,
+    pub external_urls: ExternalURLs,
+    pub name: String,
+    pub description: String,
+    #[serde(alias = "duration_ms")]
+    pub duration: u32,
+    pub images: Vec<Image>,
+    pub show: Show,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Show {
+    pub id: String,
+    pub external_urls: ExternalURLs,
+    pub name: String,
+    pub publisher: String,
+    pub images: Vec<Image>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Image {
     pub url: String,
-    pub width: u32,
-    pub height: u32,
+
+    /// The size of the image, which is unknown for some playlist images,
+    /// and isn't recorded with plays.
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Playlist {
+    pub id: String,
+    pub external_urls: ExternalURLs,
+    pub name: String,
+
+    /// The playlist's description, which may contain HTML.
+    pub description: Option<String>,
+
+    /// Whether the playlist is public, which is unknown for collaborative
+    /// playlists.
+    pub public: Option<bool>,
+
+    pub images: Vec<Image>,
+    pub owner: PlaylistOwner,
+    pub tracks: PlaylistTracks,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlaylistOwner {
+    pub id: String,
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlaylistTracks {
+    pub total: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn playing(is_playing: bool, progress: u32) -> CurrentlyPlaying {
+        CurrentlyPlaying {
+            is_playing,
+            item: None,
+            progress,
+            device: Some(Device {
+                id: Some("device".to_owned()),
+                name: "Computer".to_owned(),
+                kind: "Computer".to_owned(),
+                volume: None,
+            }),
+            shuffle: false,
+            repeat: RepeatMode::Off,
+            context: None,
+        }
+    }
+
+    #[test]
+    fn continues_playback() {
+        let elapsed = Duration::seconds(10);
+        let previous = playing(true, 20_000);
+        assert!(playing(true, 30_000).continues(&previous, elapsed));
+        assert!(playing(true, 32_000).continues(&previous, elapsed));
+        assert!(!playing(true, 40_000).continues(&previous, elapsed));
+        assert!(!playing(true, 20_000).continues(&previous, elapsed));
+        assert!(!playing(false, 30_000).continues(&previous, elapsed));
+
+        let paused = playing(false, 20_000);
+        assert!(playing(false, 20_000).continues(&paused, elapsed));
+        assert!(!playing(false, 30_000).continues(&paused, elapsed));
+
+        let mut shuffled = playing(true, 30_000);
+        shuffled.shuffle = true;
+        assert!(!shuffled.continues(&previous, elapsed));
+        let mut moved = playing(true, 30_000);
+        moved.device = None;
+        assert!(!moved.continues(&previous, elapsed));
+    }
 }
@@ -0,0 +1,104 @@
+use api::services::{LyriclyService, LyriclyServiceConfig};
+
+use serde_json::json;
+
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn service(server: &MockServer) -> LyriclyService {
+    let base_url = format!("{}/api/", server.uri());
+    LyriclyService::new({
+        LyriclyServiceConfig::builder()
+            .base_url(base_url.parse().unwrap())
+            .build()
+    })
+}
+
+#[tokio::test]
+async fn gets_lyrics() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/lyrics"))
+        .and(query_param("title", "Never Gonna Give You Up"))
+        .and(query_param("artist", "Rick Astley"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "synced": [
+                {
+                    "line": "We're no strangers to love",
+                    "milliseconds": 18800,
+                },
+                {
+                    "line": "You know the rules and so do I",
+                    "milliseconds": 22800,
+                },
+            ],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let lyricly = service(&server);
+    for _ in 0..2 {
+        let lyrics = lyricly
+            .get_lyrics("Never Gonna Give You Up", "Rick Astley")
+            .await
+            .unwrap()
+            .expect("missing lyrics");
+        let lines = lyrics.lines.expect("missing lines");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].text, "We're no strangers to love");
+        assert_eq!(lines[0].position, 18800);
+        assert_eq!(lines[1].position, 22800);
+    }
+}
+
+#[tokio::test]
+async fn gets_unsynced_lyrics() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/lyrics"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .mount(&server)
+        .await;
+
+    let lyricly = service(&server);
+    let lyrics = lyricly
+        .get_lyrics("Never Gonna Give You Up", "Rick Astley")
+        .await
+        .unwrap()
+        .expect("missing lyrics");
+    assert!(lyrics.lines.is_none());
+}
+
+#[tokio::test]
+async fn gets_missing_lyrics() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/lyrics"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let lyricly = service(&server);
+    let lyrics = lyricly
+        .get_lyrics("Never Gonna Give You Up", "Rick Astley")
+        .await
+        .unwrap();
+    assert!(lyrics.is_none());
+}
+
+#[tokio::test]
+async fn fails_on_bad_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/lyrics"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let lyricly = service(&server);
+    let result = lyricly
+        .get_lyrics("Never Gonna Give You Up", "Rick Astley")
+        .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,358 @@
+use api::services::lyrics::{parse_lrc, LrcProvider, Lyrics, LyricsProvider};
+use api::services::lyrics::{LyricsKey, LyricsStore, StoredLyrics};
+use api::services::lyrics::{ProviderKind, ServiceConfig};
+use api::services::{LyriclyServiceConfig, LyricsService};
+
+use std::collections::HashMap as Map;
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::path::PathBuf;
+use std::process;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use futures_util::future::join_all;
+use serde_json::json;
+
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const LRC: &str = "\
+[ar:Rick Astley]
+[ti:Never Gonna Give You Up]
+[offset:+200]
+
+[00:22.80]You know the rules and so do I
+[00:18.80][00:43.5]We're no <00:19.50>strangers to love
+";
+
+/// Create an empty directory for `.lrc` files, unique to the test.
+fn lrc_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "api-lyrics-{}-{}",
+        process::id(),
+        name
+    ));
+    remove_dir_all(&dir).ok();
+    create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn synced_lines(lyrics: Option<Lyrics>) -> Vec<(String, u32)> {
+    match lyrics.expect("missing lyrics") {
+        Lyrics::Synced(lines) => lines
+            .into_iter()
+            .map(|line| (line.text, line.position))
+            .collect(),
+        Lyrics::Plain(_) => panic!("lyrics are not synced"),
+    }
+}
+
+#[test]
+fn parses_synced_lrc() {
+    let lines = synced_lines(parse_lrc(LRC));
+    assert_eq!(
+        lines,
+        [
+            ("We're no strangers to love".to_owned(), 18600),
+            ("You know the rules and so do I".to_owned(), 22600),
+            ("We're no strangers to love".to_owned(), 43300),
+        ]
+    );
+}
+
+#[test]
+fn skips_invalid_lrc_lines() {
+    let text = "[99999999999:00.00] Too late\n\
+                [71583:00.00] Still too late\n\
+                [00:18.80] We're no strangers to love\n";
+    let lines = synced_lines(parse_lrc(text));
+    assert_eq!(lines, [("We're no strangers to love".to_owned(), 18800)]);
+}
+
+#[test]
+fn parses_plain_lrc() {
+    let lyrics = parse_lrc("[ar:Rick Astley]\nLine one\nLine two\n");
+    match lyrics.expect("missing lyrics") {
+        Lyrics::Plain(text) => assert_eq!(text, "Line one\nLine two"),
+        Lyrics::Synced(_) => panic!("lyrics are synced"),
+    }
+    assert!(parse_lrc("[ar:Rick Astley]\n\n").is_none());
+}
+
+#[tokio::test]
+async fn reads_lrc_files() {
+    let dir = lrc_dir("reads");
+    write(dir.join("Rick Astley - Never Gonna Give You Up.lrc"), LRC).unwrap();
+    write(dir.join("Rick Astley - Together Forever.txt"), LRC).unwrap();
+
+    let provider = LrcProvider::new(dir.to_str().unwrap()).unwrap();
+    let lyrics = provider
+        .get_lyrics("never gonna  give you up", "RICK ASTLEY")
+        .await
+        .unwrap();
+    assert_eq!(synced_lines(lyrics).len(), 3);
+    let lyrics = provider
+        .get_lyrics("Together Forever", "Rick Astley")
+        .await
+        .unwrap();
+    assert!(lyrics.is_none());
+    remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn falls_back_to_next_provider() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/lyrics"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "synced": [
+                {
+                    "line": "There's a brick wall",
+                    "milliseconds": 1000,
+                },
+            ],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = lrc_dir("falls-back");
+    write(dir.join("Rick Astley - Never Gonna Give You Up.lrc"), LRC).unwrap();
+    let lyrics = LyricsService::new({
+        let base_url = format!("{}/api/", server.uri());
+        ServiceConfig::builder()
+            .providers(vec![ProviderKind::Lrc, ProviderKind::Lyricly])
+            .lyricly({
+                LyriclyServiceConfig::builder()
+                    .base_url(base_url.parse().unwrap())
+                    .build()
+            })
+            .lrc_dir(dir.to_str().unwrap().to_owned())
+            .build()
+    })
+    .unwrap();
+
+    // Found in the directory, so Lyricly isn't asked.
+    let found = lyrics
+        .get_lyrics(&key("Never Gonna Give You Up"))
+        .await
+        .unwrap();
+    assert_eq!(synced_lines(found).len(), 3);
+
+    // Missing from the directory, so Lyricly is asked.
+    let found = lyrics.get_lyrics(&key("Together Forever")).await.unwrap();
+    assert_eq!(
+        synced_lines(found),
+        [("There's a brick wall".to_owned(), 1000)]
+    );
+    remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn fails_when_every_provider_fails() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/lyrics"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let lyrics = LyricsService::new({
+        let base_url = format!("{}/api/", server.uri());
+        ServiceConfig::builder()
+            .lyricly({
+                LyriclyServiceConfig::builder()
+                    .base_url(base_url.parse().unwrap())
+                    .build()
+            })
+            .build()
+    })
+    .unwrap();
+    let result = lyrics.get_lyrics(&key("Never Gonna Give You Up")).await;
+    assert!(result.is_err());
+}
+
+/// Keeps lyrics in memory, in place of a database.
+#[derive(Debug, Default)]
+struct MemoryLyricsStore {
+    lyrics: Mutex<Map<String, StoredLyrics>>,
+    loads: Mutex<usize>,
+    saves: Mutex<usize>,
+}
+
+impl MemoryLyricsStore {
+    fn get(&self, key: &LyricsKey) -> Option<StoredLyrics> {
+        let lyrics = self.lyrics.lock().unwrap();
+        let id = key.spotify_track_id.as_ref();
+        id.and_then(|id| lyrics.get(id))
+            .or_else(|| lyrics.get(&key.track_name))
+            .cloned()
+    }
+}
+
+#[async_trait]
+impl LyricsStore for MemoryLyricsStore {
+    async fn load(&self, key: &LyricsKey) -> Result<Option<StoredLyrics>> {
+        *self.loads.lock().unwrap() += 1;
+        Ok(self.get(key))
+    }
+
+    async fn save(&self, key: &LyricsKey, lyrics: StoredLyrics) -> Result<()> {
+        *self.saves.lock().unwrap() += 1;
+        let id = key.spotify_track_id.as_ref().unwrap_or(&key.track_name);
+        self.lyrics.lock().unwrap().insert(id.to_owned(), lyrics);
+        Ok(())
+    }
+}
+
+fn key(track_name: &str) -> LyricsKey {
+    LyricsKey::builder()
+        .track_name(track_name.to_owned())
+        .artist_name("Rick Astley".to_owned())
+        .build()
+}
+
+fn key_with_id(track_name: &str, spotify_track_id: &str) -> LyricsKey {
+    LyricsKey::builder()
+        .spotify_track_id(spotify_track_id.to_owned())
+        .track_name(track_name.to_owned())
+        .artist_name("Rick Astley".to_owned())
+        .build()
+}
+
+/// Build a lyrics service backed by Lyricly (at `server`) and `store`.
+fn stored_service(
+    server: &MockServer,
+    store: Arc<MemoryLyricsStore>,
+    not_found_ttl: Duration,
+) -> LyricsService {
+    let lyrics = LyricsService::new({
+        let base_url = format!("{}/api/", server.uri());
+        ServiceConfig::builder()
+            .lyricly({
+                LyriclyServiceConfig::builder()
+                    .base_url(base_url.parse().unwrap())
+                    .build()
+            })
+            .not_found_ttl(not_found_ttl)
+            .build()
+    })
+    .unwrap();
+    lyrics.set_store(store);
+    lyrics
+}
+
+#[tokio::test]
+async fn stores_lyrics() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/lyrics"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "synced": [
+                {
+                    "line": "We're no strangers to love",
+                    "milliseconds": 18800,
+                },
+            ],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Each service has its own in-memory cache, so the second lookup can
+    // only be answered by the store.
+    let store = Arc::new(MemoryLyricsStore::default());
+    let key = key_with_id("Never Gonna Give You Up", "4cOdK2wGLETKBW3PvgPWqT");
+    for _ in 0..2 {
+        let lyrics = stored_service(&server, store.clone(), Duration::days(1));
+        let found = lyrics.get_lyrics(&key).await.unwrap();
+        assert_eq!(synced_lines(found).len(), 1);
+    }
+    let stored = store.get(&key).expect("missing stored lyrics");
+    let expires_at = stored.expires_at.expect("missing expiry");
+    assert!(expires_at > Utc::now() + Duration::days(29));
+}
+
+#[tokio::test]
+async fn shares_concurrent_lookups() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/lyrics"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "synced": [
+                {
+                    "line": "We're no strangers to love",
+                    "milliseconds": 18800,
+                },
+            ],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Concurrent lookups are answered by a single load, which is stored
+    // once and then served from memory.
+    let store = Arc::new(MemoryLyricsStore::default());
+    let lyrics = stored_service(&server, store.clone(), Duration::days(1));
+    let key = key_with_id("Never Gonna Give You Up", "4cOdK2wGLETKBW3PvgPWqT");
+    let lookups = (0..4).map(|_| lyrics.get_lyrics(&key));
+    for found in join_all(lookups).await {
+        assert_eq!(synced_lines(found.unwrap()).len(), 1);
+    }
+    let found = lyrics.get_lyrics(&key).await.unwrap();
+    assert_eq!(synced_lines(found).len(), 1);
+    assert_eq!(*store.loads.lock().unwrap(), 1);
+    assert_eq!(*store.saves.lock().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn stores_missing_lyrics() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/lyrics"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    // Missing lyrics are remembered until they expire...
+    let store = Arc::new(MemoryLyricsStore::default());
+    let key = key_with_id("Together Forever", "3Z1rL5pzAa2RwlZUvOFRX2");
+    for _ in 0..2 {
+        let lyrics = stored_service(&server, store.clone(), Duration::days(1));
+        assert!(lyrics.get_lyrics(&key).await.unwrap().is_none());
+    }
+
+    // ...and then looked up again.
+    let store = Arc::new(MemoryLyricsStore::default());
+    for _ in 0..2 {
+        let lyrics = stored_service(&server, store.clone(), Duration::zero());
+        assert!(lyrics.get_lyrics(&key).await.unwrap().is_none());
+    }
+}
+
+#[tokio::test]
+async fn overrides_lyrics() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/lyrics"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    // Lyrics set by name are found for tracks with that name.
+    let store = Arc::new(MemoryLyricsStore::default());
+    let lyrics = stored_service(&server, store.clone(), Duration::days(1));
+    lyrics
+        .set_lyrics(&key("Never Gonna Give You Up"), parse_lrc(LRC))
+        .await
+        .unwrap();
+    let key = key_with_id("Never Gonna Give You Up", "4cOdK2wGLETKBW3PvgPWqT");
+    let found = lyrics.get_lyrics(&key).await.unwrap();
+    assert_eq!(synced_lines(found).len(), 3);
+    let stored = store.get(&key).expect("missing stored lyrics");
+    assert!(stored.expires_at.is_none());
+}
@@ -0,0 +1,339 @@
+use api::entities::MusicPlay;
+use api::pollers::{MusicPlayPoller, MusicPlayPollerConfig, PlayStore};
+use api::services::{SpotifyService, SpotifyServiceConfig};
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration as StdDuration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use chrono::{Duration, Utc};
+use serde_json::json;
+use serde_json::Value as Json;
+use tokio::time::sleep;
+
+use wiremock::matchers::{body_string_contains, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Keeps saved plays in memory, in the order they were first saved.
+#[derive(Debug, Default)]
+struct MemoryPlayStore {
+    plays: Mutex<Vec<MusicPlay>>,
+    saves: Mutex<usize>,
+}
+
+impl MemoryPlayStore {
+    fn plays(&self) -> Vec<MusicPlay> {
+        self.plays.lock().unwrap().clone()
+    }
+
+    fn saves(&self) -> usize {
+        *self.saves.lock().unwrap()
+    }
+}
+
+#[async_trait]
+impl PlayStore for MemoryPlayStore {
+    async fn save(&self, play: &MusicPlay) -> Result<()> {
+        let mut plays = self.plays.lock().unwrap();
+        match plays.iter_mut().find(|saved| saved.id == play.id) {
+            Some(saved) => *saved = play.clone(),
+            None => plays.push(play.clone()),
+        }
+        *self.saves.lock().unwrap() += 1;
+        Ok(())
+    }
+}
+
+fn track_json(id: &str) -> Json {
+    json!({
+        "id": id,
+        "external_urls": {
+            "spotify": format!("https://open.spotify.com/track/{}", id)
+        },
+        "name": "Never Gonna Give You Up",
+        "duration_ms": 213573,
+        "album": {
+            "id": "6XhjNHCyCDyyGJRM5mg40G",
+            "external_urls": {
+                "spotify": "https://open.spotify.com/album/6XhjNHCyCDyyGJRM5mg40G"
+            },
+            "name": "Whenever You Need Somebody",
+            "images": [
+                {
+                    "url": "https://i.scdn.co/image/ab67616d0000b273",
+                    "width": 640,
+                    "height": 640
+                }
+            ]
+        },
+        "artists": [
+            {
+                "id": "0gxyHStUsqpMadRV0Di1Qt",
+                "external_urls": {
+                    "spotify": "https://open.spotify.com/artist/0gxyHStUsqpMadRV0Di1Qt"
+                },
+                "name": "Rick Astley"
+            }
+        ]
+    })
+}
+
+fn episode_json() -> Json {
+    json!({
+        "id": "512ojhOuo1ktJprKbVcKyQ",
+        "external_urls": {
+            "spotify": "https://open.spotify.com/episode/512ojhOuo1ktJprKbVcKyQ"
+        },
+        "name": "Episode 1",
+        "description": "The first episode.",
+        "duration_ms": 1686230,
+        "images": [],
+        "show": {
+            "id": "38bS44xjbVVZ3No3ByF1dJ",
+            "external_urls": {
+                "spotify": "https://open.spotify.com/show/38bS44xjbVVZ3No3ByF1dJ"
+            },
+            "name": "The Show",
+            "publisher": "The Publisher",
+            "images": []
+        }
+    })
+}
+
+fn track_playing(id: &str, progress: u32, is_playing: bool) -> Json {
+    json!({
+        "is_playing": is_playing,
+        "currently_playing_type": "track",
+        "item": track_json(id),
+        "progress_ms": progress,
+    })
+}
+
+/// Start a stand-in for the Spotify Accounts service and Web API, with an
+/// initial playback state.
+async fn start_server(playing: Json) -> MockServer {
+    let server = MockServer::start().await;
+    mount_playing(&server, playing).await;
+    server
+}
+
+/// Replace the playback state that the stand-in responds with.
+async fn set_playing(server: &MockServer, playing: Json) {
+    server.reset().await;
+    mount_playing(server, playing).await;
+
+    // Wait for the cache to apply its pending writes, so that the previous
+    // playback state expires.
+    sleep(StdDuration::from_millis(700)).await;
+}
+
+async fn mount_playing(server: &MockServer, playing: Json) {
+    Mock::given(method("POST"))
+        .and(path("/api/token"))
+        .and(body_string_contains("grant_type=refresh_token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "access-token",
+            "token_type": "Bearer",
+            "expires_in": 3600,
+        })))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(playing))
+        .mount(server)
+        .await;
+}
+
+fn service(server: &MockServer) -> SpotifyService {
+    let base_url = server.uri();
+    SpotifyService::new({
+        SpotifyServiceConfig::builder()
+            .client_id("client-id".to_owned())
+            .client_secret("client-secret".to_owned())
+            .refresh_token("refresh-token".to_owned())
+            .api_base_url(format!("{}/v1/", base_url).parse().unwrap())
+            .accounts_base_url(format!("{}/", base_url).parse().unwrap())
+            .ttl(Duration::zero())
+            .build()
+    })
+    .unwrap()
+}
+
+fn poller(
+    server: &MockServer,
+    store: &Arc<MemoryPlayStore>,
+    save_interval: Duration,
+) -> MusicPlayPoller {
+    MusicPlayPoller::new({
+        MusicPlayPollerConfig::builder()
+            .spotify(service(server))
+            .store(store.clone())
+            .save_interval(save_interval)
+            .build()
+    })
+    .unwrap()
+}
+
+#[tokio::test]
+async fn records_new_play() {
+    let server = start_server(track_playing("track-1", 42000, true)).await;
+    let store = Arc::new(MemoryPlayStore::default());
+    let mut poller = poller(&server, &store, Duration::minutes(1));
+
+    let before = Utc::now();
+    poller.poll().await.unwrap();
+
+    let plays = store.plays();
+    assert_eq!(plays.len(), 1);
+    let play = &plays[0];
+    assert_eq!(play.track.id, "track-1");
+    assert_eq!(play.track.name, "Never Gonna Give You Up");
+    assert_eq!(play.track.duration, 213573);
+    assert_eq!(
+        play.track.album.image_url.as_deref(),
+        Some("https://i.scdn.co/image/ab67616d0000b273")
+    );
+    assert_eq!(play.track.artists[0].name, "Rick Astley");
+    assert_eq!(play.progress, 42000);
+    let started_at = before - Duration::milliseconds(42000);
+    assert!((play.started_at - started_at).num_seconds().abs() < 5);
+}
+
+#[tokio::test]
+async fn ignores_paused_track_without_play() {
+    let server = start_server(track_playing("track-1", 42000, false)).await;
+    let store = Arc::new(MemoryPlayStore::default());
+    let mut poller = poller(&server, &store, Duration::minutes(1));
+
+    poller.poll().await.unwrap();
+    assert!(store.plays().is_empty());
+}
+
+#[tokio::test]
+async fn continues_play() {
+    let server = start_server(track_playing("track-1", 42000, true)).await;
+    let store = Arc::new(MemoryPlayStore::default());
+    let mut poller = poller(&server, &store, Duration::minutes(1));
+
+    poller.poll().await.unwrap();
+    set_playing(&server, track_playing("track-1", 57000, true)).await;
+    poller.poll().await.unwrap();
+
+    // Progress isn't saved again until the save interval has passed.
+    assert_eq!(store.saves(), 1);
+    assert_eq!(store.plays()[0].progress, 42000);
+
+    // Finishing the play saves its final progress.
+    set_playing(&server, track_playing("track-2", 1000, true)).await;
+    poller.poll().await.unwrap();
+    let plays = store.plays();
+    assert_eq!(plays.len(), 2);
+    assert_eq!(plays[0].progress, 57000);
+    assert_eq!(plays[1].track.id, "track-2");
+}
+
+#[tokio::test]
+async fn saves_progress_periodically() {
+    let server = start_server(track_playing("track-1", 42000, true)).await;
+    let store = Arc::new(MemoryPlayStore::default());
+    let mut poller = poller(&server, &store, Duration::zero());
+
+    poller.poll().await.unwrap();
+    set_playing(&server, track_playing("track-1", 57000, true)).await;
+    poller.poll().await.unwrap();
+
+    let plays = store.plays();
+    assert_eq!(plays.len(), 1);
+    assert_eq!(plays[0].progress, 57000);
+    assert_eq!(store.saves(), 2);
+}
+
+#[tokio::test]
+async fn records_replay() {
+    let server = start_server(track_playing("track-1", 42000, true)).await;
+    let store = Arc::new(MemoryPlayStore::default());
+    let mut poller = poller(&server, &store, Duration::minutes(1));
+
+    poller.poll().await.unwrap();
+    set_playing(&server, track_playing("track-1", 3000, true)).await;
+    poller.poll().await.unwrap();
+
+    let plays = store.plays();
+    assert_eq!(plays.len(), 2);
+    assert_eq!(plays[0].progress, 42000);
+    assert_eq!(plays[1].track.id, "track-1");
+    assert_eq!(plays[1].progress, 3000);
+}
+
+#[tokio::test]
+async fn continues_rewound_play() {
+    let server = start_server(track_playing("track-1", 42000, true)).await;
+    let store = Arc::new(MemoryPlayStore::default());
+    let mut poller = poller(&server, &store, Duration::zero());
+
+    poller.poll().await.unwrap();
+    set_playing(&server, track_playing("track-1", 20000, true)).await;
+    poller.poll().await.unwrap();
+
+    let plays = store.plays();
+    assert_eq!(plays.len(), 1);
+    assert_eq!(plays[0].progress, 42000);
+}
+
+#[tokio::test]
+async fn finishes_play_on_episode() {
+    let server = start_server(track_playing("track-1", 42000, true)).await;
+    let store = Arc::new(MemoryPlayStore::default());
+    let mut poller = poller(&server, &store, Duration::minutes(1));
+
+    poller.poll().await.unwrap();
+    set_playing(&server, track_playing("track-1", 57000, true)).await;
+    poller.poll().await.unwrap();
+    set_playing(&server, {
+        json!({
+            "is_playing": true,
+            "currently_playing_type": "episode",
+            "item": episode_json(),
+            "progress_ms": 1000,
+        })
+    })
+    .await;
+    poller.poll().await.unwrap();
+
+    let plays = store.plays();
+    assert_eq!(plays.len(), 1);
+    assert_eq!(plays[0].progress, 57000);
+    assert_eq!(store.saves(), 2);
+
+    // The same track playing afterwards is a new play.
+    set_playing(&server, track_playing("track-1", 60000, true)).await;
+    poller.poll().await.unwrap();
+    assert_eq!(store.plays().len(), 2);
+}
+
+#[tokio::test]
+async fn finishes_play_on_ad() {
+    let server = start_server(track_playing("track-1", 42000, true)).await;
+    let store = Arc::new(MemoryPlayStore::default());
+    let mut poller = poller(&server, &store, Duration::minutes(1));
+
+    poller.poll().await.unwrap();
+    set_playing(&server, {
+        json!({
+            "is_playing": true,
+            "currently_playing_type": "ad",
+            "item": null,
+            "progress_ms": 1000,
+        })
+    })
+    .await;
+    poller.poll().await.unwrap();
+    assert_eq!(store.saves(), 2);
+
+    set_playing(&server, track_playing("track-1", 50000, true)).await;
+    poller.poll().await.unwrap();
+    assert_eq!(store.plays().len(), 2);
+}
@@ -0,0 +1,163 @@
+use api::services::obsidian::{NoteRevision, NoteUpdate};
+use api::services::{ObsidianService, ObsidianServiceConfig};
+
+use std::fs::{create_dir_all, remove_dir_all, remove_file, write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration as StdDuration;
+
+use futures_util::stream::StreamExt;
+use git2::{Repository, Signature};
+use tokio::time::timeout;
+
+/// Create an empty git repository, unique to the test.
+fn repo_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "api-obsidian-{}-{}",
+        process::id(),
+        name
+    ));
+    remove_dir_all(&dir).ok();
+    create_dir_all(&dir).unwrap();
+    Repository::init(&dir).unwrap();
+    dir
+}
+
+/// Write a file to a repository and commit it, returning the commit ID.
+fn commit_file(dir: &Path, path: &str, text: &str, message: &str) -> String {
+    let repo = Repository::open(dir).unwrap();
+    let file_path = dir.join(path);
+    create_dir_all(file_path.parent().unwrap()).unwrap();
+    write(&file_path, text).unwrap();
+
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new(path)).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = Signature::now("Test", "test@example.com").unwrap();
+    let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+    let parents = parent.iter().collect::<Vec<_>>();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )
+    .unwrap()
+    .to_string()
+}
+
+fn service(vault_path: &Path, history: bool) -> ObsidianService {
+    ObsidianService::new({
+        ObsidianServiceConfig::builder()
+            .vault_path(vault_path.to_str().unwrap().to_owned())
+            .history(history)
+            .build()
+    })
+    .unwrap()
+}
+
+#[tokio::test]
+async fn reads_note_history() {
+    let dir = repo_dir("history");
+    let first = commit_file(
+        &dir,
+        "vault/Note.md",
+        "---\ntags: [draft]\n---\nFirst line\n",
+        "Add note",
+    );
+    commit_file(&dir, "README.md", "Not a note\n", "Add readme");
+    let second = commit_file(
+        &dir,
+        "vault/Note.md",
+        "First line\nSecond line\n",
+        "Extend note\n\nWith a body.",
+    );
+
+    let obsidian = service(&dir.join("vault"), true);
+    let revisions = obsidian.get_note_revisions("Note").await.unwrap();
+    let revisions = revisions
+        .iter()
+        .map(|revision| {
+            let NoteRevision {
+                id,
+                message,
+                insertions,
+                deletions,
+                ..
+            } = revision;
+            (id.as_str(), message.as_str(), *insertions, *deletions)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        revisions,
+        [
+            (second.as_str(), "Extend note\n\nWith a body.", 1, 3),
+            (first.as_str(), "Add note", 4, 0),
+        ]
+    );
+
+    let content = obsidian.get_note_content_at("Note", &first).await.unwrap();
+    assert_eq!(content.as_deref(), Some("First line\n"));
+    let content = obsidian.get_note_content_at("Note", &second).await.unwrap();
+    assert_eq!(content.as_deref(), Some("First line\nSecond line\n"));
+    let content = obsidian
+        .get_note_content_at("Missing", &second)
+        .await
+        .unwrap();
+    assert!(content.is_none());
+    remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn ignores_history_when_disabled() {
+    let dir = repo_dir("disabled");
+    let id = commit_file(&dir, "Note.md", "First line\n", "Add note");
+
+    let obsidian = service(&dir, false);
+    let revisions = obsidian.get_note_revisions("Note").await.unwrap();
+    assert!(revisions.is_empty());
+    let content = obsidian.get_note_content_at("Note", &id).await.unwrap();
+    assert!(content.is_none());
+    remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+async fn watches_removed_notes() {
+    let dir = repo_dir("watch");
+    write(dir.join("Note.md"), "First line\n").unwrap();
+    write(dir.join("Secret.md"), "---\npublish: false\n---\nHidden\n").unwrap();
+
+    let obsidian = ObsidianService::new({
+        ObsidianServiceConfig::builder()
+            .vault_path(dir.to_str().unwrap().to_owned())
+            .watch(true)
+            .build()
+    })
+    .unwrap();
+    obsidian.list_notes().await.unwrap();
+    let updates = obsidian.watch_notes().unwrap();
+    futures_util::pin_mut!(updates);
+
+    let mut removed = Vec::new();
+    for name in ["Note", "Secret"] {
+        remove_file(dir.join(format!("{}.md", name))).unwrap();
+        let update = timeout(StdDuration::from_secs(10), updates.next())
+            .await
+            .expect("missing update")
+            .unwrap();
+        match update {
+            NoteUpdate::Removed { id, was_published } => {
+                removed.push((id, was_published))
+            }
+            update => panic!("unexpected update: {:?}", update),
+        }
+    }
+    assert_eq!(
+        removed,
+        [("Note".to_owned(), true), ("Secret".to_owned(), false)]
+    );
+    remove_dir_all(&dir).ok();
+}
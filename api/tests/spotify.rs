@@ -0,0 +1,842 @@
+use api::auth::{AccessToken, StoredTokens, TokenStore};
+use api::services::spotify::{PlayingItem, RepeatMode, TimeRange};
+use api::services::{SpotifyService, SpotifyServiceConfig};
+
+use std::collections::HashMap as Map;
+use std::sync::{Arc, Mutex};
+use std::time::Duration as StdDuration;
+
+use anyhow::{ensure, Result};
+use async_trait::async_trait;
+
+use chrono::{Duration, Utc};
+use futures_util::stream::StreamExt;
+use serde_json::json;
+use serde_json::Value as Json;
+use tokio::time::{sleep, timeout};
+use url::Url;
+
+use wiremock::matchers::{
+    body_string_contains, header, method, path, query_param,
+};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn track_json() -> Json {
+    json!({
+        "id": "4cOdK2wGLETKBW3PvgPWqT",
+        "external_urls": {
+            "spotify": "https://open.spotify.com/track/4cOdK2wGLETKBW3PvgPWqT"
+        },
+        "name": "Never Gonna Give You Up",
+        "duration_ms": 213573,
+        "album": {
+            "id": "6XhjNHCyCDyyGJRM5mg40G",
+            "external_urls": {
+                "spotify": "https://open.spotify.com/album/6XhjNHCyCDyyGJRM5mg40G"
+            },
+            "name": "Whenever You Need Somebody",
+            "images": [
+                {
+                    "url": "https://i.scdn.co/image/ab67616d0000b273",
+                    "width": 640,
+                    "height": 640
+                }
+            ]
+        },
+        "artists": [
+            {
+                "id": "0gxyHStUsqpMadRV0Di1Qt",
+                "external_urls": {
+                    "spotify": "https://open.spotify.com/artist/0gxyHStUsqpMadRV0Di1Qt"
+                },
+                "name": "Rick Astley"
+            }
+        ]
+    })
+}
+
+fn episode_json() -> Json {
+    json!({
+        "id": "512ojhOuo1ktJprKbVcKyQ",
+        "external_urls": {
+            "spotify": "https://open.spotify.com/episode/512ojhOuo1ktJprKbVcKyQ"
+        },
+        "name": "Episode 1",
+        "description": "The first episode.",
+        "duration_ms": 1686230,
+        "images": [],
+        "show": {
+            "id": "38bS44xjbVVZ3No3ByF1dJ",
+            "external_urls": {
+                "spotify": "https://open.spotify.com/show/38bS44xjbVVZ3No3ByF1dJ"
+            },
+            "name": "The Show",
+            "publisher": "The Publisher",
+            "images": []
+        }
+    })
+}
+
+fn device_json() -> Json {
+    json!({
+        "id": "5fbb3ba6aa454b5534c4ba43a8c7e8e45a63ad0e",
+        "name": "Kitchen speaker",
+        "type": "Speaker",
+        "volume_percent": 59,
+        "is_active": true
+    })
+}
+
+/// Start a stand-in for the Spotify Accounts service and Web API, which
+/// issues an access token in exchange for the refresh token.
+async fn start_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/token"))
+        .and(body_string_contains("grant_type=refresh_token"))
+        .and(body_string_contains("refresh_token=refresh-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "access-token",
+            "token_type": "Bearer",
+            "expires_in": 3600,
+        })))
+        .mount(&server)
+        .await;
+    server
+}
+
+fn service(server: &MockServer) -> SpotifyService {
+    let base_url = server.uri();
+    SpotifyService::new({
+        SpotifyServiceConfig::builder()
+            .client_id("client-id".to_owned())
+            .client_secret("client-secret".to_owned())
+            .refresh_token("refresh-token".to_owned())
+            .api_base_url(format!("{}/v1/", base_url).parse().unwrap())
+            .accounts_base_url(format!("{}/", base_url).parse().unwrap())
+            .build()
+    })
+    .unwrap()
+}
+
+#[tokio::test]
+async fn gets_currently_playing() {
+    let server = start_server().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .and(query_param("market", "CA"))
+        .and(header("authorization", "Bearer access-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "is_playing": true,
+            "currently_playing_type": "track",
+            "item": track_json(),
+            "progress_ms": 42000,
+            "device": device_json(),
+            "shuffle_state": true,
+            "repeat_state": "context",
+            "context": {
+                "type": "playlist",
+                "uri": "spotify:playlist:37i9dQZF1DXcBWIGoYBM5M",
+                "external_urls": {
+                    "spotify": "https://open.spotify.com/playlist/37i9dQZF1DXcBWIGoYBM5M"
+                }
+            },
+        })))
+        .mount(&server)
+        .await;
+
+    let spotify = service(&server);
+    let currently_playing = spotify.get_currently_playing().await.unwrap();
+    let currently_playing = currently_playing.expect("nothing playing");
+    assert!(currently_playing.is_playing);
+    assert_eq!(currently_playing.progress, 42000);
+
+    assert!(currently_playing.shuffle);
+    assert_eq!(currently_playing.repeat, RepeatMode::Context);
+
+    let device = currently_playing.device.as_ref().expect("missing device");
+    assert_eq!(device.name, "Kitchen speaker");
+    assert_eq!(device.kind, "Speaker");
+    assert_eq!(device.volume, Some(59));
+
+    let context = currently_playing.context.as_ref().expect("missing context");
+    assert_eq!(context.kind, "playlist");
+    assert_eq!(context.uri, "spotify:playlist:37i9dQZF1DXcBWIGoYBM5M");
+
+    let track = currently_playing.track().expect("missing track");
+    assert_eq!(track.id, "4cOdK2wGLETKBW3PvgPWqT");
+    assert_eq!(track.name, "Never Gonna Give You Up");
+    assert_eq!(track.duration, 213573);
+    assert_eq!(track.album.name, "Whenever You Need Somebody");
+    assert_eq!(track.artists[0].name, "Rick Astley");
+}
+
+#[tokio::test]
+async fn gets_episode_playing() {
+    let server = start_server().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .and(query_param("additional_types", "track,episode"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "is_playing": true,
+            "currently_playing_type": "episode",
+            "item": episode_json(),
+            "progress_ms": 1000,
+            "device": device_json(),
+            "shuffle_state": false,
+            "repeat_state": "off",
+            "context": null,
+        })))
+        .mount(&server)
+        .await;
+
+    let spotify = service(&server);
+    let currently_playing = spotify.get_currently_playing().await.unwrap();
+    let currently_playing = currently_playing.expect("nothing playing");
+    assert!(currently_playing.track().is_none());
+    assert!(currently_playing.context.is_none());
+    assert_eq!(currently_playing.repeat, RepeatMode::Off);
+
+    let episode = match currently_playing.item {
+        Some(PlayingItem::Episode(episode)) => episode,
+        item => panic!("expected episode, got {:?}", item),
+    };
+    assert_eq!(episode.name, "Episode 1");
+    assert_eq!(episode.duration, 1686230);
+    assert_eq!(episode.show.name, "The Show");
+}
+
+#[tokio::test]
+async fn gets_ad_playing() {
+    let server = start_server().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "is_playing": true,
+            "currently_playing_type": "ad",
+            "item": null,
+            "progress_ms": 5000,
+            "device": device_json(),
+            "shuffle_state": false,
+            "repeat_state": "off",
+            "context": null,
+        })))
+        .mount(&server)
+        .await;
+
+    let spotify = service(&server);
+    let currently_playing = spotify.get_currently_playing().await.unwrap();
+    let currently_playing = currently_playing.expect("nothing playing");
+    assert!(currently_playing.is_playing);
+    assert!(currently_playing.item.is_none());
+}
+
+#[tokio::test]
+async fn gets_nothing_playing() {
+    let server = start_server().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    let spotify = service(&server);
+    let currently_playing = spotify.get_currently_playing().await.unwrap();
+    assert!(currently_playing.is_none());
+}
+
+#[tokio::test]
+async fn caches_currently_playing() {
+    let server = start_server().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let spotify = service(&server);
+    for _ in 0..3 {
+        spotify.get_currently_playing().await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn reuses_access_token() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "access-token",
+            "token_type": "Bearer",
+            "expires_in": 3600,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let base_url = server.uri();
+    let spotify = SpotifyService::new({
+        SpotifyServiceConfig::builder()
+            .client_id("client-id".to_owned())
+            .client_secret("client-secret".to_owned())
+            .refresh_token("refresh-token".to_owned())
+            .api_base_url(format!("{}/v1/", base_url).parse().unwrap())
+            .accounts_base_url(format!("{}/", base_url).parse().unwrap())
+            .ttl(Duration::milliseconds(10))
+            .build()
+    })
+    .unwrap();
+    spotify.get_currently_playing().await.unwrap();
+
+    // Wait for the cached response to expire.
+    sleep(StdDuration::from_millis(1100)).await;
+    spotify.get_currently_playing().await.unwrap();
+}
+
+#[tokio::test]
+async fn watches_currently_playing() {
+    let server = start_server().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "is_playing": true,
+            "currently_playing_type": "track",
+            "item": track_json(),
+            "progress_ms": 42000,
+            "device": device_json(),
+            "shuffle_state": true,
+            "repeat_state": "context",
+            "context": {
+                "type": "playlist",
+                "uri": "spotify:playlist:37i9dQZF1DXcBWIGoYBM5M",
+                "external_urls": {
+                    "spotify": "https://open.spotify.com/playlist/37i9dQZF1DXcBWIGoYBM5M"
+                }
+            },
+        })))
+        .mount(&server)
+        .await;
+
+    let base_url = server.uri();
+    let spotify = SpotifyService::new({
+        SpotifyServiceConfig::builder()
+            .client_id("client-id".to_owned())
+            .client_secret("client-secret".to_owned())
+            .refresh_token("refresh-token".to_owned())
+            .api_base_url(format!("{}/v1/", base_url).parse().unwrap())
+            .accounts_base_url(format!("{}/", base_url).parse().unwrap())
+            .ttl(Duration::milliseconds(10))
+            .watch_interval(Duration::milliseconds(50))
+            .build()
+    })
+    .unwrap();
+    let updates = spotify.watch_currently_playing();
+    futures_util::pin_mut!(updates);
+
+    let currently_playing = updates.next().await.unwrap();
+    let currently_playing = currently_playing.expect("nothing playing");
+    assert!(currently_playing.is_playing);
+
+    // Pause the track.
+    server.reset().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "is_playing": false,
+            "currently_playing_type": "track",
+            "item": track_json(),
+            "progress_ms": 43000,
+        })))
+        .mount(&server)
+        .await;
+
+    let currently_playing = timeout(StdDuration::from_secs(5), updates.next())
+        .await
+        .expect("missing update")
+        .unwrap();
+    let currently_playing = currently_playing.expect("nothing playing");
+    assert!(!currently_playing.is_playing);
+    assert_eq!(currently_playing.progress, 43000);
+
+    // Nothing changes while the track is paused.
+    let update = timeout(StdDuration::from_millis(1500), updates.next()).await;
+    assert!(update.is_err(), "unexpected update");
+}
+
+#[tokio::test]
+async fn authorizes_with_code() {
+    let server = MockServer::start().await;
+    let spotify = SpotifyService::new({
+        let base_url = server.uri();
+        SpotifyServiceConfig::builder()
+            .client_id("client-id".to_owned())
+            .client_secret("client-secret".to_owned())
+            .redirect_url(
+                "http://localhost/auth/spotify/callback"
+                    .parse::<Url>()
+                    .unwrap(),
+            )
+            .api_base_url(format!("{}/v1/", base_url).parse().unwrap())
+            .accounts_base_url(format!("{}/", base_url).parse().unwrap())
+            .build()
+    })
+    .unwrap();
+
+    // Nothing can be fetched until the service is authorized.
+    assert!(spotify.get_currently_playing().await.is_err());
+
+    let request = spotify.authorize_url();
+    assert_eq!(request.url.path(), "/authorize");
+    let query = request.url.query_pairs().collect::<Vec<_>>();
+    let has_pair = |key: &str, value: &str| {
+        query.iter().any(|(k, v)| k == key && v == value)
+    };
+    assert!(has_pair("response_type", "code"));
+    assert!(has_pair("client_id", "client-id"));
+    assert!(has_pair("state", &request.state));
+    assert!(has_pair("code_challenge_method", "S256"));
+    assert!(has_pair(
+        "redirect_uri",
+        "http://localhost/auth/spotify/callback"
+    ));
+
+    Mock::given(method("POST"))
+        .and(path("/api/token"))
+        .and(body_string_contains("grant_type=authorization_code"))
+        .and(body_string_contains("code=authorization-code"))
+        .and(body_string_contains(format!(
+            "code_verifier={}",
+            request.verifier
+        )))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "access-token",
+            "token_type": "Bearer",
+            "expires_in": 3600,
+            "refresh_token": "refresh-token",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .and(header("authorization", "Bearer access-token"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    spotify
+        .authorize("authorization-code", &request.verifier)
+        .await
+        .unwrap();
+    spotify.get_currently_playing().await.unwrap();
+}
+
+#[tokio::test]
+async fn retries_with_refreshed_token() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "revoked-token",
+            "token_type": "Bearer",
+            "expires_in": 3600,
+        })))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "access-token",
+            "token_type": "Bearer",
+            "expires_in": 3600,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .and(header("authorization", "Bearer revoked-token"))
+        .respond_with(ResponseTemplate::new(401))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .and(header("authorization", "Bearer access-token"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let spotify = service(&server);
+    let currently_playing = spotify.get_currently_playing().await.unwrap();
+    assert!(currently_playing.is_none());
+}
+
+/// Keeps tokens in memory, in place of a database.
+#[derive(Debug, Default)]
+struct MemoryTokenStore {
+    tokens: Mutex<Map<String, StoredTokens>>,
+
+    /// Whether saving tokens should fail, as if the database were down.
+    fail_saves: bool,
+}
+
+impl MemoryTokenStore {
+    fn get(&self, provider: &str) -> Option<StoredTokens> {
+        self.tokens.lock().unwrap().get(provider).cloned()
+    }
+}
+
+#[async_trait]
+impl TokenStore for MemoryTokenStore {
+    async fn load(&self, provider: &str) -> Result<Option<StoredTokens>> {
+        Ok(self.get(provider))
+    }
+
+    async fn save(&self, provider: &str, tokens: StoredTokens) -> Result<()> {
+        ensure!(!self.fail_saves, "database unavailable");
+        let mut stored = self.tokens.lock().unwrap();
+        stored.insert(provider.to_owned(), tokens);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn restores_stored_tokens() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/token"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .and(header("authorization", "Bearer stored-access-token"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let store = MemoryTokenStore::default();
+    store
+        .save("spotify", {
+            let access_token = AccessToken {
+                token: "stored-access-token".to_owned(),
+                expires_at: Some(Utc::now() + Duration::hours(1)),
+            };
+            StoredTokens {
+                refresh_token: "stored-refresh-token".to_owned(),
+                access_token: Some(access_token),
+            }
+        })
+        .await
+        .unwrap();
+
+    let spotify = service(&server);
+    spotify.set_token_store(Arc::new(store)).await.unwrap();
+    spotify.get_currently_playing().await.unwrap();
+}
+
+#[tokio::test]
+async fn stores_rotated_refresh_token() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/token"))
+        .and(body_string_contains("refresh_token=refresh-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "access-token",
+            "token_type": "Bearer",
+            "expires_in": 3600,
+            "refresh_token": "rotated-refresh-token",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    let store = Arc::new(MemoryTokenStore::default());
+    let spotify = service(&server);
+    spotify.set_token_store(store.clone()).await.unwrap();
+    spotify.get_currently_playing().await.unwrap();
+
+    let tokens = store.get("spotify").expect("missing stored tokens");
+    assert_eq!(tokens.refresh_token, "rotated-refresh-token");
+    let access_token = tokens.access_token.expect("missing access token");
+    assert_eq!(access_token.token, "access-token");
+    assert!(access_token.expires_at.is_some());
+}
+
+#[tokio::test]
+async fn reports_store_failures() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "access-token",
+            "token_type": "Bearer",
+            "expires_in": 3600,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    let store = MemoryTokenStore {
+        fail_saves: true,
+        ..Default::default()
+    };
+    let spotify = service(&server);
+    spotify.set_token_store(Arc::new(store)).await.unwrap();
+
+    // A refresh token that can't be stored would be lost on restart...
+    let result = spotify.set_refresh_token("refresh-token".to_owned()).await;
+    assert!(result.is_err(), "expected store failure");
+
+    // ...but refreshed access tokens are still used if they can't be.
+    spotify.get_currently_playing().await.unwrap();
+}
+
+#[tokio::test]
+async fn refreshes_before_expiry() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "access-token",
+            "token_type": "Bearer",
+            "expires_in": 3600,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // The stored access token is about to expire, so it's replaced in the
+    // background without waiting for a request.
+    let store = Arc::new(MemoryTokenStore::default());
+    store
+        .save("spotify", {
+            let access_token = AccessToken {
+                token: "expiring-access-token".to_owned(),
+                expires_at: Some(Utc::now() + Duration::minutes(2)),
+            };
+            StoredTokens {
+                refresh_token: "refresh-token".to_owned(),
+                access_token: Some(access_token),
+            }
+        })
+        .await
+        .unwrap();
+    let spotify = service(&server);
+    spotify.set_token_store(store.clone()).await.unwrap();
+
+    timeout(StdDuration::from_secs(5), async {
+        loop {
+            let tokens = store.get("spotify").unwrap();
+            let access_token = tokens.access_token.unwrap();
+            if access_token.token == "access-token" {
+                break;
+            }
+            sleep(StdDuration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("access token was not refreshed");
+}
+
+#[tokio::test]
+async fn gets_top_tracks() {
+    let server = start_server().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/top/tracks"))
+        .and(query_param("time_range", "short_term"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [track_json()],
+            "next": null,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let spotify = service(&server);
+    for _ in 0..2 {
+        let tracks =
+            spotify.get_top_tracks(TimeRange::ShortTerm).await.unwrap();
+        assert_eq!(tracks.len(), 1);
+        assert_eq!(tracks[0].name, "Never Gonna Give You Up");
+    }
+}
+
+#[tokio::test]
+async fn gets_top_artists() {
+    let server = start_server().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/top/artists"))
+        .and(query_param("time_range", "long_term"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [
+                {
+                    "id": "0gxyHStUsqpMadRV0Di1Qt",
+                    "external_urls": {
+                        "spotify": "https://open.spotify.com/artist/0gxyHStUsqpMadRV0Di1Qt"
+                    },
+                    "name": "Rick Astley",
+                    "genres": ["dance pop"],
+                    "popularity": 72
+                }
+            ],
+            "next": null,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let spotify = service(&server);
+    let artists = spotify.get_top_artists(TimeRange::LongTerm).await.unwrap();
+    assert_eq!(artists.len(), 1);
+    assert_eq!(artists[0].name, "Rick Astley");
+}
+
+fn playlist_json(id: &str, owner_id: &str, public: Option<bool>) -> Json {
+    json!({
+        "id": id,
+        "external_urls": {
+            "spotify": format!("https://open.spotify.com/playlist/{}", id)
+        },
+        "name": format!("Playlist {}", id),
+        "description": "",
+        "public": public,
+        "images": [
+            {
+                "url": "https://mosaic.scdn.co/640/ab67616d0000b273",
+                "width": null,
+                "height": null
+            }
+        ],
+        "owner": {
+            "id": owner_id,
+            "display_name": "Rick Astley"
+        },
+        "tracks": {
+            "total": 12
+        }
+    })
+}
+
+#[tokio::test]
+async fn gets_public_playlists() {
+    let server = start_server().await;
+    let base_url = server.uri();
+    Mock::given(method("GET"))
+        .and(path("/v1/me"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": "rickastley",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/playlists"))
+        .and(query_param("offset", "50"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [
+                playlist_json("c", "rickastley", Some(true)),
+                playlist_json("d", "someoneelse", Some(true)),
+            ],
+            "next": null,
+        })))
+        .expect(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/playlists"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "items": [
+                playlist_json("a", "rickastley", Some(true)),
+                playlist_json("b", "rickastley", Some(false)),
+            ],
+            "next": format!(
+                "{}/v1/me/playlists?offset=50&limit=50",
+                base_url
+            ),
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let spotify = service(&server);
+    for _ in 0..2 {
+        let playlists = spotify.get_playlists().await.unwrap();
+        let ids = playlists
+            .iter()
+            .map(|playlist| playlist.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["a", "c"]);
+        assert_eq!(playlists[0].tracks.total, 12);
+    }
+}
+
+#[tokio::test]
+async fn fails_on_bad_status() {
+    let server = start_server().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/me/player"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let spotify = service(&server);
+    assert!(spotify.get_currently_playing().await.is_err());
+}
+
+#[tokio::test]
+async fn fails_without_access_token() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/token"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "error": "invalid_grant",
+        })))
+        .mount(&server)
+        .await;
+
+    let spotify = service(&server);
+    assert!(spotify.get_currently_playing().await.is_err());
+}
+
+#[tokio::test]
+async fn fails_with_invalid_token_lifetime() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "access_token": "access-token",
+            "token_type": "Bearer",
+            "expires_in": u64::MAX,
+        })))
+        .mount(&server)
+        .await;
+
+    let spotify = service(&server);
+    assert!(spotify.get_currently_playing().await.is_err());
+}
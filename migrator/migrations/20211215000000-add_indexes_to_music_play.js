@@ -0,0 +1,12 @@
+module.exports = {
+  async up(db) {
+    const musicPlay = db.collection("musicPlay");
+    await musicPlay.createIndex({ startedAt: 1 }, { name: "startedAt" });
+    await musicPlay.createIndex({ "track.id": 1 }, { name: "trackId" });
+  },
+
+  async down(db) {
+    const musicPlay = db.collection("musicPlay");
+    await musicPlay.dropIndexes(["startedAt", "trackId"]);
+  },
+};
@@ -0,0 +1,14 @@
+module.exports = {
+  async up(db) {
+    const authToken = db.collection("authToken");
+    await authToken.createIndex(
+      { provider: 1 },
+      { name: "provider", unique: true },
+    );
+  },
+
+  async down(db) {
+    const authToken = db.collection("authToken");
+    await authToken.dropIndex("provider");
+  },
+};
@@ -0,0 +1,22 @@
+module.exports = {
+  async up(db) {
+    const trackLyrics = db.collection("trackLyrics");
+    await trackLyrics.createIndex(
+      { spotifyTrackId: 1 },
+      {
+        name: "spotifyTrackId",
+        unique: true,
+        partialFilterExpression: { spotifyTrackId: { $exists: true } },
+      },
+    );
+    await trackLyrics.createIndex(
+      { trackName: 1, artistName: 1 },
+      { name: "trackName_artistName" },
+    );
+  },
+
+  async down(db) {
+    const trackLyrics = db.collection("trackLyrics");
+    await trackLyrics.dropIndexes(["spotifyTrackId", "trackName_artistName"]);
+  },
+};
@@ -72,26 +72,135 @@ export type HeartRateFieldPolicy = {
 	measuredAt?: FieldPolicy<any> | FieldReadFunction<any>,
 	measurement?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type KnowledgeEntryKeySpecifier = ('id' | 'links' | 'names' | 'tags' | KnowledgeEntryKeySpecifier)[];
+export type JournalEntryKeySpecifier = ('date' | 'entry' | JournalEntryKeySpecifier)[];
+export type JournalEntryFieldPolicy = {
+	date?: FieldPolicy<any> | FieldReadFunction<any>,
+	entry?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeEntryKeySpecifier = ('aliases' | 'content' | 'contentAt' | 'createdAt' | 'html' | 'id' | 'isPublished' | 'links' | 'modifiedAt' | 'names' | 'properties' | 'revisions' | 'tags' | 'tasks' | 'title' | 'updatedAt' | KnowledgeEntryKeySpecifier)[];
 export type KnowledgeEntryFieldPolicy = {
+	aliases?: FieldPolicy<any> | FieldReadFunction<any>,
+	content?: FieldPolicy<any> | FieldReadFunction<any>,
+	contentAt?: FieldPolicy<any> | FieldReadFunction<any>,
+	createdAt?: FieldPolicy<any> | FieldReadFunction<any>,
+	html?: FieldPolicy<any> | FieldReadFunction<any>,
 	id?: FieldPolicy<any> | FieldReadFunction<any>,
+	isPublished?: FieldPolicy<any> | FieldReadFunction<any>,
 	links?: FieldPolicy<any> | FieldReadFunction<any>,
+	modifiedAt?: FieldPolicy<any> | FieldReadFunction<any>,
 	names?: FieldPolicy<any> | FieldReadFunction<any>,
-	tags?: FieldPolicy<any> | FieldReadFunction<any>
+	properties?: FieldPolicy<any> | FieldReadFunction<any>,
+	revisions?: FieldPolicy<any> | FieldReadFunction<any>,
+	tags?: FieldPolicy<any> | FieldReadFunction<any>,
+	tasks?: FieldPolicy<any> | FieldReadFunction<any>,
+	title?: FieldPolicy<any> | FieldReadFunction<any>,
+	updatedAt?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeEntryConnectionKeySpecifier = ('edges' | 'pageInfo' | KnowledgeEntryConnectionKeySpecifier)[];
+export type KnowledgeEntryConnectionFieldPolicy = {
+	edges?: FieldPolicy<any> | FieldReadFunction<any>,
+	pageInfo?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeEntryEdgeKeySpecifier = ('cursor' | 'node' | KnowledgeEntryEdgeKeySpecifier)[];
+export type KnowledgeEntryEdgeFieldPolicy = {
+	cursor?: FieldPolicy<any> | FieldReadFunction<any>,
+	node?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type KnowledgeEntryLinksKeySpecifier = ('incoming' | 'outgoing' | KnowledgeEntryLinksKeySpecifier)[];
+export type KnowledgeEntryLinkKeySpecifier = ('alias' | 'block' | 'entry' | 'heading' | 'isEmbed' | 'target' | KnowledgeEntryLinkKeySpecifier)[];
+export type KnowledgeEntryLinkFieldPolicy = {
+	alias?: FieldPolicy<any> | FieldReadFunction<any>,
+	block?: FieldPolicy<any> | FieldReadFunction<any>,
+	entry?: FieldPolicy<any> | FieldReadFunction<any>,
+	heading?: FieldPolicy<any> | FieldReadFunction<any>,
+	isEmbed?: FieldPolicy<any> | FieldReadFunction<any>,
+	target?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeEntryLinksKeySpecifier = ('incoming' | 'outgoing' | 'references' | KnowledgeEntryLinksKeySpecifier)[];
 export type KnowledgeEntryLinksFieldPolicy = {
 	incoming?: FieldPolicy<any> | FieldReadFunction<any>,
-	outgoing?: FieldPolicy<any> | FieldReadFunction<any>
+	outgoing?: FieldPolicy<any> | FieldReadFunction<any>,
+	references?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeEntryRevisionKeySpecifier = ('committedAt' | 'content' | 'deletions' | 'id' | 'insertions' | 'message' | KnowledgeEntryRevisionKeySpecifier)[];
+export type KnowledgeEntryRevisionFieldPolicy = {
+	committedAt?: FieldPolicy<any> | FieldReadFunction<any>,
+	content?: FieldPolicy<any> | FieldReadFunction<any>,
+	deletions?: FieldPolicy<any> | FieldReadFunction<any>,
+	id?: FieldPolicy<any> | FieldReadFunction<any>,
+	insertions?: FieldPolicy<any> | FieldReadFunction<any>,
+	message?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeEntrySearchHighlightKeySpecifier = ('end' | 'start' | KnowledgeEntrySearchHighlightKeySpecifier)[];
+export type KnowledgeEntrySearchHighlightFieldPolicy = {
+	end?: FieldPolicy<any> | FieldReadFunction<any>,
+	start?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeEntrySearchResultKeySpecifier = ('entry' | 'highlights' | 'score' | 'snippet' | KnowledgeEntrySearchResultKeySpecifier)[];
+export type KnowledgeEntrySearchResultFieldPolicy = {
+	entry?: FieldPolicy<any> | FieldReadFunction<any>,
+	highlights?: FieldPolicy<any> | FieldReadFunction<any>,
+	score?: FieldPolicy<any> | FieldReadFunction<any>,
+	snippet?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeEntrySearchResultConnectionKeySpecifier = ('edges' | 'pageInfo' | KnowledgeEntrySearchResultConnectionKeySpecifier)[];
+export type KnowledgeEntrySearchResultConnectionFieldPolicy = {
+	edges?: FieldPolicy<any> | FieldReadFunction<any>,
+	pageInfo?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeEntrySearchResultEdgeKeySpecifier = ('cursor' | 'node' | KnowledgeEntrySearchResultEdgeKeySpecifier)[];
+export type KnowledgeEntrySearchResultEdgeFieldPolicy = {
+	cursor?: FieldPolicy<any> | FieldReadFunction<any>,
+	node?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeEntryUpdateKeySpecifier = ('deleted' | 'entry' | 'id' | KnowledgeEntryUpdateKeySpecifier)[];
+export type KnowledgeEntryUpdateFieldPolicy = {
+	deleted?: FieldPolicy<any> | FieldReadFunction<any>,
+	entry?: FieldPolicy<any> | FieldReadFunction<any>,
+	id?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeGraphKeySpecifier = ('edges' | 'nodes' | KnowledgeGraphKeySpecifier)[];
+export type KnowledgeGraphFieldPolicy = {
+	edges?: FieldPolicy<any> | FieldReadFunction<any>,
+	nodes?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeGraphEdgeKeySpecifier = ('source' | 'target' | KnowledgeGraphEdgeKeySpecifier)[];
+export type KnowledgeGraphEdgeFieldPolicy = {
+	source?: FieldPolicy<any> | FieldReadFunction<any>,
+	target?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeGraphNodeKeySpecifier = ('entry' | 'id' | 'isGhost' | 'names' | 'tags' | KnowledgeGraphNodeKeySpecifier)[];
+export type KnowledgeGraphNodeFieldPolicy = {
+	entry?: FieldPolicy<any> | FieldReadFunction<any>,
+	id?: FieldPolicy<any> | FieldReadFunction<any>,
+	isGhost?: FieldPolicy<any> | FieldReadFunction<any>,
+	names?: FieldPolicy<any> | FieldReadFunction<any>,
+	tags?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeTagKeySpecifier = ('children' | 'count' | 'label' | 'name' | 'totalCount' | KnowledgeTagKeySpecifier)[];
+export type KnowledgeTagFieldPolicy = {
+	children?: FieldPolicy<any> | FieldReadFunction<any>,
+	count?: FieldPolicy<any> | FieldReadFunction<any>,
+	label?: FieldPolicy<any> | FieldReadFunction<any>,
+	name?: FieldPolicy<any> | FieldReadFunction<any>,
+	totalCount?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type KnowledgeTaskKeySpecifier = ('due' | 'entry' | 'line' | 'status' | 'text' | KnowledgeTaskKeySpecifier)[];
+export type KnowledgeTaskFieldPolicy = {
+	due?: FieldPolicy<any> | FieldReadFunction<any>,
+	entry?: FieldPolicy<any> | FieldReadFunction<any>,
+	line?: FieldPolicy<any> | FieldReadFunction<any>,
+	status?: FieldPolicy<any> | FieldReadFunction<any>,
+	text?: FieldPolicy<any> | FieldReadFunction<any>
 };
 export type LyricLineKeySpecifier = ('position' | 'text' | LyricLineKeySpecifier)[];
 export type LyricLineFieldPolicy = {
 	position?: FieldPolicy<any> | FieldReadFunction<any>,
 	text?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type LyricsKeySpecifier = ('lines' | LyricsKeySpecifier)[];
+export type LyricsKeySpecifier = ('lines' | 'synced' | LyricsKeySpecifier)[];
 export type LyricsFieldPolicy = {
-	lines?: FieldPolicy<any> | FieldReadFunction<any>
+	lines?: FieldPolicy<any> | FieldReadFunction<any>,
+	synced?: FieldPolicy<any> | FieldReadFunction<any>
 };
 export type MusicAlbumKeySpecifier = ('imageUrl' | 'name' | 'spotifyId' | 'spotifyUrl' | MusicAlbumKeySpecifier)[];
 export type MusicAlbumFieldPolicy = {
@@ -106,12 +215,69 @@ export type MusicArtistFieldPolicy = {
 	spotifyId?: FieldPolicy<any> | FieldReadFunction<any>,
 	spotifyUrl?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type MusicInfoKeySpecifier = ('isPlaying' | 'progress' | 'track' | MusicInfoKeySpecifier)[];
+export type MusicArtistPlayCountKeySpecifier = ('artist' | 'playCount' | MusicArtistPlayCountKeySpecifier)[];
+export type MusicArtistPlayCountFieldPolicy = {
+	artist?: FieldPolicy<any> | FieldReadFunction<any>,
+	playCount?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type MusicContextKeySpecifier = ('spotifyUri' | 'spotifyUrl' | 'type' | MusicContextKeySpecifier)[];
+export type MusicContextFieldPolicy = {
+	spotifyUri?: FieldPolicy<any> | FieldReadFunction<any>,
+	spotifyUrl?: FieldPolicy<any> | FieldReadFunction<any>,
+	type?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type MusicDeviceKeySpecifier = ('name' | 'type' | 'volume' | MusicDeviceKeySpecifier)[];
+export type MusicDeviceFieldPolicy = {
+	name?: FieldPolicy<any> | FieldReadFunction<any>,
+	type?: FieldPolicy<any> | FieldReadFunction<any>,
+	volume?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type MusicInfoKeySpecifier = ('context' | 'device' | 'isPlaying' | 'item' | 'progress' | 'repeat' | 'shuffle' | 'track' | MusicInfoKeySpecifier)[];
 export type MusicInfoFieldPolicy = {
+	context?: FieldPolicy<any> | FieldReadFunction<any>,
+	device?: FieldPolicy<any> | FieldReadFunction<any>,
 	isPlaying?: FieldPolicy<any> | FieldReadFunction<any>,
+	item?: FieldPolicy<any> | FieldReadFunction<any>,
 	progress?: FieldPolicy<any> | FieldReadFunction<any>,
+	repeat?: FieldPolicy<any> | FieldReadFunction<any>,
+	shuffle?: FieldPolicy<any> | FieldReadFunction<any>,
 	track?: FieldPolicy<any> | FieldReadFunction<any>
 };
+export type MusicPlayKeySpecifier = ('endedAt' | 'id' | 'progress' | 'startedAt' | 'track' | MusicPlayKeySpecifier)[];
+export type MusicPlayFieldPolicy = {
+	endedAt?: FieldPolicy<any> | FieldReadFunction<any>,
+	id?: FieldPolicy<any> | FieldReadFunction<any>,
+	progress?: FieldPolicy<any> | FieldReadFunction<any>,
+	startedAt?: FieldPolicy<any> | FieldReadFunction<any>,
+	track?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type MusicPlayConnectionKeySpecifier = ('edges' | 'pageInfo' | MusicPlayConnectionKeySpecifier)[];
+export type MusicPlayConnectionFieldPolicy = {
+	edges?: FieldPolicy<any> | FieldReadFunction<any>,
+	pageInfo?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type MusicPlayEdgeKeySpecifier = ('cursor' | 'node' | MusicPlayEdgeKeySpecifier)[];
+export type MusicPlayEdgeFieldPolicy = {
+	cursor?: FieldPolicy<any> | FieldReadFunction<any>,
+	node?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type MusicPlayStatsKeySpecifier = ('listeningTime' | 'playCount' | 'topArtists' | 'topTracks' | MusicPlayStatsKeySpecifier)[];
+export type MusicPlayStatsFieldPolicy = {
+	listeningTime?: FieldPolicy<any> | FieldReadFunction<any>,
+	playCount?: FieldPolicy<any> | FieldReadFunction<any>,
+	topArtists?: FieldPolicy<any> | FieldReadFunction<any>,
+	topTracks?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type MusicPlaylistKeySpecifier = ('description' | 'imageUrl' | 'name' | 'ownerName' | 'spotifyId' | 'spotifyUrl' | 'trackCount' | MusicPlaylistKeySpecifier)[];
+export type MusicPlaylistFieldPolicy = {
+	description?: FieldPolicy<any> | FieldReadFunction<any>,
+	imageUrl?: FieldPolicy<any> | FieldReadFunction<any>,
+	name?: FieldPolicy<any> | FieldReadFunction<any>,
+	ownerName?: FieldPolicy<any> | FieldReadFunction<any>,
+	spotifyId?: FieldPolicy<any> | FieldReadFunction<any>,
+	spotifyUrl?: FieldPolicy<any> | FieldReadFunction<any>,
+	trackCount?: FieldPolicy<any> | FieldReadFunction<any>
+};
 export type MusicTrackKeySpecifier = ('album' | 'artists' | 'duration' | 'lyrics' | 'name' | 'spotifyId' | 'spotifyUrl' | MusicTrackKeySpecifier)[];
 export type MusicTrackFieldPolicy = {
 	album?: FieldPolicy<any> | FieldReadFunction<any>,
@@ -122,7 +288,12 @@ export type MusicTrackFieldPolicy = {
 	spotifyId?: FieldPolicy<any> | FieldReadFunction<any>,
 	spotifyUrl?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type MutationKeySpecifier = ('archiveForm' | 'createForm' | 'deleteForm' | 'restoreForm' | 'submitForm' | 'test' | 'testFailure' | 'updateForm' | MutationKeySpecifier)[];
+export type MusicTrackPlayCountKeySpecifier = ('playCount' | 'track' | MusicTrackPlayCountKeySpecifier)[];
+export type MusicTrackPlayCountFieldPolicy = {
+	playCount?: FieldPolicy<any> | FieldReadFunction<any>,
+	track?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type MutationKeySpecifier = ('archiveForm' | 'createForm' | 'deleteForm' | 'restoreForm' | 'submitForm' | 'test' | 'testFailure' | 'updateForm' | 'updateTrackLyrics' | MutationKeySpecifier)[];
 export type MutationFieldPolicy = {
 	archiveForm?: FieldPolicy<any> | FieldReadFunction<any>,
 	createForm?: FieldPolicy<any> | FieldReadFunction<any>,
@@ -131,9 +302,35 @@ export type MutationFieldPolicy = {
 	submitForm?: FieldPolicy<any> | FieldReadFunction<any>,
 	test?: FieldPolicy<any> | FieldReadFunction<any>,
 	testFailure?: FieldPolicy<any> | FieldReadFunction<any>,
-	updateForm?: FieldPolicy<any> | FieldReadFunction<any>
+	updateForm?: FieldPolicy<any> | FieldReadFunction<any>,
+	updateTrackLyrics?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type QueryKeySpecifier = ('buildInfo' | 'form' | 'formByHandle' | 'formResponse' | 'forms' | 'heartRate' | 'knowledgeEntries' | 'knowledgeEntry' | 'musicInfo' | 'test' | 'viewer' | QueryKeySpecifier)[];
+export type PageInfoKeySpecifier = ('endCursor' | 'hasNextPage' | 'hasPreviousPage' | 'startCursor' | PageInfoKeySpecifier)[];
+export type PageInfoFieldPolicy = {
+	endCursor?: FieldPolicy<any> | FieldReadFunction<any>,
+	hasNextPage?: FieldPolicy<any> | FieldReadFunction<any>,
+	hasPreviousPage?: FieldPolicy<any> | FieldReadFunction<any>,
+	startCursor?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type PodcastEpisodeKeySpecifier = ('description' | 'duration' | 'imageUrl' | 'name' | 'show' | 'spotifyId' | 'spotifyUrl' | PodcastEpisodeKeySpecifier)[];
+export type PodcastEpisodeFieldPolicy = {
+	description?: FieldPolicy<any> | FieldReadFunction<any>,
+	duration?: FieldPolicy<any> | FieldReadFunction<any>,
+	imageUrl?: FieldPolicy<any> | FieldReadFunction<any>,
+	name?: FieldPolicy<any> | FieldReadFunction<any>,
+	show?: FieldPolicy<any> | FieldReadFunction<any>,
+	spotifyId?: FieldPolicy<any> | FieldReadFunction<any>,
+	spotifyUrl?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type PodcastShowKeySpecifier = ('imageUrl' | 'name' | 'publisher' | 'spotifyId' | 'spotifyUrl' | PodcastShowKeySpecifier)[];
+export type PodcastShowFieldPolicy = {
+	imageUrl?: FieldPolicy<any> | FieldReadFunction<any>,
+	name?: FieldPolicy<any> | FieldReadFunction<any>,
+	publisher?: FieldPolicy<any> | FieldReadFunction<any>,
+	spotifyId?: FieldPolicy<any> | FieldReadFunction<any>,
+	spotifyUrl?: FieldPolicy<any> | FieldReadFunction<any>
+};
+export type QueryKeySpecifier = ('buildInfo' | 'form' | 'formByHandle' | 'formResponse' | 'forms' | 'heartRate' | 'journalEntries' | 'journalEntry' | 'knowledgeEntries' | 'knowledgeEntriesByTag' | 'knowledgeEntry' | 'knowledgeGraph' | 'knowledgeTags' | 'knowledgeTasks' | 'musicInfo' | 'musicPlayStats' | 'musicPlaylists' | 'musicTopArtists' | 'musicTopTracks' | 'recentKnowledgeEntries' | 'recentMusicPlays' | 'searchKnowledgeEntries' | 'test' | 'viewer' | QueryKeySpecifier)[];
 export type QueryFieldPolicy = {
 	buildInfo?: FieldPolicy<any> | FieldReadFunction<any>,
 	form?: FieldPolicy<any> | FieldReadFunction<any>,
@@ -141,9 +338,22 @@ export type QueryFieldPolicy = {
 	formResponse?: FieldPolicy<any> | FieldReadFunction<any>,
 	forms?: FieldPolicy<any> | FieldReadFunction<any>,
 	heartRate?: FieldPolicy<any> | FieldReadFunction<any>,
+	journalEntries?: FieldPolicy<any> | FieldReadFunction<any>,
+	journalEntry?: FieldPolicy<any> | FieldReadFunction<any>,
 	knowledgeEntries?: FieldPolicy<any> | FieldReadFunction<any>,
+	knowledgeEntriesByTag?: FieldPolicy<any> | FieldReadFunction<any>,
 	knowledgeEntry?: FieldPolicy<any> | FieldReadFunction<any>,
+	knowledgeGraph?: FieldPolicy<any> | FieldReadFunction<any>,
+	knowledgeTags?: FieldPolicy<any> | FieldReadFunction<any>,
+	knowledgeTasks?: FieldPolicy<any> | FieldReadFunction<any>,
 	musicInfo?: FieldPolicy<any> | FieldReadFunction<any>,
+	musicPlayStats?: FieldPolicy<any> | FieldReadFunction<any>,
+	musicPlaylists?: FieldPolicy<any> | FieldReadFunction<any>,
+	musicTopArtists?: FieldPolicy<any> | FieldReadFunction<any>,
+	musicTopTracks?: FieldPolicy<any> | FieldReadFunction<any>,
+	recentKnowledgeEntries?: FieldPolicy<any> | FieldReadFunction<any>,
+	recentMusicPlays?: FieldPolicy<any> | FieldReadFunction<any>,
+	searchKnowledgeEntries?: FieldPolicy<any> | FieldReadFunction<any>,
 	test?: FieldPolicy<any> | FieldReadFunction<any>,
 	viewer?: FieldPolicy<any> | FieldReadFunction<any>
 };
@@ -157,8 +367,10 @@ export type SubmitFormPayloadFieldPolicy = {
 	ok?: FieldPolicy<any> | FieldReadFunction<any>,
 	response?: FieldPolicy<any> | FieldReadFunction<any>
 };
-export type SubscriptionKeySpecifier = ('test' | SubscriptionKeySpecifier)[];
+export type SubscriptionKeySpecifier = ('knowledgeEntryUpdates' | 'musicInfo' | 'test' | SubscriptionKeySpecifier)[];
 export type SubscriptionFieldPolicy = {
+	knowledgeEntryUpdates?: FieldPolicy<any> | FieldReadFunction<any>,
+	musicInfo?: FieldPolicy<any> | FieldReadFunction<any>,
 	test?: FieldPolicy<any> | FieldReadFunction<any>
 };
 export type TestPayloadKeySpecifier = ('ok' | 'value' | TestPayloadKeySpecifier)[];
@@ -171,6 +383,11 @@ export type UpdateFormPayloadFieldPolicy = {
 	form?: FieldPolicy<any> | FieldReadFunction<any>,
 	ok?: FieldPolicy<any> | FieldReadFunction<any>
 };
+export type UpdateTrackLyricsPayloadKeySpecifier = ('lyrics' | 'ok' | UpdateTrackLyricsPayloadKeySpecifier)[];
+export type UpdateTrackLyricsPayloadFieldPolicy = {
+	lyrics?: FieldPolicy<any> | FieldReadFunction<any>,
+	ok?: FieldPolicy<any> | FieldReadFunction<any>
+};
 export type UserKeySpecifier = ('email' | 'id' | 'isAdmin' | UserKeySpecifier)[];
 export type UserFieldPolicy = {
 	email?: FieldPolicy<any> | FieldReadFunction<any>,
@@ -226,14 +443,74 @@ export type StrictTypedTypePolicies = {
 		keyFields?: false | HeartRateKeySpecifier | (() => undefined | HeartRateKeySpecifier),
 		fields?: HeartRateFieldPolicy,
 	},
+	JournalEntry?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | JournalEntryKeySpecifier | (() => undefined | JournalEntryKeySpecifier),
+		fields?: JournalEntryFieldPolicy,
+	},
 	KnowledgeEntry?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | KnowledgeEntryKeySpecifier | (() => undefined | KnowledgeEntryKeySpecifier),
 		fields?: KnowledgeEntryFieldPolicy,
 	},
+	KnowledgeEntryConnection?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | KnowledgeEntryConnectionKeySpecifier | (() => undefined | KnowledgeEntryConnectionKeySpecifier),
+		fields?: KnowledgeEntryConnectionFieldPolicy,
+	},
+	KnowledgeEntryEdge?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | KnowledgeEntryEdgeKeySpecifier | (() => undefined | KnowledgeEntryEdgeKeySpecifier),
+		fields?: KnowledgeEntryEdgeFieldPolicy,
+	},
+	KnowledgeEntryLink?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | KnowledgeEntryLinkKeySpecifier | (() => undefined | KnowledgeEntryLinkKeySpecifier),
+		fields?: KnowledgeEntryLinkFieldPolicy,
+	},
 	KnowledgeEntryLinks?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | KnowledgeEntryLinksKeySpecifier | (() => undefined | KnowledgeEntryLinksKeySpecifier),
 		fields?: KnowledgeEntryLinksFieldPolicy,
 	},
+	KnowledgeEntryRevision?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | KnowledgeEntryRevisionKeySpecifier | (() => undefined | KnowledgeEntryRevisionKeySpecifier),
+		fields?: KnowledgeEntryRevisionFieldPolicy,
+	},
+	KnowledgeEntrySearchHighlight?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | KnowledgeEntrySearchHighlightKeySpecifier | (() => undefined | KnowledgeEntrySearchHighlightKeySpecifier),
+		fields?: KnowledgeEntrySearchHighlightFieldPolicy,
+	},
+	KnowledgeEntrySearchResult?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | KnowledgeEntrySearchResultKeySpecifier | (() => undefined | KnowledgeEntrySearchResultKeySpecifier),
+		fields?: KnowledgeEntrySearchResultFieldPolicy,
+	},
+	KnowledgeEntrySearchResultConnection?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | KnowledgeEntrySearchResultConnectionKeySpecifier | (() => undefined | KnowledgeEntrySearchResultConnectionKeySpecifier),
+		fields?: KnowledgeEntrySearchResultConnectionFieldPolicy,
+	},
+	KnowledgeEntrySearchResultEdge?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | KnowledgeEntrySearchResultEdgeKeySpecifier | (() => undefined | KnowledgeEntrySearchResultEdgeKeySpecifier),
+		fields?: KnowledgeEntrySearchResultEdgeFieldPolicy,
+	},
+	KnowledgeEntryUpdate?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | KnowledgeEntryUpdateKeySpecifier | (() => undefined | KnowledgeEntryUpdateKeySpecifier),
+		fields?: KnowledgeEntryUpdateFieldPolicy,
+	},
+	KnowledgeGraph?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | KnowledgeGraphKeySpecifier | (() => undefined | KnowledgeGraphKeySpecifier),
+		fields?: KnowledgeGraphFieldPolicy,
+	},
+	KnowledgeGraphEdge?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | KnowledgeGraphEdgeKeySpecifier | (() => undefined | KnowledgeGraphEdgeKeySpecifier),
+		fields?: KnowledgeGraphEdgeFieldPolicy,
+	},
+	KnowledgeGraphNode?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | KnowledgeGraphNodeKeySpecifier | (() => undefined | KnowledgeGraphNodeKeySpecifier),
+		fields?: KnowledgeGraphNodeFieldPolicy,
+	},
+	KnowledgeTag?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | KnowledgeTagKeySpecifier | (() => undefined | KnowledgeTagKeySpecifier),
+		fields?: KnowledgeTagFieldPolicy,
+	},
+	KnowledgeTask?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | KnowledgeTaskKeySpecifier | (() => undefined | KnowledgeTaskKeySpecifier),
+		fields?: KnowledgeTaskFieldPolicy,
+	},
 	LyricLine?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | LyricLineKeySpecifier | (() => undefined | LyricLineKeySpecifier),
 		fields?: LyricLineFieldPolicy,
@@ -250,18 +527,66 @@ export type StrictTypedTypePolicies = {
 		keyFields?: false | MusicArtistKeySpecifier | (() => undefined | MusicArtistKeySpecifier),
 		fields?: MusicArtistFieldPolicy,
 	},
+	MusicArtistPlayCount?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | MusicArtistPlayCountKeySpecifier | (() => undefined | MusicArtistPlayCountKeySpecifier),
+		fields?: MusicArtistPlayCountFieldPolicy,
+	},
+	MusicContext?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | MusicContextKeySpecifier | (() => undefined | MusicContextKeySpecifier),
+		fields?: MusicContextFieldPolicy,
+	},
+	MusicDevice?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | MusicDeviceKeySpecifier | (() => undefined | MusicDeviceKeySpecifier),
+		fields?: MusicDeviceFieldPolicy,
+	},
 	MusicInfo?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | MusicInfoKeySpecifier | (() => undefined | MusicInfoKeySpecifier),
 		fields?: MusicInfoFieldPolicy,
 	},
+	MusicPlay?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | MusicPlayKeySpecifier | (() => undefined | MusicPlayKeySpecifier),
+		fields?: MusicPlayFieldPolicy,
+	},
+	MusicPlayConnection?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | MusicPlayConnectionKeySpecifier | (() => undefined | MusicPlayConnectionKeySpecifier),
+		fields?: MusicPlayConnectionFieldPolicy,
+	},
+	MusicPlayEdge?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | MusicPlayEdgeKeySpecifier | (() => undefined | MusicPlayEdgeKeySpecifier),
+		fields?: MusicPlayEdgeFieldPolicy,
+	},
+	MusicPlayStats?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | MusicPlayStatsKeySpecifier | (() => undefined | MusicPlayStatsKeySpecifier),
+		fields?: MusicPlayStatsFieldPolicy,
+	},
+	MusicPlaylist?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | MusicPlaylistKeySpecifier | (() => undefined | MusicPlaylistKeySpecifier),
+		fields?: MusicPlaylistFieldPolicy,
+	},
 	MusicTrack?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | MusicTrackKeySpecifier | (() => undefined | MusicTrackKeySpecifier),
 		fields?: MusicTrackFieldPolicy,
 	},
+	MusicTrackPlayCount?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | MusicTrackPlayCountKeySpecifier | (() => undefined | MusicTrackPlayCountKeySpecifier),
+		fields?: MusicTrackPlayCountFieldPolicy,
+	},
 	Mutation?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | MutationKeySpecifier | (() => undefined | MutationKeySpecifier),
 		fields?: MutationFieldPolicy,
 	},
+	PageInfo?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | PageInfoKeySpecifier | (() => undefined | PageInfoKeySpecifier),
+		fields?: PageInfoFieldPolicy,
+	},
+	PodcastEpisode?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | PodcastEpisodeKeySpecifier | (() => undefined | PodcastEpisodeKeySpecifier),
+		fields?: PodcastEpisodeFieldPolicy,
+	},
+	PodcastShow?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | PodcastShowKeySpecifier | (() => undefined | PodcastShowKeySpecifier),
+		fields?: PodcastShowFieldPolicy,
+	},
 	Query?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | QueryKeySpecifier | (() => undefined | QueryKeySpecifier),
 		fields?: QueryFieldPolicy,
@@ -286,6 +611,10 @@ export type StrictTypedTypePolicies = {
 		keyFields?: false | UpdateFormPayloadKeySpecifier | (() => undefined | UpdateFormPayloadKeySpecifier),
 		fields?: UpdateFormPayloadFieldPolicy,
 	},
+	UpdateTrackLyricsPayload?: Omit<TypePolicy, "fields" | "keyFields"> & {
+		keyFields?: false | UpdateTrackLyricsPayloadKeySpecifier | (() => undefined | UpdateTrackLyricsPayloadKeySpecifier),
+		fields?: UpdateTrackLyricsPayloadFieldPolicy,
+	},
 	User?: Omit<TypePolicy, "fields" | "keyFields"> & {
 		keyFields?: false | UserKeySpecifier | (() => undefined | UserKeySpecifier),
 		fields?: UserFieldPolicy,
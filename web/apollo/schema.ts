@@ -12,12 +12,16 @@ export type Scalars = {
   Boolean: boolean;
   Int: number;
   Float: number;
+  /** ISO 8601 calendar date without time zone. */
+  Date: any;
   /**
    * Implement the DateTime<FixedOffset> scalar
    *
    * The input/output is a string in RFC3339 format.
    */
   DateTime: any;
+  /** A scalar that can represent any JSON value. */
+  JSON: any;
   /** URL is a String implementing the [URL Standard](http://url.spec.whatwg.org/) */
   Url: any;
 };
@@ -149,20 +153,177 @@ export type HeartRate = {
   measurement: Scalars['Int'];
 };
 
+export type JournalEntry = {
+  __typename?: 'JournalEntry';
+  date: Scalars['Date'];
+  entry: KnowledgeEntry;
+};
+
 export type KnowledgeEntry = {
   __typename?: 'KnowledgeEntry';
+  aliases: Array<Scalars['String']>;
+  content: Scalars['String'];
+  /** The content of this entry at a revision. */
+  contentAt?: Maybe<Scalars['String']>;
+  createdAt?: Maybe<Scalars['DateTime']>;
+  html: Scalars['String'];
   id: Scalars['String'];
+  isPublished: Scalars['Boolean'];
   links: KnowledgeEntryLinks;
+  modifiedAt?: Maybe<Scalars['DateTime']>;
   names: Array<Scalars['String']>;
+  properties: Scalars['JSON'];
+  /** Commits that changed this entry, most recent first. */
+  revisions: Array<KnowledgeEntryRevision>;
   tags: Array<Scalars['String']>;
+  tasks: Array<KnowledgeTask>;
+  title?: Maybe<Scalars['String']>;
+  updatedAt?: Maybe<Scalars['DateTime']>;
+};
+
+
+export type KnowledgeEntryContentAtArgs = {
+  revision: Scalars['String'];
+};
+
+export type KnowledgeEntryConnection = {
+  __typename?: 'KnowledgeEntryConnection';
+  /** A list of edges. */
+  edges?: Maybe<Array<Maybe<KnowledgeEntryEdge>>>;
+  /** Information to aid in pagination. */
+  pageInfo: PageInfo;
+};
+
+/** An edge in a connection. */
+export type KnowledgeEntryEdge = {
+  __typename?: 'KnowledgeEntryEdge';
+  /** A cursor for use in pagination */
+  cursor: Scalars['String'];
+  /** The item at the end of the edge */
+  node: KnowledgeEntry;
+};
+
+export type KnowledgeEntryLink = {
+  __typename?: 'KnowledgeEntryLink';
+  alias?: Maybe<Scalars['String']>;
+  block?: Maybe<Scalars['String']>;
+  entry?: Maybe<KnowledgeEntry>;
+  heading?: Maybe<Scalars['String']>;
+  isEmbed: Scalars['Boolean'];
+  target: Scalars['String'];
 };
 
 export type KnowledgeEntryLinks = {
   __typename?: 'KnowledgeEntryLinks';
   incoming: Array<KnowledgeEntry>;
   outgoing: Array<KnowledgeEntry>;
+  references: Array<KnowledgeEntryLink>;
+};
+
+export type KnowledgeEntryRevision = {
+  __typename?: 'KnowledgeEntryRevision';
+  committedAt: Scalars['DateTime'];
+  /** The content of the entry at this revision. */
+  content?: Maybe<Scalars['String']>;
+  deletions: Scalars['Int'];
+  id: Scalars['String'];
+  insertions: Scalars['Int'];
+  message: Scalars['String'];
+};
+
+export type KnowledgeEntrySearchHighlight = {
+  __typename?: 'KnowledgeEntrySearchHighlight';
+  end: Scalars['Int'];
+  start: Scalars['Int'];
+};
+
+export type KnowledgeEntrySearchResult = {
+  __typename?: 'KnowledgeEntrySearchResult';
+  entry: KnowledgeEntry;
+  highlights: Array<KnowledgeEntrySearchHighlight>;
+  score: Scalars['Float'];
+  snippet: Scalars['String'];
+};
+
+export type KnowledgeEntrySearchResultConnection = {
+  __typename?: 'KnowledgeEntrySearchResultConnection';
+  /** A list of edges. */
+  edges?: Maybe<Array<Maybe<KnowledgeEntrySearchResultEdge>>>;
+  /** Information to aid in pagination. */
+  pageInfo: PageInfo;
+};
+
+/** An edge in a connection. */
+export type KnowledgeEntrySearchResultEdge = {
+  __typename?: 'KnowledgeEntrySearchResultEdge';
+  /** A cursor for use in pagination */
+  cursor: Scalars['String'];
+  /** The item at the end of the edge */
+  node: KnowledgeEntrySearchResult;
+};
+
+export enum KnowledgeEntrySort {
+  Id = 'ID',
+  IncomingLinks = 'INCOMING_LINKS',
+  ModifiedAt = 'MODIFIED_AT'
+}
+
+/** A knowledge entry that was added, changed, or deleted. */
+export type KnowledgeEntryUpdate = {
+  __typename?: 'KnowledgeEntryUpdate';
+  deleted: Scalars['Boolean'];
+  /** The entry, unless it was deleted. */
+  entry?: Maybe<KnowledgeEntry>;
+  id: Scalars['String'];
+};
+
+export type KnowledgeGraph = {
+  __typename?: 'KnowledgeGraph';
+  edges: Array<KnowledgeGraphEdge>;
+  nodes: Array<KnowledgeGraphNode>;
+};
+
+export type KnowledgeGraphEdge = {
+  __typename?: 'KnowledgeGraphEdge';
+  source: Scalars['String'];
+  target: Scalars['String'];
+};
+
+export type KnowledgeGraphNode = {
+  __typename?: 'KnowledgeGraphNode';
+  entry?: Maybe<KnowledgeEntry>;
+  id: Scalars['String'];
+  isGhost: Scalars['Boolean'];
+  names: Array<Scalars['String']>;
+  tags: Array<Scalars['String']>;
+};
+
+export type KnowledgeTag = {
+  __typename?: 'KnowledgeTag';
+  children: Array<KnowledgeTag>;
+  count: Scalars['Int'];
+  label: Scalars['String'];
+  name: Scalars['String'];
+  totalCount: Scalars['Int'];
+};
+
+export type KnowledgeTask = {
+  __typename?: 'KnowledgeTask';
+  due?: Maybe<Scalars['Date']>;
+  entry: KnowledgeEntry;
+  /** The line of the entry that the task is on, starting from 1. */
+  line: Scalars['Int'];
+  status: KnowledgeTaskStatus;
+  text: Scalars['String'];
 };
 
+export enum KnowledgeTaskStatus {
+  Cancelled = 'CANCELLED',
+  Done = 'DONE',
+  InProgress = 'IN_PROGRESS',
+  Todo = 'TODO'
+}
+
 export type LyricLine = {
   __typename?: 'LyricLine';
   position: Scalars['Int'];
@@ -172,6 +333,11 @@ export type LyricLine = {
 export type Lyrics = {
   __typename?: 'Lyrics';
   lines: Array<LyricLine>;
+  /**
+   * Whether the lines have positions; if not, every line's position is
+   * zero.
+   */
+  synced: Scalars['Boolean'];
 };
 
 export type MusicAlbum = {
@@ -189,13 +355,125 @@ export type MusicArtist = {
   spotifyUrl: Scalars['Url'];
 };
 
+export type MusicArtistPlayCount = {
+  __typename?: 'MusicArtistPlayCount';
+  artist: MusicArtist;
+  playCount: Scalars['Int'];
+};
+
+export type MusicContext = {
+  __typename?: 'MusicContext';
+  spotifyUri: Scalars['String'];
+  spotifyUrl?: Maybe<Scalars['Url']>;
+  /** The kind of context, i.e. `playlist` or `album`. */
+  type: Scalars['String'];
+};
+
+export type MusicDevice = {
+  __typename?: 'MusicDevice';
+  name: Scalars['String'];
+  /** The kind of device, i.e. `Computer` or `Smartphone`. */
+  type: Scalars['String'];
+  /** The device's volume, as a percentage. */
+  volume?: Maybe<Scalars['Int']>;
+};
+
 export type MusicInfo = {
   __typename?: 'MusicInfo';
+  context?: Maybe<MusicContext>;
+  device?: Maybe<MusicDevice>;
   isPlaying: Scalars['Boolean'];
+  /**
+   * The track or podcast episode that is playing, which is missing while
+   * an ad is playing.
+   */
+  item?: Maybe<MusicItem>;
+  /** The position in the item, in milliseconds. */
   progress: Scalars['Int'];
+  repeat: MusicRepeatMode;
+  shuffle: Scalars['Boolean'];
+  /** @deprecated Use `item` instead. */
+  track?: Maybe<MusicTrack>;
+};
+
+export type MusicItem = MusicTrack | PodcastEpisode;
+
+export type MusicPlay = {
+  __typename?: 'MusicPlay';
+  endedAt: Scalars['DateTime'];
+  id: Scalars['ID'];
+  /** The furthest position reached in the track, in milliseconds. */
+  progress: Scalars['Int'];
+  startedAt: Scalars['DateTime'];
   track: MusicTrack;
 };
 
+export type MusicPlayConnection = {
+  __typename?: 'MusicPlayConnection';
+  /** A list of edges. */
+  edges?: Maybe<Array<Maybe<MusicPlayEdge>>>;
+  /** Information to aid in pagination. */
+  pageInfo: PageInfo;
+};
+
+/** An edge in a connection. */
+export type MusicPlayEdge = {
+  __typename?: 'MusicPlayEdge';
+  /** A cursor for use in pagination */
+  cursor: Scalars['String'];
+  /** The item at the end of the edge */
+  node: MusicPlay;
+};
+
+export type MusicPlayStats = {
+  __typename?: 'MusicPlayStats';
+  /** The total time spent listening, in milliseconds. */
+  listeningTime: Scalars['Int'];
+  playCount: Scalars['Int'];
+  /** The most played artists, most played first. */
+  topArtists: Array<MusicArtistPlayCount>;
+  /** The most played tracks, most played first. */
+  topTracks: Array<MusicTrackPlayCount>;
+};
+
+
+export type MusicPlayStatsTopArtistsArgs = {
+  first?: Scalars['Int'];
+};
+
+
+export type MusicPlayStatsTopTracksArgs = {
+  first?: Scalars['Int'];
+};
+
+export type MusicPlaylist = {
+  __typename?: 'MusicPlaylist';
+  /** The playlist's description, which may contain HTML. */
+  description?: Maybe<Scalars['String']>;
+  imageUrl?: Maybe<Scalars['Url']>;
+  name: Scalars['String'];
+  ownerName?: Maybe<Scalars['String']>;
+  spotifyId: Scalars['String'];
+  spotifyUrl: Scalars['Url'];
+  trackCount: Scalars['Int'];
+};
+
+export enum MusicRepeatMode {
+  Context = 'CONTEXT',
+  Off = 'OFF',
+  Track = 'TRACK'
+}
+
+/** The time range over which top tracks and artists are calculated. */
+export enum MusicTimeRange {
+  /** Several years of listening. */
+  LongTerm = 'LONG_TERM',
+  /** Approximately the last 6 months. */
+  MediumTerm = 'MEDIUM_TERM',
+  /** Approximately the last 4 weeks. */
+  ShortTerm = 'SHORT_TERM'
+}
+
 export type MusicTrack = {
   __typename?: 'MusicTrack';
   album: MusicAlbum;
@@ -207,6 +485,12 @@ export type MusicTrack = {
   spotifyUrl: Scalars['Url'];
 };
 
+export type MusicTrackPlayCount = {
+  __typename?: 'MusicTrackPlayCount';
+  playCount: Scalars['Int'];
+  track: MusicTrack;
+};
+
 export type Mutation = {
   __typename?: 'Mutation';
   archiveForm: ArchiveFormPayload;
@@ -217,6 +501,8 @@ export type Mutation = {
   test: TestPayload;
   testFailure: TestPayload;
   updateForm: UpdateFormPayload;
+  /** Override the lyrics for a track, i.e. to correct them. */
+  updateTrackLyrics: UpdateTrackLyricsPayload;
 };
 
 
@@ -259,6 +545,44 @@ export type MutationUpdateFormArgs = {
   input: UpdateFormInput;
 };
 
+
+export type MutationUpdateTrackLyricsArgs = {
+  input: UpdateTrackLyricsInput;
+};
+
+/** Information about pagination in a connection */
+export type PageInfo = {
+  __typename?: 'PageInfo';
+  /** When paginating forwards, the cursor to continue. */
+  endCursor?: Maybe<Scalars['String']>;
+  /** When paginating forwards, are there more items? */
+  hasNextPage: Scalars['Boolean'];
+  /** When paginating backwards, are there more items? */
+  hasPreviousPage: Scalars['Boolean'];
+  /** When paginating backwards, the cursor to continue. */
+  startCursor?: Maybe<Scalars['String']>;
+};
+
+export type PodcastEpisode = {
+  __typename?: 'PodcastEpisode';
+  description: Scalars['String'];
+  duration: Scalars['Int'];
+  imageUrl?: Maybe<Scalars['Url']>;
+  name: Scalars['String'];
+  show: PodcastShow;
+  spotifyId: Scalars['String'];
+  spotifyUrl: Scalars['Url'];
+};
+
+export type PodcastShow = {
+  __typename?: 'PodcastShow';
+  imageUrl?: Maybe<Scalars['Url']>;
+  name: Scalars['String'];
+  publisher: Scalars['String'];
+  spotifyId: Scalars['String'];
+  spotifyUrl: Scalars['Url'];
+};
+
 export type Query = {
   __typename?: 'Query';
   buildInfo: BuildInfo;
@@ -267,9 +591,29 @@ export type Query = {
   formResponse?: Maybe<FormResponse>;
   forms: Array<Form>;
   heartRate?: Maybe<HeartRate>;
-  knowledgeEntries: Array<KnowledgeEntry>;
+  journalEntries: Array<JournalEntry>;
+  journalEntry?: Maybe<JournalEntry>;
+  knowledgeEntries: KnowledgeEntryConnection;
+  knowledgeEntriesByTag: Array<KnowledgeEntry>;
   knowledgeEntry?: Maybe<KnowledgeEntry>;
+  knowledgeGraph: KnowledgeGraph;
+  knowledgeTags: Array<KnowledgeTag>;
+  knowledgeTasks: Array<KnowledgeTask>;
   musicInfo?: Maybe<MusicInfo>;
+  /**
+   * Listening statistics for plays that started within a window of time,
+   * which defaults to the last week.
+   */
+  musicPlayStats: MusicPlayStats;
+  /** Public playlists on Spotify that are owned by the user. */
+  musicPlaylists: Array<MusicPlaylist>;
+  /** The most played artists on Spotify, most played first. */
+  musicTopArtists: Array<MusicArtist>;
+  /** The most played tracks on Spotify, most played first. */
+  musicTopTracks: Array<MusicTrack>;
+  recentKnowledgeEntries: Array<KnowledgeEntry>;
+  recentMusicPlays: MusicPlayConnection;
+  searchKnowledgeEntries: KnowledgeEntrySearchResultConnection;
   test: Scalars['Boolean'];
   viewer?: Maybe<User>;
 };
@@ -297,10 +641,78 @@ export type QueryFormsArgs = {
 };
 
 
+export type QueryJournalEntriesArgs = {
+  from: Scalars['Date'];
+  to: Scalars['Date'];
+};
+
+
+export type QueryJournalEntryArgs = {
+  date: Scalars['Date'];
+};
+
+
+export type QueryKnowledgeEntriesArgs = {
+  after?: Maybe<Scalars['String']>;
+  first?: Scalars['Int'];
+  folder?: Maybe<Scalars['String']>;
+  sort?: KnowledgeEntrySort;
+};
+
+
+export type QueryKnowledgeEntriesByTagArgs = {
+  includeDescendants?: Scalars['Boolean'];
+  tag: Scalars['String'];
+};
+
+
 export type QueryKnowledgeEntryArgs = {
   id: Scalars['String'];
 };
 
+
+export type QueryKnowledgeTasksArgs = {
+  dueBefore?: Maybe<Scalars['Date']>;
+  status?: Maybe<KnowledgeTaskStatus>;
+};
+
+
+export type QueryMusicPlayStatsArgs = {
+  since?: Maybe<Scalars['DateTime']>;
+  until?: Maybe<Scalars['DateTime']>;
+};
+
+
+export type QueryMusicTopArtistsArgs = {
+  first?: Scalars['Int'];
+  range?: MusicTimeRange;
+};
+
+
+export type QueryMusicTopTracksArgs = {
+  first?: Scalars['Int'];
+  range?: MusicTimeRange;
+};
+
+
+export type QueryRecentKnowledgeEntriesArgs = {
+  first?: Scalars['Int'];
+  since?: Maybe<Scalars['DateTime']>;
+};
+
+
+export type QueryRecentMusicPlaysArgs = {
+  after?: Maybe<Scalars['String']>;
+  first?: Scalars['Int'];
+};
+
+
+export type QuerySearchKnowledgeEntriesArgs = {
+  after?: Maybe<Scalars['String']>;
+  first?: Scalars['Int'];
+  query: Scalars['String'];
+};
+
 export type RestoreFormInput = {
   formId: Scalars['ID'];
 };
@@ -325,6 +737,18 @@ export type SubmitFormPayload = {
 
 export type Subscription = {
   __typename?: 'Subscription';
+  /**
+   * Emits knowledge entries as they are added, changed, or deleted.
+   *
+   * Only available when the vault is being watched.
+   */
+  knowledgeEntryUpdates: KnowledgeEntryUpdate;
+  /**
+   * Emits the current music info, and then emits it again whenever the
+   * item changes, is paused or resumed, or its progress jumps, or when
+   * the device, shuffle, repeat, or context changes.
+   */
+  musicInfo?: Maybe<MusicInfo>;
   test: Scalars['Int'];
 };
 
@@ -353,6 +777,27 @@ export type UpdateFormPayload = {
   ok: Scalars['Boolean'];
 };
 
+export type UpdateTrackLyricsInput = {
+  artistName: Scalars['String'];
+  /**
+   * The lyrics, in LRC format (i.e. `[01:23.45] Line`) or as plain text;
+   * if null, the track is recorded as having no lyrics.
+   */
+  lyrics?: Maybe<Scalars['String']>;
+  /**
+   * The track's ID on Spotify; without it, the lyrics are used for any
+   * track with the same name that has no lyrics of its own.
+   */
+  spotifyTrackId?: Maybe<Scalars['String']>;
+  trackName: Scalars['String'];
+};
+
+export type UpdateTrackLyricsPayload = {
+  __typename?: 'UpdateTrackLyricsPayload';
+  lyrics?: Maybe<Lyrics>;
+  ok: Scalars['Boolean'];
+};
+
 export type User = {
   __typename?: 'User';
   email: Scalars['String'];
@@ -414,12 +859,12 @@ export type HomeHeartSectionQuery = { __typename?: 'Query', heartRate?: { __type
 export type HomeMusicSectionQueryVariables = Exact<{ [key: string]: never; }>;
 
 
-export type HomeMusicSectionQuery = { __typename?: 'Query', musicInfo?: { __typename?: 'MusicInfo', isPlaying: boolean, progress: number, track: { __typename?: 'MusicTrack', spotifyId: string, spotifyUrl: any, name: string, duration: number, album: { __typename?: 'MusicAlbum', spotifyId: string, spotifyUrl: any, name: string }, artists: Array<{ __typename?: 'MusicArtist', spotifyId: string, spotifyUrl: any, name: string }> } } | null | undefined };
+export type HomeMusicSectionQuery = { __typename?: 'Query', musicInfo?: { __typename?: 'MusicInfo', isPlaying: boolean, progress: number, item?: { __typename: 'MusicTrack', spotifyId: string, spotifyUrl: any, name: string, duration: number, album: { __typename?: 'MusicAlbum', spotifyId: string, spotifyUrl: any, name: string }, artists: Array<{ __typename?: 'MusicArtist', spotifyId: string, spotifyUrl: any, name: string }> } | { __typename: 'PodcastEpisode', spotifyId: string, spotifyUrl: any, name: string, duration: number, show: { __typename?: 'PodcastShow', spotifyId: string, spotifyUrl: any, name: string } } | null | undefined } | null | undefined };
 
 export type HomeMusicSectionHeartbeatQueryVariables = Exact<{ [key: string]: never; }>;
 
 
-export type HomeMusicSectionHeartbeatQuery = { __typename?: 'Query', musicInfo?: { __typename?: 'MusicInfo', isPlaying: boolean, progress: number, track: { __typename?: 'MusicTrack', spotifyId: string } } | null | undefined };
+export type HomeMusicSectionHeartbeatQuery = { __typename?: 'Query', musicInfo?: { __typename?: 'MusicInfo', isPlaying: boolean, progress: number, item?: { __typename?: 'MusicTrack', spotifyId: string } | { __typename?: 'PodcastEpisode', spotifyId: string } | null | undefined } | null | undefined };
 
 export type KnowledgeGraphEntryFragment = { __typename?: 'KnowledgeEntry', id: string, tags: Array<string>, links: { __typename?: 'KnowledgeEntryLinks', incoming: Array<{ __typename?: 'KnowledgeEntry', id: string }>, outgoing: Array<{ __typename?: 'KnowledgeEntry', id: string }> } };
 
@@ -431,7 +876,7 @@ export type LayoutFooterQuery = { __typename?: 'Query', viewer?: { __typename?:
 export type MusicLyricsQueryVariables = Exact<{ [key: string]: never; }>;
 
 
-export type MusicLyricsQuery = { __typename?: 'Query', musicInfo?: { __typename?: 'MusicInfo', track: { __typename?: 'MusicTrack', spotifyId: string, lyrics?: { __typename?: 'Lyrics', lines: Array<{ __typename?: 'LyricLine', text: string, position: number }> } | null | undefined } } | null | undefined };
+export type MusicLyricsQuery = { __typename?: 'Query', musicInfo?: { __typename?: 'MusicInfo', item?: { __typename: 'MusicTrack', spotifyId: string, lyrics?: { __typename?: 'Lyrics', synced: boolean, lines: Array<{ __typename?: 'LyricLine', text: string, position: number }> } | null | undefined } | { __typename: 'PodcastEpisode' } | null | undefined } | null | undefined };
 
 export type UpdateFormDialogQueryVariables = Exact<{
   formId: Scalars['ID'];
@@ -454,10 +899,12 @@ export type HomePageQueryVariables = Exact<{
 
 export type HomePageQuery = { __typename?: 'Query', dailyEntry?: { __typename?: 'KnowledgeEntry', id: string, tags: Array<string>, links: { __typename?: 'KnowledgeEntryLinks', incoming: Array<{ __typename?: 'KnowledgeEntry', id: string, tags: Array<string>, links: { __typename?: 'KnowledgeEntryLinks', incoming: Array<{ __typename?: 'KnowledgeEntry', id: string }>, outgoing: Array<{ __typename?: 'KnowledgeEntry', id: string }> } }>, outgoing: Array<{ __typename?: 'KnowledgeEntry', id: string, tags: Array<string>, links: { __typename?: 'KnowledgeEntryLinks', incoming: Array<{ __typename?: 'KnowledgeEntry', id: string }>, outgoing: Array<{ __typename?: 'KnowledgeEntry', id: string }> } }> } } | null | undefined };
 
-export type KnowledgePageQueryVariables = Exact<{ [key: string]: never; }>;
+export type KnowledgePageQueryVariables = Exact<{
+  after?: Maybe<Scalars['String']>;
+}>;
 
 
-export type KnowledgePageQuery = { __typename?: 'Query', entries: Array<{ __typename?: 'KnowledgeEntry', id: string, tags: Array<string>, links: { __typename?: 'KnowledgeEntryLinks', incoming: Array<{ __typename?: 'KnowledgeEntry', id: string }>, outgoing: Array<{ __typename?: 'KnowledgeEntry', id: string }> } }> };
+export type KnowledgePageQuery = { __typename?: 'Query', entries: { __typename?: 'KnowledgeEntryConnection', edges?: Array<{ __typename?: 'KnowledgeEntryEdge', node: { __typename?: 'KnowledgeEntry', id: string, tags: Array<string>, links: { __typename?: 'KnowledgeEntryLinks', incoming: Array<{ __typename?: 'KnowledgeEntry', id: string }>, outgoing: Array<{ __typename?: 'KnowledgeEntry', id: string }> } } } | null | undefined> | null | undefined, pageInfo: { __typename?: 'PageInfo', hasNextPage: boolean, endCursor?: string | null | undefined } } };
 
 export type SubmitFormMutationVariables = Exact<{
   input: SubmitFormInput;
@@ -817,21 +1264,35 @@ export const HomeMusicSectionDocument = gql`
     query HomeMusicSection {
   musicInfo {
     isPlaying
-    track {
-      spotifyId
-      spotifyUrl
-      name
-      album {
+    item {
+      __typename
+      ... on MusicTrack {
         spotifyId
         spotifyUrl
         name
+        album {
+          spotifyId
+          spotifyUrl
+          name
+        }
+        artists {
+          spotifyId
+          spotifyUrl
+          name
+        }
+        duration
       }
-      artists {
+      ... on PodcastEpisode {
         spotifyId
         spotifyUrl
         name
+        show {
+          spotifyId
+          spotifyUrl
+          name
+        }
+        duration
       }
-      duration
     }
     progress
   }
@@ -868,8 +1329,13 @@ export const HomeMusicSectionHeartbeatDocument = gql`
     query HomeMusicSectionHeartbeat {
   musicInfo {
     isPlaying
-    track {
-      spotifyId
+    item {
+      ... on MusicTrack {
+        spotifyId
+      }
+      ... on PodcastEpisode {
+        spotifyId
+      }
     }
     progress
   }
@@ -941,12 +1407,16 @@ export type LayoutFooterQueryResult = Apollo.QueryResult<LayoutFooterQuery, Layo
 export const MusicLyricsDocument = gql`
     query MusicLyrics {
   musicInfo {
-    track {
-      spotifyId
-      lyrics {
-        lines {
-          text
-          position
+    item {
+      __typename
+      ... on MusicTrack {
+        spotifyId
+        lyrics {
+          lines {
+            text
+            position
+          }
+          synced
         }
       }
     }
@@ -1131,10 +1601,18 @@ export type HomePageQueryHookResult = ReturnType<typeof useHomePageQuery>;
 export type HomePageLazyQueryHookResult = ReturnType<typeof useHomePageLazyQuery>;
 export type HomePageQueryResult = Apollo.QueryResult<HomePageQuery, HomePageQueryVariables>;
 export const KnowledgePageDocument = gql`
-    query KnowledgePage {
-  entries: knowledgeEntries {
-    id
-    ...KnowledgeGraphEntry
+    query KnowledgePage($after: String) {
+  entries: knowledgeEntries(first: 100, after: $after) {
+    edges {
+      node {
+        id
+        ...KnowledgeGraphEntry
+      }
+    }
+    pageInfo {
+      hasNextPage
+      endCursor
+    }
   }
 }
     ${KnowledgeGraphEntryFragmentDoc}`;
@@ -1151,6 +1629,7 @@ export const KnowledgePageDocument = gql`
  * @example
  * const { data, loading, error } = useKnowledgePageQuery({
  *   variables: {
+ *      after: // value for 'after'
  *   },
  * });
  */
@@ -0,0 +1,74 @@
+import React, { FC, useState } from "react";
+
+import { HiMusicNote } from "react-icons/hi";
+
+import { BoxProps } from "@chakra-ui/react";
+import { Heading, Icon } from "@chakra-ui/react";
+import { Button } from "@chakra-ui/react";
+
+import { Section, SectionText } from "components/section";
+import { useToast } from "components/toast";
+
+import { apiBaseUrl } from "config";
+
+// Ask the API to start authorizing access to Spotify, returning the URL to
+// send the user to.
+const startSpotifyAuthorization = async (): Promise<string> => {
+  const tokenResponse = await fetch("/api/auth/token");
+  if (tokenResponse.status !== 200) {
+    throw new Error("Not authenticated.");
+  }
+  const token = await tokenResponse.text();
+  const response = await fetch(`${apiBaseUrl}/auth/spotify/start`, {
+    method: "POST",
+    credentials: "include",
+    headers: {
+      authorization: `Bearer ${token}`,
+    },
+  });
+  const body = await response.json();
+  if (!response.ok) {
+    const [firstError] = body.errors ?? [];
+    throw new Error(firstError?.message ?? "Unknown error.");
+  }
+  return body.url;
+};
+
+export type AdminSpotifySectionProps = BoxProps;
+
+export const AdminSpotifySection: FC<AdminSpotifySectionProps> = ({
+  ...otherProps
+}) => {
+  const toast = useToast();
+  const [isLoading, setIsLoading] = useState(false);
+  return (
+    <Section align="stretch" {...otherProps}>
+      <Heading>Spotify</Heading>
+      <SectionText maxW="unset">
+        Authorize access to a Spotify account, for playback and listening
+        history.
+      </SectionText>
+      <Button
+        leftIcon={<Icon as={HiMusicNote} fontSize="lg" />}
+        colorScheme="black"
+        isLoading={isLoading}
+        onClick={async () => {
+          setIsLoading(true);
+          try {
+            const url = await startSpotifyAuthorization();
+            window.location.assign(url);
+          } catch (error) {
+            toast({
+              status: "error",
+              title: "Failed to authorize Spotify",
+              description: error instanceof Error ? error.message : undefined,
+            });
+            setIsLoading(false);
+          }
+        }}
+      >
+        Authorize Spotify
+      </Button>
+    </Section>
+  );
+};
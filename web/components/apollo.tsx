@@ -18,6 +18,7 @@ import { SentryLink } from "apollo-link-sentry";
 import { WebSocketLink as WsLink } from "@apollo/client/link/ws";
 import { setContext as setLinkContext } from "@apollo/client/link/context";
 import { getMainDefinition } from "@apollo/client/utilities";
+import { relayStylePagination } from "@apollo/client/utilities";
 import { InMemoryCache, NormalizedCacheObject } from "@apollo/client";
 import { TypedTypePolicies as TypePolicies } from "apollo/helpers";
 import { split as splitLinks } from "@apollo/client";
@@ -26,7 +27,13 @@ import { from as mergeLinks } from "@apollo/client";
 import { apiBaseUrl } from "config";
 
 const typePolicies: TypePolicies = {
+  Query: {
+    fields: {
+      knowledgeEntries: relayStylePagination(["sort", "folder"]),
+    },
+  },
   KnowledgeEntryLinks: { keyFields: false },
+  KnowledgeEntryUpdate: { keyFields: false },
   MusicInfo: { keyFields: [] },
   MusicTrack: { keyFields: ["spotifyId"] },
   MusicAlbum: { keyFields: ["spotifyId"] },
@@ -20,21 +20,35 @@ gql`
   query HomeMusicSection {
     musicInfo {
       isPlaying
-      track {
-        spotifyId
-        spotifyUrl
-        name
-        album {
+      item {
+        __typename
+        ... on MusicTrack {
           spotifyId
           spotifyUrl
           name
+          album {
+            spotifyId
+            spotifyUrl
+            name
+          }
+          artists {
+            spotifyId
+            spotifyUrl
+            name
+          }
+          duration
         }
-        artists {
+        ... on PodcastEpisode {
           spotifyId
           spotifyUrl
           name
+          show {
+            spotifyId
+            spotifyUrl
+            name
+          }
+          duration
         }
-        duration
       }
       progress
     }
@@ -45,8 +59,13 @@ gql`
   query HomeMusicSectionHeartbeat {
     musicInfo {
       isPlaying
-      track {
-        spotifyId
+      item {
+        ... on MusicTrack {
+          spotifyId
+        }
+        ... on PodcastEpisode {
+          spotifyId
+        }
       }
       progress
     }
@@ -109,9 +128,14 @@ export const HomeMusicSection: FC<HomeMusicSectionProps> = ({
   }, [musicInfo, dataTimestamp]);
 
   const render = (info: NonNullable<HomeMusicSectionQuery["musicInfo"]>) => {
-    const { isPlaying, track } = info;
-    const { duration, artists } = track;
-    const artist = first(artists);
+    const { isPlaying, item } = info;
+    if (!item) {
+      // Nothing is known about what's playing (i.e. an ad).
+      return null;
+    }
+    const { duration } = item;
+    const creator =
+      item.__typename === "MusicTrack" ? first(item.artists) : item.show;
     return (
       <Section {...otherProps}>
         <VStack spacing={1}>
@@ -120,7 +144,9 @@ export const HomeMusicSection: FC<HomeMusicSectionProps> = ({
           </Text>
           {isPlaying && (
             <MusicLyrics
-              trackSpotifyId={track.spotifyId}
+              trackSpotifyId={
+                item.__typename === "MusicTrack" ? item.spotifyId : null
+              }
               progress={interpolatedProgress}
               duration={duration}
             />
@@ -129,22 +155,22 @@ export const HomeMusicSection: FC<HomeMusicSectionProps> = ({
         <SectionText>
           I&apos;m currently listening to{" "}
           <ExternalLink
-            href={track.spotifyUrl}
+            href={item.spotifyUrl}
             _light={{ color: "gray.800" }}
             _dark={{ color: "gray.200" }}
           >
-            {track.name}
+            {item.name}
           </ExternalLink>
-          {artist && (
+          {creator && (
             <>
               {" "}
-              by{" "}
+              {item.__typename === "MusicTrack" ? "by" : "from"}{" "}
               <ExternalLink
-                href={track.spotifyUrl}
+                href={creator.spotifyUrl}
                 _light={{ color: "gray.800" }}
                 _dark={{ color: "gray.200" }}
               >
-                {artist.name}
+                {creator.name}
               </ExternalLink>
               .
             </>
@@ -12,12 +12,16 @@ import { useMusicLyricsQuery } from "apollo/schema";
 gql`
   query MusicLyrics {
     musicInfo {
-      track {
-        spotifyId
-        lyrics {
-          lines {
-            text
-            position
+      item {
+        __typename
+        ... on MusicTrack {
+          spotifyId
+          lyrics {
+            lines {
+              text
+              position
+            }
+            synced
           }
         }
       }
@@ -46,8 +50,12 @@ export const MusicLyrics: FC<MusicLyricsProps> = ({
   });
 
   const line = useMemo(() => {
-    const { lines } = data?.musicInfo?.track?.lyrics ?? {};
-    if (lines && typeof progress === "number") {
+    const { item } = data?.musicInfo ?? {};
+    const lyrics = item?.__typename === "MusicTrack" ? item.lyrics : null;
+
+    // Unsynced lyrics can't be followed along with.
+    if (lyrics?.synced && typeof progress === "number") {
+      const { lines } = lyrics;
       const estimatedProgress = progress - MUSIC_LYRICS_DELAY;
       for (let i = 0; i < lines.length; i++) {
         const { position, text } = lines[i];
@@ -5,6 +5,7 @@ import { Layout } from "components/layout";
 import { useAuthentication } from "components/auth0";
 
 import { AdminResearchSection } from "components/admin-research-section";
+import { AdminSpotifySection } from "components/admin-spotify-section";
 
 const AdminPage: NextPage = () => {
   useAuthentication();
@@ -15,6 +16,7 @@ const AdminPage: NextPage = () => {
       align="center"
     >
       <AdminResearchSection />
+      <AdminSpotifySection />
     </Layout>
   );
 };
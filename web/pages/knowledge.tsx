@@ -1,4 +1,4 @@
-import React from "react";
+import React, { useEffect, useMemo } from "react";
 import type { NextPage } from "next";
 
 import { Center, Spinner } from "@chakra-ui/react";
@@ -10,17 +10,40 @@ import { gql } from "@apollo/client";
 import { useKnowledgePageQuery } from "apollo/schema";
 
 gql`
-  query KnowledgePage {
-    entries: knowledgeEntries {
-      id
-      ...KnowledgeGraphEntry
+  query KnowledgePage($after: String) {
+    entries: knowledgeEntries(first: 100, after: $after) {
+      edges {
+        node {
+          id
+          ...KnowledgeGraphEntry
+        }
+      }
+      pageInfo {
+        hasNextPage
+        endCursor
+      }
     }
   }
 `;
 
 const KnowledgePage: NextPage = () => {
-  const { data } = useKnowledgePageQuery();
-  const { entries } = data ?? {};
+  const { data, fetchMore } = useKnowledgePageQuery();
+  const { hasNextPage, endCursor } = data?.entries.pageInfo ?? {};
+
+  // Load every page of entries, since the graph needs all of them.
+  useEffect(() => {
+    if (hasNextPage && endCursor) {
+      fetchMore({ variables: { after: endCursor } });
+    }
+  }, [hasNextPage, endCursor, fetchMore]);
+
+  const entries = useMemo(() => {
+    if (!data || hasNextPage) {
+      return undefined;
+    }
+    const { edges } = data.entries;
+    return (edges ?? []).flatMap(edge => (edge ? [edge.node] : []));
+  }, [data, hasNextPage]);
 
   return (
     <Center w="100vw" h="100vh">